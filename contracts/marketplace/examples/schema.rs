@@ -0,0 +1,120 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+use std::path::Path;
+
+use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
+
+use human_marketplace::MarketplaceContract;
+use human_marketplace::msg::{
+    AskCountResponse, AskHookExecuteMsg, AskHookMsg, AskInfo, AskOffset, AskOffsetBidCount,
+    AskOffsetSellPrice, AskResponse, AsksResponse, BidExecuteMsg, BidHookMsg, BidOffset,
+    BidResponse, BidsResponse, BundleAskResponse, BundleAsksResponse, CollectionBidExecuteMsg,
+    CollectionBidHookMsg, CollectionConfigResponse, CollectionOffset, CollectionStatusResponse,
+    CollectionsResponse, CustodyResponse, EscrowSummaryResponse, ExecuteMsg, HooksResponse,
+    InstantiateMsg, MigrateMsg, MigrationStatusResponse, OfferExecuteMsg, OfferHookMsg, OfferOffset, OfferResponse,
+    OffersResponse, OrderDigestResponse, OrderKeyResponse, OrderNonceResponse, OrderSignDoc,
+    ParamsResponse, PauseStatusResponse, PayoutSimulationResponse, QueryMsg, ReceiveNftMsg,
+    SaleExecuteMsg, SaleHookMsg, SaleHookMsgV2, SignedOrder, SudoMsg, SwapExecuteMsg, SwapHookMsg, SwapResponse,
+    SwapsResponse, TraitBidExecuteMsg, TraitBidHookMsg, TraitBidOffset, TraitBidResponse,
+    TraitBidsResponse,
+};
+use human_marketplace::state::{Ask, Bid, BundleAsk, CollectionBid, Custody, Offer, SudoParams, Swap, TraitBid};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schemas(&out_dir);
+}
+
+/// Writes the schema of every message, response and hook type to `out_dir`
+pub fn export_schemas(out_dir: &Path) {
+    let out_dir = &out_dir.to_path_buf();
+
+    // Messages
+    export_schema(&schema_for!(InstantiateMsg), out_dir);
+    export_schema(&schema_for!(ExecuteMsg), out_dir);
+    export_schema(&schema_for!(QueryMsg), out_dir);
+    export_schema(&schema_for!(SudoMsg), out_dir);
+    export_schema(&schema_for!(MigrateMsg), out_dir);
+    export_schema(&schema_for!(ReceiveNftMsg), out_dir);
+    export_schema(&schema_for!(AskInfo), out_dir);
+    export_schema(&schema_for!(SignedOrder), out_dir);
+    export_schema(&schema_for!(OrderSignDoc), out_dir);
+
+    // Pagination offsets
+    export_schema(&schema_for!(AskOffset), out_dir);
+    export_schema(&schema_for!(AskOffsetBidCount), out_dir);
+    export_schema(&schema_for!(AskOffsetSellPrice), out_dir);
+    export_schema(&schema_for!(BidOffset), out_dir);
+    export_schema(&schema_for!(CollectionOffset), out_dir);
+    export_schema(&schema_for!(OfferOffset), out_dir);
+    export_schema(&schema_for!(TraitBidOffset), out_dir);
+
+    // Query responses
+    export_schema(&schema_for!(AskResponse), out_dir);
+    export_schema(&schema_for!(AsksResponse), out_dir);
+    export_schema(&schema_for!(AskCountResponse), out_dir);
+    export_schema(&schema_for!(BidResponse), out_dir);
+    export_schema(&schema_for!(BidsResponse), out_dir);
+    export_schema(&schema_for!(BundleAskResponse), out_dir);
+    export_schema(&schema_for!(BundleAsksResponse), out_dir);
+    export_schema(&schema_for!(CollectionsResponse), out_dir);
+    export_schema(&schema_for!(CollectionStatusResponse), out_dir);
+    export_schema(&schema_for!(CollectionConfigResponse), out_dir);
+    export_schema(&schema_for!(CustodyResponse), out_dir);
+    export_schema(&schema_for!(EscrowSummaryResponse), out_dir);
+    export_schema(&schema_for!(HooksResponse), out_dir);
+    export_schema(&schema_for!(MigrationStatusResponse), out_dir);
+    export_schema(&schema_for!(OfferResponse), out_dir);
+    export_schema(&schema_for!(OffersResponse), out_dir);
+    export_schema(&schema_for!(OrderDigestResponse), out_dir);
+    export_schema(&schema_for!(OrderKeyResponse), out_dir);
+    export_schema(&schema_for!(OrderNonceResponse), out_dir);
+    export_schema(&schema_for!(ParamsResponse), out_dir);
+    export_schema(&schema_for!(PauseStatusResponse), out_dir);
+    export_schema(&schema_for!(PayoutSimulationResponse), out_dir);
+    export_schema(&schema_for!(SwapResponse), out_dir);
+    export_schema(&schema_for!(SwapsResponse), out_dir);
+    export_schema(&schema_for!(TraitBidResponse), out_dir);
+    export_schema(&schema_for!(TraitBidsResponse), out_dir);
+
+    // Hooks, as received by hook contracts
+    export_schema(&schema_for!(SaleHookMsg), out_dir);
+    export_schema(&schema_for!(SaleHookMsgV2), out_dir);
+    export_schema(&schema_for!(AskHookMsg), out_dir);
+    export_schema(&schema_for!(BidHookMsg), out_dir);
+    export_schema(&schema_for!(CollectionBidHookMsg), out_dir);
+    export_schema(&schema_for!(OfferHookMsg), out_dir);
+    export_schema(&schema_for!(SwapHookMsg), out_dir);
+    export_schema(&schema_for!(TraitBidHookMsg), out_dir);
+    export_schema_with_title(&schema_for!(SaleExecuteMsg), out_dir, "SaleHookExecuteMsg");
+    export_schema(&schema_for!(AskHookExecuteMsg), out_dir);
+    export_schema_with_title(&schema_for!(BidExecuteMsg), out_dir, "BidHookExecuteMsg");
+    export_schema_with_title(
+        &schema_for!(CollectionBidExecuteMsg),
+        out_dir,
+        "CollectionBidHookExecuteMsg",
+    );
+    export_schema_with_title(&schema_for!(OfferExecuteMsg), out_dir, "OfferHookExecuteMsg");
+    export_schema_with_title(&schema_for!(SwapExecuteMsg), out_dir, "SwapHookExecuteMsg");
+    export_schema_with_title(
+        &schema_for!(TraitBidExecuteMsg),
+        out_dir,
+        "TraitBidHookExecuteMsg",
+    );
+
+    // State
+    export_schema(&schema_for!(Ask), out_dir);
+    export_schema(&schema_for!(Bid), out_dir);
+    export_schema(&schema_for!(BundleAsk), out_dir);
+    export_schema(&schema_for!(CollectionBid), out_dir);
+    export_schema(&schema_for!(Offer), out_dir);
+    export_schema(&schema_for!(Swap), out_dir);
+    export_schema(&schema_for!(Custody), out_dir);
+    export_schema(&schema_for!(TraitBid), out_dir);
+    export_schema(&schema_for!(SudoParams), out_dir);
+    export_schema(&schema_for!(MarketplaceContract), out_dir);
+}
@@ -0,0 +1,387 @@
+use crate::error::ContractError;
+use crate::escrow::{bundle_escrow, track_escrow, Escrowed};
+use crate::execute::{
+    only_nft_owner_of, only_owner_nft, only_unpaused, only_verified_collection,
+    pay_fee_and_royalties, price_validate, simulate_payout, transfer_items, NATIVE_DENOM,
+};
+use crate::msg::{BundleShare, NftRef};
+use crate::state::{
+    bundle_asks, collection_params, BundleAllocation, BundleAsk, NftItem, OrderExpire,
+    SaleType, BUNDLE_COUNT, PAUSE,
+};
+use cosmwasm_std::{
+    coin, Addr, Api, BankMsg, Coin, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, Storage, SubMsg, Uint128,
+};
+use cw_utils::{maybe_addr, may_pay, must_pay, nonpayable};
+
+/// Most NFTs a single bundle ask can hold
+pub const MAX_BUNDLE_ITEMS: u32 = 25;
+
+/// Lists several NFTs as one ask. Every collection must pass the checks of a single listing,
+/// and the NFTs are pulled into escrow through the approvals given to the marketplace.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_set_bundle_ask(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sale_type: SaleType,
+    items: Vec<NftRef>,
+    allocations: Vec<BundleShare>,
+    price: Coin,
+    funds_recipient: Option<String>,
+    expires: u64,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.listing, "listing")?;
+
+    if items.is_empty() {
+        return Err(ContractError::EmptyBatch {});
+    }
+    if items.len() > MAX_BUNDLE_ITEMS as usize {
+        return Err(ContractError::BundleTooLarge(MAX_BUNDLE_ITEMS));
+    }
+
+    let seller = info.sender.clone();
+    let mut bundle_items: Vec<NftItem> = vec![];
+    for NftRef {
+        collection,
+        token_id,
+    } in items
+    {
+        let item = NftItem {
+            collection: deps.api.addr_validate(&collection)?,
+            token_id,
+        };
+        if bundle_items.contains(&item) {
+            return Err(ContractError::DuplicateNft {});
+        }
+
+        only_nft_owner_of(deps.as_ref(), &item, &seller)?;
+
+        bundle_items.push(item);
+    }
+
+    let allocations = validate_allocations(deps.api, &bundle_items, allocations)?;
+
+    // The strictest policy of the bundled collections applies
+    let mut listing_fee = Uint128::zero();
+    let mut min_price = Uint128::zero();
+    for allocation in &allocations {
+        only_verified_collection(deps.as_ref(), &allocation.collection)?;
+        price_validate(deps.storage, &allocation.collection, &price)?;
+
+        let (params, config) = collection_params(deps.storage, &allocation.collection)?;
+        if !config.allows_sale_type(&sale_type) {
+            return Err(ContractError::SaleTypeNotAllowed {});
+        }
+        params.ask_expiry.is_valid(expires)?;
+
+        listing_fee = listing_fee.max(params.listing_fee);
+        min_price = min_price.max(params.min_price);
+    }
+
+    let paid_fee = may_pay(&info, NATIVE_DENOM)?;
+    if paid_fee != listing_fee {
+        return Err(ContractError::InvalidListingFee(paid_fee));
+    }
+
+    let id = BUNDLE_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    BUNDLE_COUNT.save(deps.storage, &id)?;
+
+    let bundle = BundleAsk {
+        id,
+        sale_type,
+        items: bundle_items,
+        allocations,
+        seller: seller.clone(),
+        price: price.amount,
+        funds_recipient: maybe_addr(deps.api, funds_recipient)?,
+        expires_at: env.block.time.plus_seconds(expires),
+        max_bid: Some(min_price),
+        max_bidder: Some(env.contract.address.clone()),
+        bid_count: Uint128::zero(),
+    };
+    store_bundle(deps.storage, &env.contract.address, &bundle)?;
+
+    let mut res = Response::new();
+    transfer_items(&bundle.items, &env.contract.address, &mut res)?;
+
+    Ok(res
+        .add_attribute("human_action", "human_marketplace_set_bundle_ask")
+        .add_attribute("id", id.to_string())
+        .add_attribute("seller", seller)
+        .add_attribute("items", bundle.items.len().to_string())
+        .add_attribute("price", price.amount.to_string())
+        .add_attribute("expires", expires.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// Buys a fixed price bundle, or places an escrowed bid on a bundle auction
+pub fn execute_set_bundle_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.bidding, "bidding")?;
+
+    let bid_price = must_pay(&info, NATIVE_DENOM)?;
+    let mut bundle = bundle_asks()
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::AskNotFound {})?;
+
+    if bundle.is_expired(&env.block) {
+        return Err(ContractError::AskExpired {});
+    }
+
+    let bidder = info.sender;
+    let mut res = Response::new();
+
+    let action = match bundle.sale_type {
+        SaleType::FixedPrice => {
+            only_unpaused(pause.settlement, "settlement")?;
+            if bundle.price != bid_price {
+                return Err(ContractError::InvalidPrice {});
+            }
+
+            remove_bundle(deps.storage, &env.contract.address, &bundle)?;
+            finalize_bundle_sale(deps.as_ref(), &bundle, bid_price, &bidder, &mut res)?;
+            "human_marketplace_buy_bundle"
+        }
+        SaleType::Auction => {
+            if bundle.price > bid_price {
+                return Err(ContractError::PriceTooSmall(bid_price));
+            }
+
+            let (max_bid, max_bidder) = match (bundle.max_bid, bundle.max_bidder.clone()) {
+                (Some(max_bid), Some(max_bidder)) => (max_bid, max_bidder),
+                _ => return Err(ContractError::WrongAskInfo {}),
+            };
+            if bid_price <= max_bid {
+                return Err(ContractError::InsufficientFundsSend {});
+            }
+
+            if max_bidder != env.contract.address {
+                res = res.add_message(BankMsg::Send {
+                    to_address: max_bidder.to_string(),
+                    amount: vec![coin(max_bid.u128(), NATIVE_DENOM)],
+                });
+            }
+
+            bundle.max_bid = Some(bid_price);
+            bundle.max_bidder = Some(bidder.clone());
+            bundle.bid_count += Uint128::new(1);
+            store_bundle(deps.storage, &env.contract.address, &bundle)?;
+            "human_marketplace_set_bundle_bid"
+        }
+    };
+
+    Ok(res
+        .add_attribute("human_action", action)
+        .add_attribute("id", id.to_string())
+        .add_attribute("buyer", bidder)
+        .add_attribute("seller", bundle.seller.to_string())
+        .add_attribute("price", bid_price.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// Removes a bundle ask, returning its NFTs to the seller and refunding the highest bid
+pub fn execute_remove_bundle_ask(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.removal, "removal")?;
+
+    nonpayable(&info)?;
+
+    let bundle = bundle_asks()
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::AskNotFound {})?;
+    only_owner_nft(&info, bundle.seller.clone())?;
+
+    remove_bundle(deps.storage, &env.contract.address, &bundle)?;
+
+    let mut res = Response::new();
+    transfer_items(&bundle.items, &bundle.seller, &mut res)?;
+
+    if let (Some(max_bid), Some(max_bidder)) = (bundle.max_bid, bundle.max_bidder) {
+        if max_bidder != env.contract.address {
+            res = res.add_message(BankMsg::Send {
+                to_address: max_bidder.to_string(),
+                amount: vec![coin(max_bid.u128(), NATIVE_DENOM)],
+            });
+        }
+    }
+
+    Ok(res
+        .add_attribute("human_action", "human_marketplace_remove_bundle_ask")
+        .add_attribute("id", id.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// Settles an ended bundle auction with its highest bidder, or returns the NFTs of a
+/// bundle that did not sell
+pub fn execute_accept_bundle_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+
+    nonpayable(&info)?;
+
+    let bundle = bundle_asks()
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::AskNotFound {})?;
+    only_owner_nft(&info, bundle.seller.clone())?;
+
+    if !bundle.is_expired(&env.block) {
+        return Err(ContractError::AuctionNotEnded {});
+    }
+
+    let mut res = Response::new();
+    match (bundle.max_bid, bundle.max_bidder.clone()) {
+        (Some(max_bid), Some(max_bidder)) if max_bidder != env.contract.address => {
+            only_unpaused(pause.settlement, "settlement")?;
+            remove_bundle(deps.storage, &env.contract.address, &bundle)?;
+            finalize_bundle_sale(deps.as_ref(), &bundle, max_bid, &max_bidder, &mut res)?;
+
+            res = res
+                .add_attribute("human_action", "human_marketplace_accept_bundle_bid")
+                .add_attribute("id", id.to_string())
+                .add_attribute("buyer", max_bidder)
+                .add_attribute("seller", bundle.seller.to_string())
+                .add_attribute("price", max_bid)
+                .add_attribute("time", env.block.time.to_string());
+        }
+        // Returning unsold NFTs is an exit and stays available while settlement is paused
+        _ => {
+            remove_bundle(deps.storage, &env.contract.address, &bundle)?;
+            transfer_items(&bundle.items, &bundle.seller, &mut res)?;
+        }
+    }
+
+    Ok(res)
+}
+
+/// Pays out a bundle sale and transfers every NFT to the buyer. Each collection's share of
+/// the price is paid out like a single sale of its first NFT in the bundle, the last
+/// collection takes the rounding remainder so the whole price is allocated.
+fn finalize_bundle_sale(
+    deps: Deps,
+    bundle: &BundleAsk,
+    price: Uint128,
+    buyer: &Addr,
+    res: &mut Response,
+) -> StdResult<()> {
+    let mut allocated = Uint128::zero();
+    let mut seller_amount = Uint128::zero();
+    for (i, allocation) in bundle.allocations.iter().enumerate() {
+        let portion = if i + 1 == bundle.allocations.len() {
+            price - allocated
+        } else {
+            price * allocation.share
+        };
+        allocated += portion;
+
+        let token_id = bundle
+            .items
+            .iter()
+            .find(|item| item.collection == allocation.collection)
+            .map(|item| item.token_id.as_str())
+            .unwrap_or_default();
+        let breakdown = simulate_payout(deps, &allocation.collection, token_id, portion)?;
+        pay_fee_and_royalties(&allocation.collection, &breakdown, res);
+        seller_amount += breakdown.seller_amount;
+    }
+
+    if !seller_amount.is_zero() {
+        let recipient = bundle.funds_recipient.as_ref().unwrap_or(&bundle.seller);
+        res.messages.push(SubMsg::new(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![coin(seller_amount.u128(), NATIVE_DENOM)],
+        }));
+    }
+
+    transfer_items(&bundle.items, buyer, res)
+}
+
+/// Checks the declared shares cover every bundled collection exactly once and sum to 1.
+/// A bundle of a single collection may omit them.
+fn validate_allocations(
+    api: &dyn Api,
+    items: &[NftItem],
+    shares: Vec<BundleShare>,
+) -> Result<Vec<BundleAllocation>, ContractError> {
+    let mut collections: Vec<&Addr> = vec![];
+    for item in items {
+        if !collections.contains(&&item.collection) {
+            collections.push(&item.collection);
+        }
+    }
+
+    if shares.is_empty() && collections.len() == 1 {
+        return Ok(vec![BundleAllocation {
+            collection: collections[0].clone(),
+            share: Decimal::one(),
+        }]);
+    }
+
+    let mut allocations: Vec<BundleAllocation> = vec![];
+    for BundleShare { collection, share } in shares {
+        let collection = api.addr_validate(&collection)?;
+        if share.is_zero()
+            || !collections.contains(&&collection)
+            || allocations.iter().any(|a| a.collection == collection)
+        {
+            return Err(ContractError::InvalidBundleAllocation {});
+        }
+        allocations.push(BundleAllocation { collection, share });
+    }
+
+    let total = allocations
+        .iter()
+        .fold(Decimal::zero(), |total, a| total + a.share);
+    if allocations.len() != collections.len() || total != Decimal::one() {
+        return Err(ContractError::InvalidBundleAllocation {});
+    }
+
+    Ok(allocations)
+}
+
+/// Saves a bundle ask, the funds of its highest bid count towards the bundle escrow
+fn store_bundle(
+    store: &mut dyn Storage,
+    marketplace: &Addr,
+    bundle: &BundleAsk,
+) -> StdResult<()> {
+    let before = bundle_asks().may_load(store, bundle.id)?;
+    bundle_asks().save(store, bundle.id, bundle)?;
+    track_escrow(
+        store,
+        Escrowed::Bundle(bundle.id),
+        before.map_or(Uint128::zero(), |b| bundle_escrow(&b, marketplace)),
+        bundle_escrow(bundle, marketplace),
+    )
+}
+
+/// Removes a bundle ask, releasing the funds of its highest bid from the bundle escrow
+fn remove_bundle(
+    store: &mut dyn Storage,
+    marketplace: &Addr,
+    bundle: &BundleAsk,
+) -> StdResult<()> {
+    bundle_asks().remove(store, bundle.id)?;
+    track_escrow(
+        store,
+        Escrowed::Bundle(bundle.id),
+        bundle_escrow(bundle, marketplace),
+        Uint128::zero(),
+    )
+}
@@ -16,6 +16,9 @@ pub enum ContractError {
     #[error("UnauthorizedOperator")]
     UnauthorizedOperator {},
 
+    #[error("UnauthorizedAdmin")]
+    UnauthorizedAdmin {},
+
     #[error("InvalidPrice")]
     InvalidPrice {},
 
@@ -55,9 +58,12 @@ pub enum ContractError {
     #[error("Invalid finders fee bps: {0}")]
     InvalidFindersFeeBps(u64),
 
-    #[error("Invalid finders fee bps: {0}")]
+    #[error("Invalid trading fee bps: {0}")]
     InvalidTradingFeeBps(u64),
 
+    #[error("Royalty cap cannot exceed 100%")]
+    InvalidRoyaltyCap {},
+
     #[error("Invalid finders fee bps: {0}")]
     InvalidBidRemovalRewardBps(u64),
 
@@ -85,6 +91,108 @@ pub enum ContractError {
     #[error("Insufficient funds sent")]
     InsufficientFundsSend {},   
 
+    #[error("Batch cannot be empty")]
+    EmptyBatch {},
+
+    #[error("Batch cannot hold more than {0} items")]
+    BatchTooLarge(u32),
+
+    #[error("Batch payment of {received} does not match the total price {expected}")]
+    InvalidBatchPayment { expected: Uint128, received: Uint128 },
+
+    #[error("Bundle cannot hold more than {0} NFTs")]
+    BundleTooLarge(u32),
+
+    #[error("Swap cannot offer or request more than {0} NFTs")]
+    SwapTooLarge(u32),
+
+    #[error("NFT appears more than once")]
+    DuplicateNft {},
+
+    #[error("Bundle allocations must cover each collection once with shares summing to 1")]
+    InvalidBundleAllocation {},
+
+    #[error("SwapNotFound")]
+    SwapNotFound {},
+
+    #[error("SwapExpired")]
+    SwapExpired {},
+
+    #[error("Swap can only be accepted by its counterparty")]
+    UnauthorizedCounterparty {},
+
+    #[error("Swap cannot be accepted by its proposer")]
+    SwapSelfAccept {},
+
+    #[error("NFT was not requested by the swap")]
+    SwapTokenNotRequested {},
+
+    #[error("OfferNotFound")]
+    OfferNotFound {},
+
+    #[error("OfferExpired")]
+    OfferExpired {},
+
+    #[error("Offers can only be made on fixed price asks")]
+    OfferOnAuction {},
+
+    #[error("Offer must be below the ask price")]
+    OfferNotBelowAsk {},
+
+    #[error("Counter price must be above the offer and below the ask price")]
+    InvalidCounterPrice {},
+
+    #[error("No counter offer for this offer")]
+    NoCounterOffer {},
+
+    #[error("Amount exceeds the surplus above escrowed funds: {0}")]
+    InsufficientSurplus(Uint128),
+
+    #[error("Escrowed funds are still being counted by MigrateRecords")]
+    EscrowNotCounted {},
+
+    #[error("TraitBidNotFound")]
+    TraitBidNotFound {},
+
+    #[error("TraitBidExpired")]
+    TraitBidExpired {},
+
+    #[error("NFT does not have the trait of the bid")]
+    TraitMismatch {},
+
+    #[error("NFT is still listed, remove the ask instead")]
+    AskStillListed {},
+
+    #[error("No custody record for this NFT")]
+    NoCustodyRecord {},
+
+    #[error("NFT is not held by the marketplace")]
+    NftNotInCustody {},
+
+    #[error("Operators can only rescue an NFT to its depositor")]
+    RescueRecipientNotDepositor {},
+
+    #[error("Seller has no order signing key")]
+    NoOrderKey {},
+
+    #[error("Order signing key must be a 33 or 65 byte secp256k1 public key")]
+    InvalidOrderKey {},
+
+    #[error("Invalid order signature")]
+    InvalidOrderSignature {},
+
+    #[error("OrderExpired")]
+    OrderExpired {},
+
+    #[error("Order nonce {0} was already used")]
+    OrderNonceUsed(u64),
+
+    #[error("Order nonce {0} was cancelled")]
+    OrderNonceCancelled(u64),
+
+    #[error("Cannot decode ReceiveNft payload: {0}")]
+    InvalidReceiveNftMsg(String),
+
     #[error("Wrong ask info")]
     WrongAskInfo {},
 
@@ -102,7 +210,31 @@ pub enum ContractError {
     
     #[error("contract not found")]
     ContractNotFound {},
+
+    #[error("collection is denied")]
+    CollectionDenied {},
+
+    #[error("collection is disabled: {reason}")]
+    CollectionDisabled { reason: String },
+
+    #[error("Marketplace {0} is paused")]
+    Paused(String),
+
+    #[error("Sale type not allowed for this collection")]
+    SaleTypeNotAllowed {},
+
+    #[error("Invalid protocol fee discount bps: {0}")]
+    InvalidFeeDiscountBps(u64),
+
+    #[error("Protocol fee discount can only be set by governance")]
+    FeeDiscountGovernanceOnly {},
     
     #[error("Cannot migrate from different contract type: {previous_contract}")]
     CannotMigrate { previous_contract: String },
+
+    #[error("Cannot migrate from version {from} down to {to}")]
+    CannotMigrateDowngrade { from: String, to: String },
+
+    #[error("No record migration in progress")]
+    NoRecordMigration {},
 }
@@ -0,0 +1,172 @@
+use crate::error::ContractError;
+use crate::execute::NATIVE_DENOM;
+use crate::msg::EscrowSummaryResponse;
+use crate::state::{
+    Bid, BidKey, BundleAsk, EscrowTotals, OfferKey, RecordMigration, ESCROW_TOTALS,
+    RECORD_MIGRATION,
+};
+use cosmwasm_std::{
+    coin, Addr, BankMsg, Deps, DepsMut, Env, Response, StdResult, Storage, Uint128,
+};
+use cw_storage_plus::PrimaryKey;
+
+/// A record holding funds for someone, by its key
+pub(crate) enum Escrowed<'a> {
+    Bid(&'a BidKey),
+    Bundle(u64),
+    Offer(&'a OfferKey),
+    Swap(u64),
+    TraitBid(u64),
+}
+
+impl Escrowed<'_> {
+    /// Position of the record kind in the escrow count of `MigrateRecords` and its key
+    fn count_position(&self) -> (u8, Vec<u8>) {
+        match self {
+            Escrowed::Bid(key) => (1, key.joined_key()),
+            Escrowed::Bundle(id) => (2, id.joined_key()),
+            Escrowed::Offer(key) => (3, key.joined_key()),
+            Escrowed::Swap(id) => (4, id.joined_key()),
+            Escrowed::TraitBid(id) => (5, id.joined_key()),
+        }
+    }
+
+    fn total<'t>(&self, totals: &'t mut EscrowTotals) -> &'t mut Uint128 {
+        match self {
+            Escrowed::Bid(_) => &mut totals.auction,
+            Escrowed::Bundle(_) => &mut totals.bundle,
+            Escrowed::Offer(_) => &mut totals.offer,
+            Escrowed::Swap(_) => &mut totals.swap,
+            Escrowed::TraitBid(_) => &mut totals.trait_bid,
+        }
+    }
+}
+
+/// Funds a bid holds. Only the highest bid of an auction is active, outbid bids were
+/// refunded when they were outbid.
+pub(crate) fn bid_escrow(bid: &Bid) -> Uint128 {
+    if bid.active {
+        bid.price
+    } else {
+        Uint128::zero()
+    }
+}
+
+/// Funds a bundle ask holds, its highest bid unless that is still the marketplace's
+/// placeholder for the minimum price
+pub(crate) fn bundle_escrow(bundle: &BundleAsk, marketplace: &Addr) -> Uint128 {
+    match (bundle.max_bid, &bundle.max_bidder) {
+        (Some(max_bid), Some(max_bidder)) if max_bidder != marketplace => max_bid,
+        _ => Uint128::zero(),
+    }
+}
+
+/// Moves the running escrow totals from what a record held before a change to what it holds
+/// after it. Records the escrow count of `MigrateRecords` has not reached yet are left to it.
+pub(crate) fn track_escrow(
+    store: &mut dyn Storage,
+    record: Escrowed,
+    before: Uint128,
+    after: Uint128,
+) -> StdResult<()> {
+    if before == after || !is_counted(store, &record)? {
+        return Ok(());
+    }
+
+    let mut totals = ESCROW_TOTALS.may_load(store)?.unwrap_or_default();
+    let total = record.total(&mut totals);
+    *total = total.checked_sub(before)?.checked_add(after)?;
+    ESCROW_TOTALS.save(store, &totals)
+}
+
+/// Adds the funds of a record the escrow count of `MigrateRecords` reached to the totals
+pub(crate) fn add_counted_escrow(
+    store: &mut dyn Storage,
+    record: Escrowed,
+    amount: Uint128,
+) -> StdResult<()> {
+    let mut totals = ESCROW_TOTALS.may_load(store)?.unwrap_or_default();
+    let total = record.total(&mut totals);
+    *total = total.checked_add(amount)?;
+    ESCROW_TOTALS.save(store, &totals)
+}
+
+/// Whether the running totals include the record, they include every record once the
+/// escrow count is done
+fn is_counted(store: &dyn Storage, record: &Escrowed) -> StdResult<bool> {
+    let (stage, cursor) = match RECORD_MIGRATION.may_load(store)? {
+        None => return Ok(true),
+        Some(RecordMigration::Asks { .. }) => (0, None),
+        Some(RecordMigration::Bids { start_after }) => (1, start_after.map(|k| k.joined_key())),
+        Some(RecordMigration::Bundles { start_after }) => (2, start_after.map(|k| k.joined_key())),
+        Some(RecordMigration::Offers { start_after }) => (3, start_after.map(|k| k.joined_key())),
+        Some(RecordMigration::Swaps { start_after }) => (4, start_after.map(|k| k.joined_key())),
+        Some(RecordMigration::TraitBids { start_after }) => {
+            (5, start_after.map(|k| k.joined_key()))
+        }
+    };
+
+    let (position, key) = record.count_position();
+    Ok(position < stage || (position == stage && cursor.map_or(false, |c| key <= c)))
+}
+
+/// Compares the native balance of the contract with the funds it holds for others: the
+/// active bid of every auction, the highest bid of every bundle auction, escrowed offers,
+/// swap funds and trait bids. The totals are kept as funds are escrowed and released, so
+/// this stays cheap however many records are stored. What is left above the liabilities
+/// are collected listing fees.
+pub fn escrow_summary(deps: Deps, env: &Env) -> StdResult<EscrowSummaryResponse> {
+    let totals = ESCROW_TOTALS.may_load(deps.storage)?.unwrap_or_default();
+    let counting = RECORD_MIGRATION.may_load(deps.storage)?.is_some();
+
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, NATIVE_DENOM)?
+        .amount;
+    let liabilities =
+        totals.auction + totals.bundle + totals.offer + totals.swap + totals.trait_bid;
+
+    Ok(EscrowSummaryResponse {
+        balance,
+        auction_escrow: totals.auction,
+        bundle_escrow: totals.bundle,
+        offer_escrow: totals.offer,
+        swap_escrow: totals.swap,
+        trait_bid_escrow: totals.trait_bid,
+        liabilities,
+        surplus: balance.saturating_sub(liabilities),
+        deficit: liabilities.saturating_sub(balance),
+        counting,
+    })
+}
+
+/// Sends funds above the escrow liabilities to `recipient`, all of the surplus unless
+/// `amount` is set. Escrowed funds can never be moved this way, nor can anything be moved
+/// before `MigrateRecords` has counted the escrow of records stored by earlier versions.
+pub fn sudo_recover_surplus(
+    deps: DepsMut,
+    env: Env,
+    recipient: Addr,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let summary = escrow_summary(deps.as_ref(), &env)?;
+    if summary.counting {
+        return Err(ContractError::EscrowNotCounted {});
+    }
+    let amount = amount.unwrap_or(summary.surplus);
+    if amount.is_zero() || amount > summary.surplus {
+        return Err(ContractError::InsufficientSurplus(summary.surplus));
+    }
+
+    let send_msg = BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![coin(amount.u128(), NATIVE_DENOM)],
+    };
+
+    Ok(Response::new()
+        .add_message(send_msg)
+        .add_attribute("action", "recover_surplus")
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("liabilities", summary.liabilities.to_string()))
+}
@@ -1,37 +1,65 @@
+use crate::bundle::{
+    execute_accept_bundle_bid, execute_remove_bundle_ask, execute_set_bundle_ask,
+    execute_set_bundle_bid,
+};
 use crate::error::ContractError;
-use crate::helpers::{map_validate, ExpiryRange};
+use crate::escrow::{bid_escrow, track_escrow, Escrowed};
+use crate::offer::{
+    execute_accept_counter_offer, execute_accept_offer, execute_counter_offer,
+    execute_remove_offer, execute_set_offer,
+};
+use crate::signed_order::{
+    execute_cancel_signed_orders, execute_fill_signed_order, execute_set_order_key,
+};
+use crate::swap::{
+    execute_accept_swap, execute_accept_swap_with_nft, execute_cancel_swap, execute_create_swap,
+};
+use crate::trait_bid::{
+    execute_accept_trait_bid, execute_remove_trait_bid, execute_set_trait_bid,
+};
+use crate::helpers::map_validate;
 use crate::msg::{
-    AskHookMsg, BidHookMsg, ExecuteMsg, HookAction, InstantiateMsg,
-    SaleHookMsg, NftInfoResponse, Metadata, CreateCollectionQueryMsg, MigrateMsg
+    AskHookMsg, AskPriceUpdate, BatchBid, BidHookMsg, ExecuteMsg, NftRef, HookAction, InstantiateMsg,
+    SaleHookMsg, SaleHookMsgV2, NftInfoResponse, Metadata, CreateCollectionQueryMsg,
+    CollectionStatus, CollectionVerification, PayoutSimulationResponse, RoyaltyPayout,
+    Cw2981QueryMsg, RoyaltiesInfoResponse, RoyaltySource, SwapHookMsg, OfferHookMsg, TraitBidHookMsg, AskInfo, BidInfo, ReceiveNftMsg, ReceiveNftMsgV1
 };
+use crate::migrate::execute_migrate_records;
+use crate::rescue::execute_rescue_nft;
 use crate::query::query_all_bids;
 use crate::state::{
-    ask_key, asks, bid_key, bids, Ask, Bid, OrderExpire, SaleType, SudoParams, TokenId, ASK_HOOKS, BID_HOOKS, SALE_HOOKS,
-    SUDO_PARAMS
+    ask_key, asks, bid_key, bids, collection_params, Ask, AskKey, Bid, BidKey, CollectionConfig, CollectionList,
+    OrderExpire, SaleOrigin, SaleType, SudoParams, TokenId, ASK_HOOKS, BID_HOOKS, COLLECTION_CONFIGS,
+    COLLECTION_REGISTRY, PAUSE, SALE_HOOKS, SALE_HOOKS_V2, SUDO_PARAMS, NftItem, Swap, SWAP_HOOKS, Offer, OFFER_HOOKS,
+    TraitBid, TRAIT_BID_HOOKS, Custody, CUSTODY,
 };
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coin, to_binary, Addr, BankMsg, Coin, Decimal, Deps, DepsMut, Empty, Env, Event, MessageInfo,
-    Reply, StdError, StdResult, Storage,  Uint128, WasmMsg, Response, SubMsg, from_binary, CosmosMsg
+    coin, to_binary, Addr, BankMsg, Coin, Decimal, Deps, DepsMut, Env, Event, MessageInfo, Order,
+    Reply, StdError, StdResult, Storage,  Uint128, WasmMsg, Response, SubMsg, CosmosMsg
 };
-use cw2::{set_contract_version,get_contract_version};
+use cw2::set_contract_version;
 use cw721_base::ExecuteMsg as Cw721ExecuteMsg;
 use cw721_base::QueryMsg as Cw721QueryMsg;
 use cw721_base::CollectionInfoResponse;
-use cw721::{Cw721ReceiveMsg};
+use cw721_base::msg::AdminResponse;
+use cw721::{Cw721ReceiveMsg, OwnerOfResponse};
 
-use cw_utils::{may_pay, must_pay, nonpayable, Duration};
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use cw_utils::{may_pay, must_pay, nonpayable};
 // use sg1::fair_burn;
 
 pub const NATIVE_DENOM: &str = "uheart";
+pub const MAX_FEE_BPS: u64 = 10000;
+/// Most asks a single `SweepFloor` buys
+pub const MAX_SWEEP_ITEMS: u32 = 50;
+/// Most items a single batch message handles
+pub const MAX_BATCH: u32 = 50;
 
 // Version info for migration info
-const CONTRACT_NAME: &str = "crates.io:human-marketplace";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const CONTRACT_NAME: &str = "crates.io:human-marketplace";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -47,15 +75,25 @@ pub fn instantiate(
 
     deps.api.addr_validate(&msg.create_collection_address)?;
 
+    if msg.trading_fee_bps > MAX_FEE_BPS {
+        return Err(ContractError::InvalidTradingFeeBps(msg.trading_fee_bps));
+    }
+
     let params = SudoParams {
-        // trading_fee_percent: Decimal::percent(msg.trading_fee_bps),
+        trading_fee_percent: Decimal::from_ratio(msg.trading_fee_bps, MAX_FEE_BPS),
+        fee_recipient: msg
+            .fee_recipient
+            .map(|r| deps.api.addr_validate(&r))
+            .transpose()?,
         ask_expiry: msg.ask_expiry,
         bid_expiry: msg.bid_expiry,
         operators: map_validate(deps.api, &msg.operators)?,
         // max_finders_fee_percent: Decimal::percent(msg.max_finders_fee_bps),
         min_price: msg.min_price,
         listing_fee: msg.listing_fee,
-        create_collection_address: msg.create_collection_address
+        create_collection_address: msg.create_collection_address,
+        require_factory_check: false,
+        max_royalty_rate: None,
     };
     SUDO_PARAMS.save(deps.storage, &params)?;
 
@@ -66,25 +104,6 @@ pub fn instantiate(
     Ok(Response::new())
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-
-pub struct AskInfo {
-    pub sale_type: SaleType,
-    pub collection: Addr,
-    pub token_id: TokenId,
-    pub price: Coin,
-    pub funds_recipient: Option<Addr>,
-    pub expires: u64,
-}
-
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-
-pub struct BidInfo {
-    collection: Addr,
-    token_id: TokenId,
-}
-
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -95,7 +114,7 @@ pub fn execute(
     let api = deps.api;
 
     match msg {
-        ExecuteMsg::ReceiveNft(rcv_msg) => execute_set_ask(deps, env, info, rcv_msg),
+        ExecuteMsg::ReceiveNft(rcv_msg) => execute_receive_nft(deps, env, info, rcv_msg),
         
         ExecuteMsg::RemoveAsk {
             collection,
@@ -139,6 +158,150 @@ pub fn execute(
             token_id,
             price,
         ),
+        ExecuteMsg::BatchSetBid { bids, atomic } => {
+            execute_batch_set_bid(deps, env, info, bids, atomic)
+        }
+        ExecuteMsg::SweepFloor {
+            collection,
+            max_items,
+            max_total,
+            max_unit_price,
+        } => execute_sweep_floor(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            max_items,
+            max_total,
+            max_unit_price,
+        ),
+        ExecuteMsg::SetBundleAsk {
+            sale_type,
+            items,
+            allocations,
+            price,
+            funds_recipient,
+            expires,
+        } => execute_set_bundle_ask(
+            deps,
+            env,
+            info,
+            sale_type,
+            items,
+            allocations,
+            price,
+            funds_recipient,
+            expires,
+        ),
+        ExecuteMsg::SetBundleBid { id } => execute_set_bundle_bid(deps, env, info, id),
+        ExecuteMsg::RemoveBundleAsk { id } => execute_remove_bundle_ask(deps, env, info, id),
+        ExecuteMsg::AcceptBundleBid { id } => execute_accept_bundle_bid(deps, env, info, id),
+        ExecuteMsg::CreateSwap {
+            offered,
+            requested,
+            counterparty,
+            expires,
+        } => execute_create_swap(deps, env, info, offered, requested, counterparty, expires),
+        ExecuteMsg::AcceptSwap { id } => execute_accept_swap(deps, env, info, id),
+        ExecuteMsg::CancelSwap { id } => execute_cancel_swap(deps, env, info, id),
+        ExecuteMsg::SetOffer {
+            collection,
+            token_id,
+            expires,
+        } => execute_set_offer(deps, env, info, api.addr_validate(&collection)?, token_id, expires),
+        ExecuteMsg::RemoveOffer {
+            collection,
+            token_id,
+        } => execute_remove_offer(deps, env, info, api.addr_validate(&collection)?, token_id),
+        ExecuteMsg::AcceptOffer {
+            collection,
+            token_id,
+            buyer,
+        } => execute_accept_offer(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            token_id,
+            api.addr_validate(&buyer)?,
+        ),
+        ExecuteMsg::CounterOffer {
+            collection,
+            token_id,
+            buyer,
+            price,
+        } => execute_counter_offer(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            token_id,
+            api.addr_validate(&buyer)?,
+            price,
+        ),
+        ExecuteMsg::AcceptCounterOffer {
+            collection,
+            token_id,
+        } => execute_accept_counter_offer(deps, env, info, api.addr_validate(&collection)?, token_id),
+        ExecuteMsg::SetTraitBid {
+            collection,
+            trait_type,
+            value,
+            expires,
+        } => execute_set_trait_bid(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            trait_type,
+            value,
+            expires,
+        ),
+        ExecuteMsg::RemoveTraitBid { id } => execute_remove_trait_bid(deps, env, info, id),
+        ExecuteMsg::SetOrderKey { pubkey } => execute_set_order_key(deps, info, pubkey),
+        ExecuteMsg::CancelSignedOrders { nonces } => execute_cancel_signed_orders(deps, info, nonces),
+        ExecuteMsg::FillSignedOrder { order, signature } => {
+            execute_fill_signed_order(deps, env, info, order, signature)
+        }
+        ExecuteMsg::BatchSetAsk { asks } => execute_batch_set_ask(deps, env, info, asks),
+        ExecuteMsg::BatchRemoveAsk { asks } => execute_batch_remove_ask(deps, env, info, asks),
+        ExecuteMsg::BatchUpdateAskPrice { updates } => {
+            execute_batch_update_ask_price(deps, env, info, updates)
+        }
+        ExecuteMsg::SetCollectionConfig { collection, config } => {
+            execute_set_collection_config(deps, info, api.addr_validate(&collection)?, config)
+        }
+        ExecuteMsg::MigrateRecords { limit } => execute_migrate_records(deps, env, info, limit),
+        ExecuteMsg::RescueNft {
+            collection,
+            token_id,
+            recipient,
+        } => execute_rescue_nft(
+            deps,
+            env,
+            info,
+            api.addr_validate(&collection)?,
+            token_id,
+            api.addr_validate(&recipient)?,
+        ),
+    }
+}
+
+/// Dispatches a received NFT on its `ReceiveNftMsg` payload
+pub fn execute_receive_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rcv_msg: Cw721ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match ReceiveNftMsg::decode(&rcv_msg.msg)? {
+        ReceiveNftMsgV1::SetAsk(ask_info) => execute_set_ask(deps, env, info, rcv_msg, ask_info),
+        ReceiveNftMsgV1::AcceptSwap { id } => {
+            execute_accept_swap_with_nft(deps, env, info, rcv_msg, id)
+        }
+        ReceiveNftMsgV1::AcceptTraitBid { id } => {
+            execute_accept_trait_bid(deps, env, info, rcv_msg, id)
+        }
     }
 }
 
@@ -148,22 +311,14 @@ pub fn execute_set_ask(
     env: Env,
     info: MessageInfo,
     rcv_msg: Cw721ReceiveMsg,
+    ask_info: AskInfo,
 ) -> Result<Response, ContractError> {
-    let ask_info: AskInfo = from_binary(&rcv_msg.msg)?;
-    let collection_address = info.clone().sender;
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.listing, "listing")?;
 
-    let params = SUDO_PARAMS.load(deps.storage)?;
-    
-    let AskInfo {
-        sale_type,
-        collection,
-        token_id,
-        price,
-        funds_recipient,
-        expires,
-    } = ask_info.clone();
+    let collection_address = info.clone().sender;
 
-    if rcv_msg.token_id != token_id {
+    if rcv_msg.token_id != ask_info.token_id {
         return Err(ContractError::IdMismatch{});
     }
 
@@ -171,48 +326,123 @@ pub fn execute_set_ask(
         return Err(ContractError::CollectionMismatch{} );
     }
 
-    let is_exist: bool = deps
-        .querier
-        .query_wasm_smart(params.create_collection_address, &CreateCollectionQueryMsg::CheckCollection { address: collection_address.to_string()  })?;
+    // Check if msg has correct listing fee
+    let listing_fee = may_pay(&info, NATIVE_DENOM)?;
+    if listing_fee != validate_ask(deps.as_ref(), &ask_info)? {
+        return Err(ContractError::InvalidListingFee(listing_fee));
+    }
+
+    let seller = deps.api.addr_validate(rcv_msg.sender.as_str())?;
+    list_ask(deps, &env, seller, ask_info)
+}
+
+/// Lists several NFTs of the sender at once, failing as a whole if any listing is rejected.
+/// The NFTs are pulled into escrow through the approvals given to the marketplace, and the
+/// funds sent must equal the summed listing fees.
+pub fn execute_batch_set_ask(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asks: Vec<AskInfo>,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.listing, "listing")?;
+    validate_batch_size(asks.len())?;
+
+    let seller = info.sender.clone();
+    let mut items: Vec<NftItem> = vec![];
+    let mut listing_fees = Uint128::zero();
+    for ask_info in &asks {
+        let item = NftItem {
+            collection: deps.api.addr_validate(ask_info.collection.as_str())?,
+            token_id: ask_info.token_id.clone(),
+        };
+        if items.contains(&item) {
+            return Err(ContractError::DuplicateNft {});
+        }
+
+        only_nft_owner_of(deps.as_ref(), &item, &seller)?;
+        let listing_fee = validate_ask(deps.as_ref(), ask_info)?;
+        listing_fees = listing_fees.checked_add(listing_fee).map_err(StdError::from)?;
 
-    if is_exist != true{
-        return Err(ContractError::ContractNotFound{})
+        items.push(item);
     }
 
-    price_validate(deps.storage, &price)?;
+    let paid_fee = may_pay(&info, NATIVE_DENOM)?;
+    if paid_fee != listing_fees {
+        return Err(ContractError::InvalidListingFee(paid_fee));
+    }
 
-    let params = SUDO_PARAMS.load(deps.storage)?;
-    params.ask_expiry.is_valid(expires)?;
+    let mut res = Response::new().add_attribute("human_action", "human_marketplace_batch_set_ask");
+    transfer_items(&items, &env.contract.address, &mut res)?;
+    for ask_info in asks {
+        let item = list_ask(deps.branch(), &env, seller.clone(), ask_info)?;
+        res = merge_batch_item(res, item, "batch-set-ask");
+    }
 
-    // Check if msg has correct listing fee
-    let listing_fee = may_pay(&info, NATIVE_DENOM)?;
-    if listing_fee != params.listing_fee {
-        return Err(ContractError::InvalidListingFee(listing_fee));
+    Ok(res)
+}
+
+/// Checks a listing against the policy of its collection, returning the listing fee it owes
+fn validate_ask(deps: Deps, ask_info: &AskInfo) -> Result<Uint128, ContractError> {
+    let collection = &ask_info.collection;
+    only_verified_collection(deps, collection)?;
+
+    price_validate(deps.storage, collection, &ask_info.price)?;
+
+    let (params, config) = collection_params(deps.storage, collection)?;
+    if !config.allows_sale_type(&ask_info.sale_type) {
+        return Err(ContractError::SaleTypeNotAllowed {});
     }
+    params.ask_expiry.is_valid(ask_info.expires)?;
 
+    Ok(params.listing_fee)
+}
+
+/// Stores the ask of a validated listing whose NFT the marketplace holds or is about to receive
+fn list_ask(
+    deps: DepsMut,
+    env: &Env,
+    seller: Addr,
+    ask_info: AskInfo,
+) -> Result<Response, ContractError> {
+    let AskInfo {
+        sale_type,
+        collection,
+        token_id,
+        price,
+        funds_recipient,
+        expires,
+    } = ask_info;
+
+    let (params, _) = collection_params(deps.storage, &collection)?;
     let now = env.block.time;
 
     let nft_info: NftInfoResponse<Metadata> = deps
         .querier
         .query_wasm_smart(collection.clone(), &Cw721QueryMsg::NftInfo { token_id: token_id.clone() })?;
 
-    let seller = deps.api.addr_validate(rcv_msg.sender.as_str())?;
-    
     let ask = Ask {
         sale_type,
         collection: collection.clone(),
         token_id: token_id.clone(),
         img_url: nft_info.extension.image_url, 
-        seller: deps.api.addr_validate(rcv_msg.sender.as_str())?,
+        seller: seller.clone(),
         price: price.amount,
         funds_recipient,
         expires_at: now.plus_seconds(expires),
         max_bidder: Some(env.contract.address.clone()),
         max_bid: Some(params.min_price),
         bid_count: Uint128::zero(),
-        content_type: nft_info.content_type
+        content_type: nft_info.content_type,
+        traits: nft_info.extension.attributes,
     };
     store_ask(deps.storage, &ask)?;
+    let custody = Custody {
+        depositor: seller.clone(),
+        deposited_at: now,
+    };
+    CUSTODY.save(deps.storage, ask_key(&collection, &token_id), &custody)?;
 
     let hook = prepare_ask_hook(deps.as_ref(), &ask, HookAction::Create)?;
 
@@ -237,6 +467,9 @@ pub fn execute_remove_ask(
     collection: Addr,
     token_id: TokenId,
 ) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.removal, "removal")?;
+
     nonpayable(&info)?;
 
     let key = ask_key(&collection, &token_id);
@@ -245,7 +478,7 @@ pub fn execute_remove_ask(
     let owner = ask.clone().seller;
     only_owner_nft(&info, owner)?;
 
-    asks().remove(deps.storage, key)?;
+    remove_ask(deps.storage, key)?;
 
     let mut messages : Vec<CosmosMsg> = Vec::new();
 
@@ -270,7 +503,7 @@ pub fn execute_remove_ask(
         messages.push(send_msg);
 
         let bid_key = bid_key(&ask.collection, &ask.token_id, &ask.clone().max_bidder.unwrap());
-        bids().remove(deps.storage, bid_key)?;
+        remove_bid(deps.storage, bid_key)?;
     }
 
     let hook = prepare_ask_hook(deps.as_ref(), &ask, HookAction::Delete)?;
@@ -294,8 +527,11 @@ pub fn execute_update_ask_price(
     token_id: TokenId,
     price: Coin,
 ) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.listing, "listing")?;
+
     nonpayable(&info)?;
-    price_validate(deps.storage, &price)?;
+    price_validate(deps.storage, &collection, &price)?;
 
     let key = ask_key(&collection, &token_id);
 
@@ -334,138 +570,370 @@ pub fn execute_set_bid(
         collection,
         token_id,
     } = bid_info;
-    let params = SUDO_PARAMS.load(deps.storage)?;
 
     let bid_price = must_pay(&info, NATIVE_DENOM)?;
-    if bid_price < params.min_price {
-        return Err(ContractError::PriceTooSmall(bid_price));
+    let ask = validate_bid(deps.as_ref(), &env, &collection, &token_id, bid_price)?;
+
+    let bidder = info.sender;
+    let mut res = Response::new();
+    let action = place_bid(deps, &env, bidder.clone(), bid_price, ask.clone(), &mut res)?;
+
+    Ok(res
+        .add_attribute("human_action", action)
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("token_id", token_id.to_string())
+        .add_attribute("buyer", bidder)
+        .add_attribute("seller", ask.seller.to_string())
+        .add_attribute("price", bid_price.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// Bids on or buys several NFTs with one payment that must match the summed bid prices.
+/// An atomic batch fails as a whole, otherwise bids that cannot be placed are skipped and refunded.
+pub fn execute_batch_set_bid(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bids: Vec<BatchBid>,
+    atomic: bool,
+) -> Result<Response, ContractError> {
+    validate_batch_size(bids.len())?;
+
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    let total = bids
+        .iter()
+        .try_fold(Uint128::zero(), |total, bid| total.checked_add(bid.price))
+        .map_err(StdError::from)?;
+    if payment != total {
+        return Err(ContractError::InvalidBatchPayment {
+            expected: total,
+            received: payment,
+        });
     }
 
-    let bidder = info.sender.clone();
+    let bidder = info.sender;
     let mut res = Response::new();
-    let ask_key = ask_key(&collection, &token_id);
-    let current_bid_key = bid_key(&collection, &token_id, &bidder);
+    let mut placed = 0u32;
+    let mut failed = 0u32;
+    let mut refund = Uint128::zero();
 
-    let existing_ask = asks().may_load(deps.storage, ask_key.clone())?;
+    for BatchBid {
+        collection,
+        token_id,
+        price,
+    } in bids
+    {
+        // Validation does not touch state, so a rejected bid can be skipped safely
+        let validated = deps
+            .api
+            .addr_validate(&collection)
+            .map_err(ContractError::from)
+            .and_then(|addr| {
+                validate_bid(deps.as_ref(), &env, &addr, &token_id, price).map(|ask| (addr, ask))
+            });
+        let (collection, ask) = match validated {
+            Ok(validated) => validated,
+            Err(err) if !atomic => {
+                failed += 1;
+                refund += price;
+                res = res.add_event(
+                    Event::new("batch-bid-failed")
+                        .add_attribute("collection", collection)
+                        .add_attribute("token_id", token_id)
+                        .add_attribute("price", price.to_string())
+                        .add_attribute("error", err.to_string()),
+                );
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
 
-    // if there is no ask
-    // return an error
-    if existing_ask.is_none() {
-        return Err(ContractError::AskNotFound {});
+        let action = place_bid(deps.branch(), &env, bidder.clone(), price, ask.clone(), &mut res)?;
+        placed += 1;
+        res = res.add_event(
+            Event::new("batch-bid")
+                .add_attribute("human_action", action)
+                .add_attribute("collection", collection.to_string())
+                .add_attribute("token_id", token_id)
+                .add_attribute("seller", ask.seller.to_string())
+                .add_attribute("price", price.to_string()),
+        );
+    }
+
+    if !refund.is_zero() {
+        res = res.add_message(BankMsg::Send {
+            to_address: bidder.to_string(),
+            amount: vec![coin(refund.u128(), NATIVE_DENOM)],
+        });
     }
 
-    let mut ask = existing_ask.unwrap();
+    Ok(res
+        .add_attribute("human_action", "human_marketplace_batch_set_bid")
+        .add_attribute("buyer", bidder)
+        .add_attribute("placed", placed.to_string())
+        .add_attribute("failed", failed.to_string())
+        .add_attribute("refund", refund.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
 
-    if ask.is_expired(&env.block) {
-        return Err(ContractError::AskExpired {});
+/// Buys the cheapest fixed price asks of a collection, up to `max_items` NFTs of at most
+/// `max_unit_price` each and `max_total` overall. The funds sent must equal `max_total`,
+/// whatever is not spent is refunded.
+pub fn execute_sweep_floor(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    max_items: u32,
+    max_total: Uint128,
+    max_unit_price: Uint128,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.bidding, "bidding")?;
+    only_unpaused(pause.settlement, "settlement")?;
+
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment != max_total {
+        return Err(ContractError::InvalidBatchPayment {
+            expected: max_total,
+            received: payment,
+        });
     }
 
-    // If the bid price is lower than the required one, it fails
-    if ask.sale_type == SaleType::Auction && ask.price > bid_price {
-        return Err(ContractError::PriceTooSmall(bid_price));
+    let buyer = info.sender;
+    let max_items = max_items.min(MAX_SWEEP_ITEMS) as usize;
+
+    // Asks come cheapest first, so the walk stops at the first one over budget
+    let mut spent = Uint128::zero();
+    let mut swept: Vec<Ask> = vec![];
+    for item in asks()
+        .idx
+        .collection_price
+        .sub_prefix(collection.clone())
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (_, ask) = item?;
+        if swept.len() >= max_items || ask.price > max_unit_price || spent + ask.price > max_total {
+            break;
+        }
+        if ask.sale_type != SaleType::FixedPrice || ask.is_expired(&env.block) || ask.seller == buyer {
+            continue;
+        }
+        spent += ask.price;
+        swept.push(ask);
     }
-   
 
-    let existing_bid = bids().may_load(deps.storage, current_bid_key.clone())?;
-    if existing_bid.is_some() {
-        bids().remove(deps.storage, current_bid_key)?;
+    let mut res = Response::new();
+    let mut token_ids = vec![];
+    for ask in swept {
+        remove_ask(deps.storage, ask_key(&ask.collection, &ask.token_id))?;
+        token_ids.push(ask.token_id.clone());
+        let sale = Sale::of_ask(&ask, ask.price, buyer.clone(), SaleOrigin::FixedPrice);
+        finalize_sale(deps.as_ref(), &env, sale, &mut res)?;
     }
-    else{
-       if ask.sale_type == SaleType::Auction {
-            ask.bid_count = ask.bid_count + Uint128::new(1);
-            asks().save(deps.storage, ask_key.clone(), &ask)?;
-       }
+
+    let refund = max_total - spent;
+    if !refund.is_zero() {
+        res = res.add_message(BankMsg::Send {
+            to_address: buyer.to_string(),
+            amount: vec![coin(refund.u128(), NATIVE_DENOM)],
+        });
     }
 
-    
-    let save_bid = |store| -> StdResult<_> {
-        let bid = Bid::new(
-            collection.clone(),
-            token_id.clone(),
-            bidder.clone(),
-            bid_price,
-            true,
-            env.block.time
-        );
-        store_bid(store, &bid)?;
-        Ok(Some(bid))
-    };
+    let event = Event::new("sweep-floor")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("buyer", buyer.to_string())
+        .add_attribute("count", token_ids.len().to_string())
+        .add_attribute("token_ids", token_ids.join(","))
+        .add_attribute("spent", spent.to_string())
+        .add_attribute("refund", refund.to_string());
+
+    Ok(res
+        .add_event(event)
+        .add_attribute("human_action", "human_marketplace_sweep_floor")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("buyer", buyer)
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// Removes several asks of the sender, failing as a whole if any of them cannot be removed
+pub fn execute_batch_remove_ask(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asks: Vec<NftRef>,
+) -> Result<Response, ContractError> {
+    validate_batch_size(asks.len())?;
 
-    let action:String;
+    let mut res = Response::new().add_attribute("human_action", "human_marketplace_batch_remove_ask");
+    for NftRef { collection, token_id } in asks {
+        let collection = deps.api.addr_validate(&collection)?;
+        let item = execute_remove_ask(deps.branch(), env.clone(), info.clone(), collection, token_id)?;
+        res = merge_batch_item(res, item, "batch-remove-ask");
+    }
 
-    let bid = match ask.sale_type {
+    Ok(res)
+}
+
+/// Updates the price of several asks of the sender, failing as a whole if any update is rejected
+pub fn execute_batch_update_ask_price(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    updates: Vec<AskPriceUpdate>,
+) -> Result<Response, ContractError> {
+    validate_batch_size(updates.len())?;
+
+    let mut res = Response::new().add_attribute("human_action", "human_marketplace_batch_update_ask_price");
+    for AskPriceUpdate { collection, token_id, price } in updates {
+        let collection = deps.api.addr_validate(&collection)?;
+        let item = execute_update_ask_price(deps.branch(), env.clone(), info.clone(), collection, token_id, price)?;
+        res = merge_batch_item(res, item, "batch-update-ask-price");
+    }
+
+    Ok(res)
+}
+
+/// Checks a batch holds at least one and at most `MAX_BATCH` items
+fn validate_batch_size(len: usize) -> Result<(), ContractError> {
+    if len == 0 {
+        return Err(ContractError::EmptyBatch {});
+    }
+    if len > MAX_BATCH as usize {
+        return Err(ContractError::BatchTooLarge(MAX_BATCH));
+    }
+
+    Ok(())
+}
+
+/// Folds the response of a single batch item into the batch response, its attributes become an event
+fn merge_batch_item(res: Response, item: Response, event: &str) -> Response {
+    res.add_submessages(item.messages)
+        .add_events(item.events)
+        .add_event(Event::new(event).add_attributes(item.attributes))
+}
+
+/// Checks a bid against the ask of an NFT without changing any state
+fn validate_bid(
+    deps: Deps,
+    env: &Env,
+    collection: &Addr,
+    token_id: &TokenId,
+    bid_price: Uint128,
+) -> Result<Ask, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.bidding, "bidding")?;
+
+    let (params, _) = collection_params(deps.storage, collection)?;
+    if bid_price < params.min_price {
+        return Err(ContractError::PriceTooSmall(bid_price));
+    }
+
+    // if there is no ask
+    // return an error
+    let ask = asks()
+        .may_load(deps.storage, ask_key(collection, token_id))?
+        .ok_or(ContractError::AskNotFound {})?;
+
+    if ask.is_expired(&env.block) {
+        return Err(ContractError::AskExpired {});
+    }
+
+    match ask.sale_type {
         SaleType::FixedPrice => {
+            only_unpaused(pause.settlement, "settlement")?;
             if ask.price != bid_price {
                 return Err(ContractError::InvalidPrice {});
             }
-            action = "human_marketplace_buy_fixed_price".to_string();
-            asks().remove(deps.storage, ask_key)?;
-            finalize_sale(
-                deps.as_ref(),
-                ask.clone(),
-                bid_price,
-                bidder.clone(),
-                // finder,
-                &mut res,
-            )?;
-            None
-        },
+        }
         SaleType::Auction => {
-            if ask.max_bid.is_none() || ask.max_bidder.is_none() {
-                return Err(ContractError::WrongAskInfo {});
+            // If the bid price is lower than the required one, it fails
+            if ask.price > bid_price {
+                return Err(ContractError::PriceTooSmall(bid_price));
             }
 
-            if bid_price <= ask.max_bid.unwrap() {
-                return Err(ContractError::InsufficientFundsSend {});
+            match (ask.max_bid, &ask.max_bidder) {
+                (Some(max_bid), Some(_)) => {
+                    if bid_price <= max_bid {
+                        return Err(ContractError::InsufficientFundsSend {});
+                    }
+                }
+                _ => return Err(ContractError::WrongAskInfo {}),
             }
+        }
+    }
 
-            action = "human_marketplace_set_bid".to_string();
+    Ok(ask)
+}
+
+/// Places a bid that passed `validate_bid`, a bid on a fixed price ask buys the NFT outright.
+/// Returns the action taken.
+fn place_bid(
+    deps: DepsMut,
+    env: &Env,
+    bidder: Addr,
+    bid_price: Uint128,
+    mut ask: Ask,
+    res: &mut Response,
+) -> Result<&'static str, ContractError> {
+    let ask_key = ask_key(&ask.collection, &ask.token_id);
+    let current_bid_key = bid_key(&ask.collection, &ask.token_id, &bidder);
 
-            let max_bidder = ask.max_bidder.unwrap();
+    let existing_bid = bids().may_load(deps.storage, current_bid_key.clone())?;
+    if existing_bid.is_some() {
+        remove_bid(deps.storage, current_bid_key)?;
+    } else if ask.sale_type == SaleType::Auction {
+        ask.bid_count += Uint128::new(1);
+    }
 
-            let refund_msg = BankMsg::Send {
-                to_address: max_bidder.to_string(),
-                amount: vec![coin(ask.max_bid.unwrap().u128(), NATIVE_DENOM)],
-            };
+    match ask.sale_type {
+        SaleType::FixedPrice => {
+            remove_ask(deps.storage, ask_key)?;
+            let sale = Sale::of_ask(&ask, bid_price, bidder, SaleOrigin::FixedPrice);
+            finalize_sale(deps.as_ref(), env, sale, res)?;
+            Ok("human_marketplace_buy_fixed_price")
+        }
+        SaleType::Auction => {
+            let max_bidder = ask.max_bidder.clone().ok_or(ContractError::WrongAskInfo {})?;
+            let max_bid = ask.max_bid.ok_or(ContractError::WrongAskInfo {})?;
 
             if max_bidder != env.contract.address {
-                res = res.add_message(refund_msg);
-                
-                let prev_bid_key = bid_key(&collection, &token_id, &max_bidder);
-                let prev_bid = bids().may_load(deps.storage, prev_bid_key.clone())?;
+                res.messages.push(SubMsg::new(BankMsg::Send {
+                    to_address: max_bidder.to_string(),
+                    amount: vec![coin(max_bid.u128(), NATIVE_DENOM)],
+                }));
+
+                let prev_bid_key = bid_key(&ask.collection, &ask.token_id, &max_bidder);
+                let prev_bid = bids().may_load(deps.storage, prev_bid_key)?;
 
                 if let Some(mut prev_bid) = prev_bid {
                     prev_bid.active = false;
-                    bids().save(deps.storage, prev_bid_key, &prev_bid)?;
+                    store_bid(deps.storage, &prev_bid)?;
                 }
             }
-                
+
             ask.max_bid = Some(bid_price);
-            ask.max_bidder = Some(info.sender);
+            ask.max_bidder = Some(bidder.clone());
             asks().save(deps.storage, ask_key, &ask)?;
 
-            save_bid(deps.storage)?
+            let bid = Bid::new(
+                ask.collection.clone(),
+                ask.token_id.clone(),
+                bidder,
+                bid_price,
+                true,
+                env.block.time,
+            );
+            store_bid(deps.storage, &bid)?;
+
+            res.messages
+                .append(&mut prepare_bid_hook(deps.as_ref(), &bid, HookAction::Create)?);
+            Ok("human_marketplace_set_bid")
         }
-    };
-
-    let hook = if let Some(bid) = bid {
-        prepare_bid_hook(deps.as_ref(), &bid, HookAction::Create)?
-    } else {
-        vec![]
-    };
-
-    Ok(res.add_submessages(hook)
-        .add_attribute("human_action", action)
-        .add_attribute("collection", collection.to_string())
-        .add_attribute("token_id", token_id.to_string())
-        .add_attribute("buyer", bidder)
-        .add_attribute("seller", ask.seller.to_string())
-        .add_attribute("price", bid_price.to_string())
-        .add_attribute("time", env.block.time.to_string()))
-
+    }
 }
 
-/// Removes a bid made by the bidder. Bidders can only remove their own bids
+// Removes a bid made by the bidder. Bidders can only remove their own bids
 // pub fn execute_remove_bid(
 //     deps: DepsMut,
 //     _env: Env,
@@ -508,6 +976,8 @@ pub fn execute_accept_bid(
     collection: Addr,
     token_id: TokenId,
 ) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+
     nonpayable(&info)?;
 
     let ask_key = ask_key(&collection, &token_id);
@@ -519,7 +989,12 @@ pub fn execute_accept_bid(
         return Err(ContractError::AuctionNotEnded {});
     }
 
-    asks().remove(deps.storage, ask_key)?;
+    // Returning an unsold NFT is an exit and stays available while settlement is paused
+    if existing_ask.max_bidder != Some(env.contract.address.clone()) {
+        only_unpaused(pause.settlement, "settlement")?;
+    }
+
+    remove_ask(deps.storage, ask_key)?;
  
 
     let mut res = Response::new();
@@ -528,18 +1003,12 @@ pub fn execute_accept_bid(
     let max_bid_price = existing_ask.clone().max_bid.unwrap();
 
     if max_bidder != env.contract.address {
-        finalize_sale(
-            deps.as_ref(),
-            existing_ask.clone(),
-            max_bid_price,
-            max_bidder.clone(),
-            // finder,
-            &mut res,
-        )?;
+        let sale = Sale::of_ask(&existing_ask, max_bid_price, max_bidder.clone(), SaleOrigin::Auction);
+        finalize_sale(deps.as_ref(), &env, sale, &mut res)?;
 
         let current_bids = query_all_bids(deps.as_ref(), collection.clone(), token_id.clone())?;
         for current_bid in current_bids.bids{
-            bids().remove(deps.storage, bid_key(&collection, &token_id, &current_bid.bidder))?;
+            remove_bid(deps.storage, bid_key(&collection, &token_id, &current_bid.bidder))?;
         }
 
         res = res.add_attribute("human_action", "human_marketplace_accept_bid")
@@ -567,38 +1036,66 @@ pub fn execute_accept_bid(
 
     Ok(res)
 }
-/// Transfers funds and NFT, updates bid
-fn finalize_sale(
+/// An NFT held or approved by the marketplace, settled by `finalize_sale`
+pub(crate) struct Sale {
+    pub collection: Addr,
+    pub token_id: TokenId,
+    pub seller: Addr,
+    /// Receives the seller's share of the price
+    pub funds_recipient: Addr,
+    pub price: Uint128,
+    pub buyer: Addr,
+    pub origin: SaleOrigin,
+}
+
+impl Sale {
+    /// Sale of the NFT of an ask, paying the ask's funds recipient if it has one
+    pub(crate) fn of_ask(ask: &Ask, price: Uint128, buyer: Addr, origin: SaleOrigin) -> Self {
+        Sale {
+            collection: ask.collection.clone(),
+            token_id: ask.token_id.clone(),
+            seller: ask.seller.clone(),
+            funds_recipient: ask
+                .funds_recipient
+                .clone()
+                .unwrap_or_else(|| ask.seller.clone()),
+            price,
+            buyer,
+            origin,
+        }
+    }
+}
+
+/// Transfers funds and NFT, informs the sale hooks
+pub(crate) fn finalize_sale(
     deps: Deps,
-    ask: Ask,
-    price: Uint128,
-    buyer: Addr,
+    env: &Env,
+    sale: Sale,
     res: &mut Response,
 ) -> StdResult<()> {
-    payout(
+    let breakdown = payout(
         deps,
-        ask.collection.clone(),
-        price,
-        ask.funds_recipient
-            .clone()
-            .unwrap_or_else(|| ask.seller.clone()),
+        sale.collection.clone(),
+        &sale.token_id,
+        sale.price,
+        sale.funds_recipient.clone(),
         res,
     )?;
 
     let cw721_transfer_msg = Cw721ExecuteMsg::<Metadata>::TransferNft {
-        token_id: ask.token_id.to_string(),
-        recipient: buyer.to_string(),
+        token_id: sale.token_id.to_string(),
+        recipient: sale.buyer.to_string(),
     };
 
     let exec_cw721_transfer = WasmMsg::Execute {
-        contract_addr: ask.collection.to_string(),
+        contract_addr: sale.collection.to_string(),
         msg: to_binary(&cw721_transfer_msg)?,
         funds: vec![],
     };
     res.messages.push(SubMsg::new(exec_cw721_transfer));
 
     res.messages
-        .append(&mut prepare_sale_hook(deps, &ask, buyer.clone())?);
+        .append(&mut prepare_sale_hook(deps, env, &sale, &breakdown)?);
 
     Ok(())
 }
@@ -607,94 +1104,324 @@ fn finalize_sale(
 fn payout(
     deps: Deps,
     collection: Addr,
+    token_id: &str,
     payment: Uint128,
     payment_recipient: Addr,
     // finder: Option<Addr>,
     // finders_fee_bps: Option<u64>,
     res: &mut Response,
-) -> StdResult<()> {
-    // let params = SUDO_PARAMS.load(deps.storage)?;
+) -> StdResult<PayoutSimulationResponse> {
+    let breakdown = simulate_payout(deps, &collection, token_id, payment)?;
+    pay_fee_and_royalties(&collection, &breakdown, res);
+
+    if !breakdown.seller_amount.is_zero() {
+        let seller_share_msg = BankMsg::Send {
+            to_address: payment_recipient.to_string(),
+            amount: vec![coin(
+                breakdown.seller_amount.u128(),
+                NATIVE_DENOM.to_string(),
+            )],
+        };
+        res.messages.push(SubMsg::new(seller_share_msg));
+    }
 
-    // Append Fair Burn message
-    // let network_fee = payment * params.trading_fee_percent / Uint128::from(100u128);
-    // fair_burn(network_fee.u128(), None, res);
+    Ok(breakdown)
+}
 
-    let collection_info: CollectionInfoResponse = deps
-        .querier
-        .query_wasm_smart(collection.clone(), &Cw721QueryMsg::GetCollectionState  {})?;
-
-    match collection_info.royalty_info {
-        // If token supports royalities, payout shares to royalty recipient
-        Some(royalty) => {
-            let amount = coin((payment * royalty.royalty_rate).u128(), NATIVE_DENOM);
-            if payment < amount.amount {
-                return Err(StdError::generic_err("Fees exceed payment"));
-            }
+/// Pays out the protocol fee and royalties of a payment breakdown
+pub(crate) fn pay_fee_and_royalties(
+    collection: &Addr,
+    breakdown: &PayoutSimulationResponse,
+    res: &mut Response,
+) {
+    // Emitted on every sale, so indexers can tell "no royalty" from "no royalty support"
+    let event = Event::new("royalty-source")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("source", breakdown.royalty_source.to_string());
+    res.events.push(event);
+
+    if let Some(fee_recipient) = &breakdown.fee_recipient {
+        if !breakdown.protocol_fee.is_zero() {
+            let amount = coin(breakdown.protocol_fee.u128(), NATIVE_DENOM);
             res.messages.push(SubMsg::new(BankMsg::Send {
-                to_address: royalty.address.to_string(),
+                to_address: fee_recipient.to_string(),
                 amount: vec![amount.clone()],
             }));
 
-            let event = Event::new("royalty-payout")
+            let event = Event::new("protocol-fee")
                 .add_attribute("collection", collection.to_string())
                 .add_attribute("amount", amount.to_string())
-                .add_attribute("recipient", royalty.address.to_string());
+                .add_attribute("recipient", fee_recipient.to_string());
             res.events.push(event);
+        }
+    }
+
+    for royalty in &breakdown.royalties {
+        let amount = coin(royalty.amount.u128(), NATIVE_DENOM);
+        res.messages.push(SubMsg::new(BankMsg::Send {
+            to_address: royalty.recipient.to_string(),
+            amount: vec![amount.clone()],
+        }));
 
-            let seller_share_msg = BankMsg::Send {
-                to_address: payment_recipient.to_string(),
-                amount: vec![coin(
-                    (payment * (Decimal::one() - royalty.royalty_rate)).u128(),
-                    NATIVE_DENOM.to_string(),
-                )],
-            };
-            res.messages.push(SubMsg::new(seller_share_msg));
+        let event = Event::new("royalty-payout")
+            .add_attribute("collection", collection.to_string())
+            .add_attribute("amount", amount.to_string())
+            .add_attribute("recipient", royalty.recipient.to_string())
+            .add_attribute("source", breakdown.royalty_source.to_string());
+        res.events.push(event);
+    }
+}
+
+/// Splits a payment between protocol fee, royalties and seller. Royalties are looked up via
+/// CW2981 first, then the custom `GetCollectionState`, and are absent if neither is supported.
+/// The royalty is capped by governance, rounding dust of a royalty split goes to the primary
+/// royalty address and the seller receives the exact remainder, so nothing is left behind.
+pub fn simulate_payout(
+    deps: Deps,
+    collection: &Addr,
+    token_id: &str,
+    payment: Uint128,
+) -> StdResult<PayoutSimulationResponse> {
+    let (params, config) = collection_params(deps.storage, collection)?;
+
+    let fee_discount = Decimal::from_ratio(
+        config.protocol_fee_discount_bps.unwrap_or_default(),
+        MAX_FEE_BPS,
+    );
+    // The fee is only charged once governance has set where it goes
+    let protocol_fee = match params.fee_recipient {
+        Some(_) => payment * (params.trading_fee_percent * (Decimal::one() - fee_discount)),
+        None => Uint128::zero(),
+    };
+
+    // Royalties never exceed the governance cap, nor what is left after the protocol fee
+    let royalty_cap = params
+        .max_royalty_rate
+        .map_or(payment, |rate| payment * rate)
+        .min(payment - protocol_fee);
+
+    let cw2981_royalty: Option<RoyaltiesInfoResponse> = deps
+        .querier
+        .query_wasm_smart(
+            collection.clone(),
+            &Cw2981QueryMsg::RoyaltyInfo {
+                token_id: token_id.to_string(),
+                sale_price: payment,
+            },
+        )
+        .ok();
+
+    let mut royalties = vec![];
+    let royalty_source = if let Some(royalty) = cw2981_royalty {
+        // CW2981 uses an empty address to signal that no royalty is due
+        if !royalty.address.is_empty() {
+            royalties.push(RoyaltyPayout {
+                recipient: deps.api.addr_validate(&royalty.address)?,
+                amount: royalty.royalty_amount.min(royalty_cap),
+            });
+        }
+        RoyaltySource::Cw2981
+    } else if let Ok(collection_info) = deps
+        .querier
+        .query_wasm_smart::<CollectionInfoResponse>(collection.clone(), &Cw721QueryMsg::GetCollectionState  {})
+    {
+        if let Some(royalty) = collection_info.royalty_info {
+            let royalty_total = (payment * royalty.royalty_rate).min(royalty_cap);
+
+            let mut remainder = royalty_total;
+            for share in royalty.shares.unwrap_or_default() {
+                let amount = (royalty_total * share.share).min(remainder);
+                remainder -= amount;
+                royalties.push(RoyaltyPayout {
+                    recipient: deps.api.addr_validate(&share.address)?,
+                    amount,
+                });
+            }
+            royalties.push(RoyaltyPayout {
+                recipient: deps.api.addr_validate(&royalty.address)?,
+                amount: remainder,
+            });
         }
-        None => {
-            // if payment < network_fee {
-            //     return Err(StdError::generic_err("Fees exceed payment"));
-            // }
-            // If token doesn't support royalties, pay seller in full
-            let seller_share_msg = BankMsg::Send {
-                to_address: payment_recipient.to_string(),
-                amount: vec![coin(
-                    payment.u128(),
-                    NATIVE_DENOM.to_string(),
-                )],
-            };
-            res.messages.push(SubMsg::new(seller_share_msg));
+        RoyaltySource::CollectionState
+    } else {
+        RoyaltySource::None
+    };
+    royalties.retain(|r| !r.amount.is_zero());
+
+    let royalty_total: Uint128 = royalties.iter().map(|r| r.amount).sum();
+
+    Ok(PayoutSimulationResponse {
+        price: payment,
+        protocol_fee,
+        fee_recipient: params.fee_recipient,
+        royalty_source,
+        royalties,
+        seller_amount: payment - protocol_fee - royalty_total,
+    })
+}
+
+/// The collection admin, as reported by the cw721 `Admin {}` query, may override the
+/// listing policy of their collection. Not covered by the pause switches, it only changes
+/// policy for future orders and moves neither funds nor NFTs.
+pub fn execute_set_collection_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    collection: Addr,
+    config: CollectionConfig,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let admin: AdminResponse = deps
+        .querier
+        .query_wasm_smart(collection.clone(), &Cw721QueryMsg::Admin {})?;
+    if admin.admin != info.sender {
+        return Err(ContractError::UnauthorizedAdmin {});
+    }
+
+    let existing = COLLECTION_CONFIGS
+        .may_load(deps.storage, &collection)?
+        .unwrap_or_default();
+    if config.protocol_fee_discount_bps != existing.protocol_fee_discount_bps {
+        return Err(ContractError::FeeDiscountGovernanceOnly {});
+    }
+
+    store_collection_config(deps.storage, &collection, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("human_action", "human_marketplace_set_collection_config")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("admin", info.sender))
+}
+
+/// Validates and saves collection overrides, an empty config removes them
+pub fn store_collection_config(
+    store: &mut dyn Storage,
+    collection: &Addr,
+    config: &CollectionConfig,
+) -> Result<(), ContractError> {
+    config.ask_expiry.as_ref().map(|a| a.validate()).transpose()?;
+    config.bid_expiry.as_ref().map(|b| b.validate()).transpose()?;
+    if let Some(discount) = config.protocol_fee_discount_bps {
+        if discount > MAX_FEE_BPS {
+            return Err(ContractError::InvalidFeeDiscountBps(discount));
         }
     }
+    if config.sale_types.as_ref().map_or(false, |s| s.is_empty()) {
+        return Err(ContractError::SaleTypeNotAllowed {});
+    }
 
+    if config == &CollectionConfig::default() {
+        COLLECTION_CONFIGS.remove(store, collection);
+    } else {
+        COLLECTION_CONFIGS.save(store, collection, config)?;
+    }
     Ok(())
 }
 
-fn price_validate(store: &dyn Storage, price: &Coin) -> Result<(), ContractError> {
+/// Checks a collection may be listed on the marketplace
+pub(crate) fn only_verified_collection(deps: Deps, collection: &Addr) -> Result<(), ContractError> {
+    let status = verify_collection(deps, collection)?;
+    match status.verification {
+        CollectionVerification::Denied => Err(ContractError::CollectionDenied {}),
+        CollectionVerification::Disabled => Err(ContractError::CollectionDisabled {
+            reason: status.reason.unwrap_or_default(),
+        }),
+        CollectionVerification::Unverified => Err(ContractError::ContractNotFound {}),
+        CollectionVerification::Allowed | CollectionVerification::FactoryVerified => Ok(()),
+    }
+}
+
+/// Resolves whether a collection may be listed. The local registry is consulted first,
+/// falling back to the factory `CheckCollection` query for unregistered collections.
+pub fn verify_collection(deps: Deps, collection: &Addr) -> StdResult<CollectionStatus> {
+    let params = SUDO_PARAMS.load(deps.storage)?;
+    let record = COLLECTION_REGISTRY
+        .may_load(deps.storage, collection)?
+        .unwrap_or_default();
+
+    // A failing factory query is a misconfiguration, not an unregistered collection
+    let factory_check = || -> StdResult<bool> {
+        deps.querier.query_wasm_smart(
+            params.create_collection_address.clone(),
+            &CreateCollectionQueryMsg::CheckCollection {
+                address: collection.to_string(),
+            },
+        )
+    };
+
+    let verification = match record.list {
+        Some(CollectionList::Deny) => CollectionVerification::Denied,
+        _ if !record.enabled => CollectionVerification::Disabled,
+        Some(CollectionList::Allow) if !params.require_factory_check || factory_check()? => {
+            CollectionVerification::Allowed
+        }
+        Some(CollectionList::Allow) => CollectionVerification::Unverified,
+        None if factory_check()? => CollectionVerification::FactoryVerified,
+        None => CollectionVerification::Unverified,
+    };
+
+    Ok(CollectionStatus {
+        collection: collection.clone(),
+        verification,
+        reason: record.reason,
+    })
+}
+
+pub(crate) fn price_validate(store: &dyn Storage, collection: &Addr, price: &Coin) -> Result<(), ContractError> {
     if price.amount.is_zero() || price.denom != NATIVE_DENOM {
         return Err(ContractError::InvalidPrice {});
     }
 
-    if price.amount < SUDO_PARAMS.load(store)?.min_price {
+    if price.amount < collection_params(store, collection)?.0.min_price {
         return Err(ContractError::PriceTooSmall(price.amount));
     }
 
     Ok(())
 }
 
+/// Saves a bid, the funds of an active bid count towards the auction escrow
 fn store_bid(store: &mut dyn Storage, bid: &Bid) -> StdResult<()> {
-    bids().save(
+    let key = bid_key(&bid.collection, &bid.token_id, &bid.bidder);
+    let before = bids().may_load(store, key.clone())?;
+    bids().save(store, key.clone(), bid)?;
+    track_escrow(
         store,
-        bid_key(&bid.collection, &bid.token_id, &bid.bidder),
-        bid,
+        Escrowed::Bid(&key),
+        before.as_ref().map(bid_escrow).unwrap_or_default(),
+        bid_escrow(bid),
     )
 }
 
+/// Removes a bid, releasing the funds of an active one from the auction escrow
+fn remove_bid(store: &mut dyn Storage, key: BidKey) -> StdResult<()> {
+    if let Some(bid) = bids().may_load(store, key.clone())? {
+        bids().remove(store, key.clone())?;
+        track_escrow(store, Escrowed::Bid(&key), bid_escrow(&bid), Uint128::zero())?;
+    }
+    Ok(())
+}
+
 fn store_ask(store: &mut dyn Storage, ask: &Ask) -> StdResult<()> {
     asks().save(store, ask_key(&ask.collection, &ask.token_id), ask)
 }
 
+/// Removes an ask along with the custody record of its NFT, which leaves with it
+pub(crate) fn remove_ask(store: &mut dyn Storage, key: AskKey) -> StdResult<()> {
+    CUSTODY.remove(store, key.clone());
+    asks().remove(store, key)
+}
+
+/// Checks the given pause switch is off
+pub(crate) fn only_unpaused(paused: bool, action: &str) -> Result<(), ContractError> {
+    if paused {
+        return Err(ContractError::Paused(action.to_string()));
+    }
+
+    Ok(())
+}
+
 /// Checks to enfore only NFT owner can call
-fn only_owner_nft(
+pub(crate) fn only_owner_nft(
     info: &MessageInfo,
     owner: Addr,
 ) -> Result<Response, ContractError> {
@@ -705,8 +1432,41 @@ fn only_owner_nft(
     Ok(Response::default())
 }
 
+/// Checks `owner` holds the NFT according to its collection
+pub(crate) fn only_nft_owner_of(deps: Deps, item: &NftItem, owner: &Addr) -> Result<(), ContractError> {
+    let res: OwnerOfResponse = deps.querier.query_wasm_smart(
+        &item.collection,
+        &Cw721QueryMsg::OwnerOf {
+            token_id: item.token_id.clone(),
+            include_expired: None,
+        },
+    )?;
+    if res.owner != *owner {
+        return Err(ContractError::UnauthorizedOwner {});
+    }
+
+    Ok(())
+}
+
+/// Transfers each NFT to the recipient, from escrow or through an approval of the marketplace
+pub(crate) fn transfer_items(items: &[NftItem], recipient: &Addr, res: &mut Response) -> StdResult<()> {
+    for item in items {
+        let cw721_transfer_msg = Cw721ExecuteMsg::<Metadata>::TransferNft {
+            token_id: item.token_id.to_string(),
+            recipient: recipient.to_string(),
+        };
+        res.messages.push(SubMsg::new(WasmMsg::Execute {
+            contract_addr: item.collection.to_string(),
+            msg: to_binary(&cw721_transfer_msg)?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(())
+}
+
 /// Checks to enforce only privileged operators
-fn _only_operator(store: &dyn Storage, info: &MessageInfo) -> Result<Addr, ContractError> {
+pub(crate) fn only_operator(store: &dyn Storage, info: &MessageInfo) -> Result<Addr, ContractError> {
     let params = SUDO_PARAMS.load(store)?;
     if !params
         .operators
@@ -724,6 +1484,9 @@ enum HookReply {
     Sale,
     Bid,
     CollectionBid,
+    Swap,
+    Offer,
+    TraitBid,
 }
 
 impl From<u64> for HookReply {
@@ -733,6 +1496,9 @@ impl From<u64> for HookReply {
             2 => HookReply::Sale,
             3 => HookReply::Bid,
             4 => HookReply::CollectionBid,
+            5 => HookReply::Swap,
+            6 => HookReply::Offer,
+            7 => HookReply::TraitBid,
             _ => panic!("invalid reply type"),
         }
     }
@@ -765,6 +1531,24 @@ pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, Contract
                 .add_attribute("error", msg.result.unwrap_err());
             Ok(res)
         }
+        HookReply::Swap => {
+            let res = Response::new()
+                .add_attribute("action", "swap-hook-failed")
+                .add_attribute("error", msg.result.unwrap_err());
+            Ok(res)
+        }
+        HookReply::Offer => {
+            let res = Response::new()
+                .add_attribute("action", "offer-hook-failed")
+                .add_attribute("error", msg.result.unwrap_err());
+            Ok(res)
+        }
+        HookReply::TraitBid => {
+            let res = Response::new()
+                .add_attribute("action", "trait-bid-hook-failed")
+                .add_attribute("error", msg.result.unwrap_err());
+            Ok(res)
+        }
     }
 }
 
@@ -782,14 +1566,20 @@ fn prepare_ask_hook(deps: Deps, ask: &Ask, action: HookAction) -> StdResult<Vec<
     Ok(submsgs)
 }
 
-fn prepare_sale_hook(deps: Deps, ask: &Ask, buyer: Addr) -> StdResult<Vec<SubMsg>> {
-    let submsgs = SALE_HOOKS.prepare_hooks(deps.storage, |h| {
+/// Informs the sale hooks of a settled sale, v2 hooks also receive the payout breakdown
+fn prepare_sale_hook(
+    deps: Deps,
+    env: &Env,
+    sale: &Sale,
+    breakdown: &PayoutSimulationResponse,
+) -> StdResult<Vec<SubMsg>> {
+    let mut submsgs = SALE_HOOKS.prepare_hooks(deps.storage, |h| {
         let msg = SaleHookMsg {
-            collection: ask.collection.to_string(),
-            token_id: ask.token_id.to_string(),
-            price: coin(ask.price.clone().u128(), NATIVE_DENOM),
-            seller: ask.seller.to_string(),
-            buyer: buyer.to_string(),
+            collection: sale.collection.to_string(),
+            token_id: sale.token_id.to_string(),
+            price: coin(breakdown.price.u128(), NATIVE_DENOM),
+            seller: sale.seller.to_string(),
+            buyer: sale.buyer.to_string(),
         };
         let execute = WasmMsg::Execute {
             contract_addr: h.to_string(),
@@ -799,6 +1589,28 @@ fn prepare_sale_hook(deps: Deps, ask: &Ask, buyer: Addr) -> StdResult<Vec<SubMsg
         Ok(SubMsg::reply_on_error(execute, HookReply::Sale as u64))
     })?;
 
+    submsgs.append(&mut SALE_HOOKS_V2.prepare_hooks(deps.storage, |h| {
+        let msg = SaleHookMsgV2 {
+            collection: sale.collection.to_string(),
+            token_id: sale.token_id.to_string(),
+            origin: sale.origin.clone(),
+            price: coin(breakdown.price.u128(), NATIVE_DENOM),
+            seller: sale.seller.to_string(),
+            buyer: sale.buyer.to_string(),
+            royalties: breakdown.royalties.clone(),
+            protocol_fee: breakdown.protocol_fee,
+            finders_fee: Uint128::zero(),
+            seller_amount: breakdown.seller_amount,
+            timestamp: env.block.time,
+        };
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.into_binary()?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(execute, HookReply::Sale as u64))
+    })?);
+
     Ok(submsgs)
 }
 
@@ -816,14 +1628,48 @@ fn prepare_bid_hook(deps: Deps, bid: &Bid, action: HookAction) -> StdResult<Vec<
     Ok(submsgs)
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    let version = get_contract_version(deps.storage)?;
-    if version.contract != CONTRACT_NAME {
-        return Err(ContractError::CannotMigrate {
-            previous_contract: version.contract,
-        });
-    }
-    Ok(Response::default())
+pub(crate) fn prepare_swap_hook(deps: Deps, swap: &Swap, action: HookAction) -> StdResult<Vec<SubMsg>> {
+    let submsgs = SWAP_HOOKS.prepare_hooks(deps.storage, |h| {
+        let msg = SwapHookMsg { swap: swap.clone() };
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.into_binary(action.clone())?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(execute, HookReply::Swap as u64))
+    })?;
+
+    Ok(submsgs)
+}
+
+pub(crate) fn prepare_offer_hook(deps: Deps, offer: &Offer, action: HookAction) -> StdResult<Vec<SubMsg>> {
+    let submsgs = OFFER_HOOKS.prepare_hooks(deps.storage, |h| {
+        let msg = OfferHookMsg { offer: offer.clone() };
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.into_binary(action.clone())?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(execute, HookReply::Offer as u64))
+    })?;
+
+    Ok(submsgs)
 }
 
+pub(crate) fn prepare_trait_bid_hook(
+    deps: Deps,
+    trait_bid: &TraitBid,
+    action: HookAction,
+) -> StdResult<Vec<SubMsg>> {
+    let submsgs = TRAIT_BID_HOOKS.prepare_hooks(deps.storage, |h| {
+        let msg = TraitBidHookMsg::new(trait_bid.clone());
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.into_binary(action.clone())?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(execute, HookReply::TraitBid as u64))
+    })?;
+
+    Ok(submsgs)
+}
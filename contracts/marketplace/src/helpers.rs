@@ -1,6 +1,23 @@
-use crate::msg::ExecuteMsg;
-use cosmwasm_std::{to_binary, Addr, Api, StdError, StdResult, WasmMsg, CosmosMsg};
+use crate::execute::NATIVE_DENOM;
+use crate::msg::{
+    AskInfo,     AskCountResponse, AskOffset, AskOffsetBidCount, AskOffsetSellPrice, AskResponse,
+    AsksResponse, BatchBid, BidOffset, BidResponse, Bidder, BidsResponse, BundleAskResponse,
+    BundleAsksResponse, Collection, CollectionConfigResponse, CollectionOffset,
+    CollectionStatusResponse, CollectionsResponse, CustodyResponse, EscrowSummaryResponse, ExecuteMsg, HooksResponse, MigrationStatusResponse, NftRef,
+    OfferOffset, OfferResponse, OffersResponse, OrderDigestResponse, OrderKeyResponse,
+    OrderNonceResponse, ParamsResponse, PauseStatusResponse, PayoutSimulationResponse, QueryMsg,
+    ReceiveNftMsg, ReceiveNftMsgV1,
+    Seller, SignedOrder, SwapResponse, SwapsResponse, TraitBidOffset, TraitBidResponse,
+    TraitBidsResponse,
+};
+use crate::state::{SaleType, TokenId};
+use cosmwasm_std::{
+    coins, to_binary, Addr, Api, Binary, Coin, CosmosMsg, QuerierWrapper, StdError, StdResult,
+    Uint128, WasmMsg,
+};
+use cw721::Cw721ExecuteMsg;
 use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -14,14 +31,736 @@ impl MarketplaceContract {
     }
 
     pub fn call<T: Into<ExecuteMsg>>(&self, msg: T) -> StdResult<CosmosMsg> {
+        self.call_with_funds(msg, vec![])
+    }
+
+    pub fn call_with_funds<T: Into<ExecuteMsg>>(
+        &self,
+        msg: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
         let msg = to_binary(&msg.into())?;
         Ok(WasmMsg::Execute {
             contract_addr: self.addr().into(),
             msg,
+            funds,
+        }
+        .into())
+    }
+
+    // Execute message builders, attaching the funds each message expects
+
+    /// Bid `price` on an ask, or buy it at its fixed price
+    pub fn set_bid(
+        &self,
+        collection: impl Into<String>,
+        token_id: impl Into<TokenId>,
+        price: Uint128,
+    ) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::SetBid {
+            collection: collection.into(),
+            token_id: token_id.into(),
+        };
+        self.call_with_funds(msg, native_funds(price))
+    }
+
+    /// Bid on several asks, sending the summed prices
+    pub fn batch_set_bid(&self, bids: Vec<BatchBid>, atomic: bool) -> StdResult<CosmosMsg> {
+        let total = bids
+            .iter()
+            .try_fold(Uint128::zero(), |total, bid| total.checked_add(bid.price))?;
+        self.call_with_funds(ExecuteMsg::BatchSetBid { bids, atomic }, native_funds(total))
+    }
+
+    /// Buy the cheapest asks of a collection, sending `max_total`
+    pub fn sweep_floor(
+        &self,
+        collection: impl Into<String>,
+        max_items: u32,
+        max_total: Uint128,
+        max_unit_price: Uint128,
+    ) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::SweepFloor {
+            collection: collection.into(),
+            max_items,
+            max_total,
+            max_unit_price,
+        };
+        self.call_with_funds(msg, native_funds(max_total))
+    }
+
+    /// Buy a fixed price bundle or bid `price` on a bundle auction
+    pub fn set_bundle_bid(&self, id: u64, price: Uint128) -> StdResult<CosmosMsg> {
+        self.call_with_funds(ExecuteMsg::SetBundleBid { id }, native_funds(price))
+    }
+
+    /// Create a swap, escrowing `funds` alongside the offered NFTs
+    pub fn create_swap(
+        &self,
+        offered: Vec<NftRef>,
+        requested: Vec<NftRef>,
+        counterparty: Option<String>,
+        expires: u64,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::CreateSwap {
+            offered,
+            requested,
+            counterparty,
+            expires,
+        };
+        self.call_with_funds(msg, funds)
+    }
+
+    /// Bid `price` on any NFT of `collection` with the trait `trait_type`: `value`
+    pub fn set_trait_bid(
+        &self,
+        collection: impl Into<String>,
+        trait_type: impl Into<String>,
+        value: impl Into<String>,
+        price: Uint128,
+        expires: u64,
+    ) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::SetTraitBid {
+            collection: collection.into(),
+            trait_type: trait_type.into(),
+            value: value.into(),
+            expires,
+        };
+        self.call_with_funds(msg, native_funds(price))
+    }
+
+    /// Offer `price` on a fixed price ask
+    pub fn set_offer(
+        &self,
+        collection: impl Into<String>,
+        token_id: impl Into<TokenId>,
+        price: Uint128,
+        expires: u64,
+    ) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::SetOffer {
+            collection: collection.into(),
+            token_id: token_id.into(),
+            expires,
+        };
+        self.call_with_funds(msg, native_funds(price))
+    }
+
+    /// Accept a counter offer, sending the difference `top_up` between counter and offer
+    pub fn accept_counter_offer(
+        &self,
+        collection: impl Into<String>,
+        token_id: impl Into<TokenId>,
+        top_up: Uint128,
+    ) -> StdResult<CosmosMsg> {
+        let msg = ExecuteMsg::AcceptCounterOffer {
+            collection: collection.into(),
+            token_id: token_id.into(),
+        };
+        self.call_with_funds(msg, native_funds(top_up))
+    }
+
+    /// Fill a signed order, sending its price
+    pub fn fill_signed_order(
+        &self,
+        order: SignedOrder,
+        signature: Binary,
+    ) -> StdResult<CosmosMsg> {
+        let funds = vec![order.price.clone()];
+        self.call_with_funds(ExecuteMsg::FillSignedOrder { order, signature }, funds)
+    }
+
+    /// List several NFTs the marketplace was approved for, sending `listing_fees`, the summed
+    /// listing fee of the asks
+    pub fn batch_set_ask(&self, asks: Vec<AskInfo>, listing_fees: Uint128) -> StdResult<CosmosMsg> {
+        let funds = if listing_fees.is_zero() {
+            vec![]
+        } else {
+            native_funds(listing_fees)
+        };
+        self.call_with_funds(ExecuteMsg::BatchSetAsk { asks }, funds)
+    }
+
+    /// List an NFT by sending it from its collection to the marketplace, to be executed by
+    /// the owner of the NFT. `SendNft` carries no funds, so while governance charges a listing
+    /// fee NFTs are listed through `batch_set_ask` instead.
+    pub fn send_nft_to_list(
+        &self,
+        collection: Addr,
+        token_id: impl Into<TokenId>,
+        sale_type: SaleType,
+        price: Uint128,
+        funds_recipient: Option<Addr>,
+        expires: u64,
+    ) -> StdResult<CosmosMsg> {
+        let token_id = token_id.into();
+        let ask_info = AskInfo {
+            sale_type,
+            collection: collection.clone(),
+            token_id: token_id.clone(),
+            price: Coin::new(price.u128(), NATIVE_DENOM),
+            funds_recipient,
+            expires,
+        };
+        let msg = Cw721ExecuteMsg::SendNft {
+            contract: self.addr().into(),
+            token_id,
+            msg: to_binary(&ReceiveNftMsg::V1(ReceiveNftMsgV1::SetAsk(ask_info)))?,
+        };
+        Ok(WasmMsg::Execute {
+            contract_addr: collection.into(),
+            msg: to_binary(&msg)?,
             funds: vec![],
         }
         .into())
     }
+
+    // Typed queries, one per `QueryMsg` variant
+
+    fn query<T: DeserializeOwned>(&self, querier: &QuerierWrapper, msg: QueryMsg) -> StdResult<T> {
+        querier.query_wasm_smart(self.addr(), &msg)
+    }
+
+    pub fn collections(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<Collection>,
+        limit: Option<u32>,
+    ) -> StdResult<CollectionsResponse> {
+        self.query(querier, QueryMsg::Collections { start_after, limit })
+    }
+
+    pub fn registered_collections(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<Collection>,
+        limit: Option<u32>,
+    ) -> StdResult<CollectionsResponse> {
+        self.query(querier, QueryMsg::RegisteredCollections { start_after, limit })
+    }
+
+    pub fn collection_status(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+    ) -> StdResult<CollectionStatusResponse> {
+        self.query(querier, QueryMsg::CollectionStatus { collection })
+    }
+
+    pub fn collection_config(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+    ) -> StdResult<CollectionConfigResponse> {
+        self.query(querier, QueryMsg::CollectionConfig { collection })
+    }
+
+    pub fn ask(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        token_id: TokenId,
+    ) -> StdResult<AskResponse> {
+        self.query(querier, QueryMsg::Ask { collection, token_id })
+    }
+
+    pub fn asks(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        start_after: Option<TokenId>,
+        limit: Option<u32>,
+    ) -> StdResult<AsksResponse> {
+        let msg = QueryMsg::Asks {
+            collection,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn reverse_asks(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        start_before: Option<TokenId>,
+        limit: Option<u32>,
+    ) -> StdResult<AsksResponse> {
+        let msg = QueryMsg::ReverseAsks {
+            collection,
+            start_before,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn asks_sorted_by_price(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        start_after: Option<AskOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<AsksResponse> {
+        let msg = QueryMsg::AsksSortedByPrice {
+            collection,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn reverse_asks_sorted_by_price(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        start_before: Option<AskOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<AsksResponse> {
+        let msg = QueryMsg::ReverseAsksSortedByPrice {
+            collection,
+            start_before,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn asks_sorted_by_bid_count(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<AskOffsetBidCount>,
+        limit: Option<u32>,
+    ) -> StdResult<AsksResponse> {
+        self.query(querier, QueryMsg::AsksSortedByBidCount { start_after, limit })
+    }
+
+    pub fn asks_sorted_by_sell_price(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<AskOffsetSellPrice>,
+        limit: Option<u32>,
+    ) -> StdResult<AsksResponse> {
+        self.query(querier, QueryMsg::AsksSortedBySellPrice { start_after, limit })
+    }
+
+    pub fn asks_sorted_by_expiration(
+        &self,
+        querier: &QuerierWrapper,
+        limit: Option<u32>,
+    ) -> StdResult<AsksResponse> {
+        self.query(querier, QueryMsg::AsksSortedByExpiration { limit })
+    }
+
+    pub fn reverse_sorted_by_sell_price(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<AskOffsetSellPrice>,
+        limit: Option<u32>,
+    ) -> StdResult<AsksResponse> {
+        self.query(querier, QueryMsg::ReverseSortedBySellPrice { start_after, limit })
+    }
+
+    pub fn asks_by_trait(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        trait_type: String,
+        value: String,
+        start_after: Option<TokenId>,
+        limit: Option<u32>,
+    ) -> StdResult<AsksResponse> {
+        let msg = QueryMsg::AsksByTrait {
+            collection,
+            trait_type,
+            value,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn asks_sorted_by_content_type(
+        &self,
+        querier: &QuerierWrapper,
+        content_type: String,
+        start_after: Option<CollectionOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<AsksResponse> {
+        let msg = QueryMsg::AsksSortedByContentType {
+            content_type,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn ask_count(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+    ) -> StdResult<AskCountResponse> {
+        self.query(querier, QueryMsg::AskCount { collection })
+    }
+
+    pub fn asks_by_seller(
+        &self,
+        querier: &QuerierWrapper,
+        seller: Seller,
+        start_after: Option<CollectionOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<AsksResponse> {
+        let msg = QueryMsg::AsksBySeller {
+            seller,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn bid(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        token_id: TokenId,
+        bidder: Bidder,
+    ) -> StdResult<BidResponse> {
+        let msg = QueryMsg::Bid {
+            collection,
+            token_id,
+            bidder,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn bids_by_bidder(
+        &self,
+        querier: &QuerierWrapper,
+        bidder: Bidder,
+        start_after: Option<CollectionOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<BidsResponse> {
+        let msg = QueryMsg::BidsByBidder {
+            bidder,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn bids(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        token_id: TokenId,
+        start_after: Option<Bidder>,
+        limit: Option<u32>,
+    ) -> StdResult<BidsResponse> {
+        let msg = QueryMsg::Bids {
+            collection,
+            token_id,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn offer(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        token_id: TokenId,
+        buyer: String,
+    ) -> StdResult<OfferResponse> {
+        let msg = QueryMsg::Offer {
+            collection,
+            token_id,
+            buyer,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn offers(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        token_id: TokenId,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OffersResponse> {
+        let msg = QueryMsg::Offers {
+            collection,
+            token_id,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn offers_by_buyer(
+        &self,
+        querier: &QuerierWrapper,
+        buyer: String,
+        start_after: Option<CollectionOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<OffersResponse> {
+        let msg = QueryMsg::OffersByBuyer {
+            buyer,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn offers_by_seller(
+        &self,
+        querier: &QuerierWrapper,
+        seller: String,
+        start_after: Option<OfferOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<OffersResponse> {
+        let msg = QueryMsg::OffersBySeller {
+            seller,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn bids_sorted_by_price(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        start_after: Option<BidOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<BidsResponse> {
+        let msg = QueryMsg::BidsSortedByPrice {
+            collection,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn reverse_bids_sorted_by_price(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        start_before: Option<BidOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<BidsResponse> {
+        let msg = QueryMsg::ReverseBidsSortedByPrice {
+            collection,
+            start_before,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn ask_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::AskHooks {})
+    }
+
+    pub fn bid_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::BidHooks {})
+    }
+
+    pub fn sale_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::SaleHooks {})
+    }
+
+    pub fn sale_hooks_v2(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::SaleHooksV2 {})
+    }
+
+    pub fn swap_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::SwapHooks {})
+    }
+
+    pub fn offer_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::OfferHooks {})
+    }
+
+    pub fn trait_bid_hooks(&self, querier: &QuerierWrapper) -> StdResult<HooksResponse> {
+        self.query(querier, QueryMsg::TraitBidHooks {})
+    }
+
+    pub fn trait_bid(&self, querier: &QuerierWrapper, id: u64) -> StdResult<TraitBidResponse> {
+        self.query(querier, QueryMsg::TraitBid { id })
+    }
+
+    pub fn trait_bids_sorted_by_price(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        trait_type: String,
+        value: String,
+        start_after: Option<TraitBidOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<TraitBidsResponse> {
+        let msg = QueryMsg::TraitBidsSortedByPrice {
+            collection,
+            trait_type,
+            value,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn reverse_trait_bids_sorted_by_price(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        trait_type: String,
+        value: String,
+        start_before: Option<TraitBidOffset>,
+        limit: Option<u32>,
+    ) -> StdResult<TraitBidsResponse> {
+        let msg = QueryMsg::ReverseTraitBidsSortedByPrice {
+            collection,
+            trait_type,
+            value,
+            start_before,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn trait_bids_by_bidder(
+        &self,
+        querier: &QuerierWrapper,
+        bidder: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<TraitBidsResponse> {
+        let msg = QueryMsg::TraitBidsByBidder {
+            bidder,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn swap(&self, querier: &QuerierWrapper, id: u64) -> StdResult<SwapResponse> {
+        self.query(querier, QueryMsg::Swap { id })
+    }
+
+    pub fn swaps_by_proposer(
+        &self,
+        querier: &QuerierWrapper,
+        proposer: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<SwapsResponse> {
+        let msg = QueryMsg::SwapsByProposer {
+            proposer,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn swaps_by_counterparty(
+        &self,
+        querier: &QuerierWrapper,
+        counterparty: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<SwapsResponse> {
+        let msg = QueryMsg::SwapsByCounterparty {
+            counterparty,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn bundle_ask(&self, querier: &QuerierWrapper, id: u64) -> StdResult<BundleAskResponse> {
+        self.query(querier, QueryMsg::BundleAsk { id })
+    }
+
+    pub fn bundle_asks_by_seller(
+        &self,
+        querier: &QuerierWrapper,
+        seller: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<BundleAsksResponse> {
+        let msg = QueryMsg::BundleAsksBySeller {
+            seller,
+            start_after,
+            limit,
+        };
+        self.query(querier, msg)
+    }
+
+    pub fn order_key(
+        &self,
+        querier: &QuerierWrapper,
+        seller: String,
+    ) -> StdResult<OrderKeyResponse> {
+        self.query(querier, QueryMsg::OrderKey { seller })
+    }
+
+    pub fn order_nonce(
+        &self,
+        querier: &QuerierWrapper,
+        seller: String,
+        nonce: u64,
+    ) -> StdResult<OrderNonceResponse> {
+        self.query(querier, QueryMsg::OrderNonce { seller, nonce })
+    }
+
+    pub fn order_digest(
+        &self,
+        querier: &QuerierWrapper,
+        order: SignedOrder,
+    ) -> StdResult<OrderDigestResponse> {
+        self.query(querier, QueryMsg::OrderDigest { order })
+    }
+
+    pub fn params(&self, querier: &QuerierWrapper) -> StdResult<ParamsResponse> {
+        self.query(querier, QueryMsg::Params {})
+    }
+
+    pub fn migration_status(&self, querier: &QuerierWrapper) -> StdResult<MigrationStatusResponse> {
+        self.query(querier, QueryMsg::MigrationStatus {})
+    }
+
+    pub fn custody(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        token_id: TokenId,
+    ) -> StdResult<CustodyResponse> {
+        self.query(querier, QueryMsg::Custody { collection, token_id })
+    }
+
+    pub fn escrow_summary(&self, querier: &QuerierWrapper) -> StdResult<EscrowSummaryResponse> {
+        self.query(querier, QueryMsg::EscrowSummary {})
+    }
+
+    pub fn pause_status(&self, querier: &QuerierWrapper) -> StdResult<PauseStatusResponse> {
+        self.query(querier, QueryMsg::PauseStatus {})
+    }
+
+    pub fn payout_simulation(
+        &self,
+        querier: &QuerierWrapper,
+        collection: Collection,
+        token_id: TokenId,
+        price: Uint128,
+    ) -> StdResult<PayoutSimulationResponse> {
+        let msg = QueryMsg::PayoutSimulation {
+            collection,
+            token_id,
+            price,
+        };
+        self.query(querier, msg)
+    }
+}
+
+fn native_funds(amount: Uint128) -> Vec<Coin> {
+    coins(amount.u128(), NATIVE_DENOM)
 }
 
 pub fn map_validate(api: &dyn Api, addresses: &[String]) -> StdResult<Vec<Addr>> {
@@ -78,6 +817,7 @@ impl ExpiryRange {
 mod tests {
     use super::*;
     use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{from_binary, ContractResult, SystemResult, WasmQuery};
 
     #[test]
     fn test_map_validate() {
@@ -114,4 +854,115 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_message_builders() {
+        let marketplace = MarketplaceContract(Addr::unchecked("marketplace"));
+
+        let msg = marketplace
+            .set_bid("collection1", "Test.1", Uint128::new(100))
+            .unwrap();
+        assert_eq!(
+            msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "marketplace".to_string(),
+                msg: to_binary(&ExecuteMsg::SetBid {
+                    collection: "collection1".to_string(),
+                    token_id: "Test.1".to_string(),
+                })
+                .unwrap(),
+                funds: coins(100, NATIVE_DENOM),
+            })
+        );
+
+        let msg = marketplace
+            .send_nft_to_list(
+                Addr::unchecked("collection1"),
+                "Test.1",
+                SaleType::FixedPrice,
+                Uint128::new(100),
+                None,
+                200,
+            )
+            .unwrap();
+        let (contract_addr, msg) = match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => (contract_addr, msg),
+            _ => panic!("expected a wasm execute"),
+        };
+        assert_eq!(contract_addr, "collection1");
+        match from_binary(&msg).unwrap() {
+            Cw721ExecuteMsg::SendNft {
+                contract,
+                token_id,
+                msg,
+            } => {
+                assert_eq!(contract, "marketplace");
+                assert_eq!(token_id, "Test.1");
+                let ask_info = match from_binary(&msg).unwrap() {
+                    ReceiveNftMsg::V1(ReceiveNftMsgV1::SetAsk(ask_info)) => ask_info,
+                    _ => panic!("expected SetAsk"),
+                };
+                assert_eq!(ask_info.price, Coin::new(100, NATIVE_DENOM));
+                assert_eq!(ask_info.expires, 200);
+            }
+            _ => panic!("expected SendNft"),
+        }
+
+        let ask_info = AskInfo {
+            sale_type: SaleType::FixedPrice,
+            collection: Addr::unchecked("collection1"),
+            token_id: "Test.1".to_string(),
+            price: Coin::new(100, NATIVE_DENOM),
+            funds_recipient: None,
+            expires: 200,
+        };
+        let msg = marketplace
+            .batch_set_ask(vec![ask_info.clone()], Uint128::new(5))
+            .unwrap();
+        assert_eq!(
+            msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "marketplace".to_string(),
+                msg: to_binary(&ExecuteMsg::BatchSetAsk { asks: vec![ask_info.clone()] }).unwrap(),
+                funds: coins(5, NATIVE_DENOM),
+            })
+        );
+        let msg = marketplace.batch_set_ask(vec![ask_info], Uint128::zero()).unwrap();
+        assert!(matches!(msg, CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) if funds.is_empty()));
+
+        // a summed bid price that overflows is an error rather than a panic
+        let batch_bid = |price: u128| BatchBid {
+            collection: "collection1".to_string(),
+            token_id: "Test.1".to_string(),
+            price: Uint128::new(price),
+        };
+        let err = marketplace
+            .batch_set_bid(vec![batch_bid(u128::MAX), batch_bid(1)], true)
+            .unwrap_err();
+        assert!(matches!(err, StdError::Overflow { .. }));
+    }
+
+    #[test]
+    fn test_typed_queries() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => match from_binary(msg).unwrap() {
+                QueryMsg::SaleHooks {} => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&HooksResponse {
+                        hooks: vec!["rewards".to_string()],
+                    })
+                    .unwrap(),
+                )),
+                _ => panic!("unexpected query"),
+            },
+            _ => panic!("unexpected query"),
+        });
+
+        let marketplace = MarketplaceContract(Addr::unchecked("marketplace"));
+        let querier = QuerierWrapper::new(&deps.querier);
+        let res = marketplace.sale_hooks(&querier).unwrap();
+        assert_eq!(res.hooks, vec!["rewards".to_string()]);
+    }
 }
@@ -1,10 +1,18 @@
+pub mod bundle;
 mod error;
+pub mod escrow;
 pub mod execute;
 mod helpers;
+pub mod migrate;
 pub mod msg;
+pub mod offer;
 pub mod query;
+pub mod rescue;
+pub mod signed_order;
 pub mod state;
 pub mod sudo;
+pub mod swap;
+pub mod trait_bid;
 
 pub use error::ContractError;
 pub use helpers::{ExpiryRange, ExpiryRangeError, MarketplaceContract};
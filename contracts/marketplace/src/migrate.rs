@@ -0,0 +1,391 @@
+use crate::error::ContractError;
+use crate::execute::{only_operator, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::msg::{Metadata, MigrateMsg, NftInfoResponse, Trait};
+use crate::escrow::{add_counted_escrow, bid_escrow, bundle_escrow, Escrowed};
+use crate::state::{
+    asks, bids, bundle_asks, offers, swaps, trait_bids, Ask, AskKey, Bid, BidKey, Custody,
+    EscrowTotals, OfferKey, RecordMigration, SaleType, TokenId, CUSTODY, ESCROW_TOTALS,
+    RECORD_MIGRATION, SUDO_PARAMS,
+};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Timestamp,
+    Uint128,
+};
+use cw721_base::QueryMsg as Cw721QueryMsg;
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::{Bound, Map};
+use cw_utils::nonpayable;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+// Batch limits for rewriting records
+const DEFAULT_BATCH_LIMIT: u32 = 30;
+const MAX_BATCH_LIMIT: u32 = 100;
+
+type MigrationStep = fn(DepsMut) -> Result<(), ContractError>;
+
+/// Ordered migration steps, each run when migrating from a version below it
+const MIGRATIONS: &[(&str, MigrationStep)] =
+    &[("1.1.0", migrate_1_1_0), ("1.2.0", migrate_1_2_0)];
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::CannotMigrate {
+            previous_contract: stored.contract,
+        });
+    }
+
+    let from = parse_version(&stored.version)?;
+    let to = parse_version(CONTRACT_VERSION)?;
+    if from > to {
+        return Err(ContractError::CannotMigrateDowngrade {
+            from: stored.version,
+            to: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", from.to_string())
+        .add_attribute("to_version", to.to_string());
+
+    for (version, step) in MIGRATIONS {
+        let version = parse_version(version)?;
+        if from < version && version <= to {
+            step(deps.branch())?;
+            res = res.add_attribute("migration_step", version.to_string());
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(res)
+}
+
+fn parse_version(version: &str) -> Result<Version, ContractError> {
+    Version::parse(version).map_err(|_| ContractError::InvalidContractVersion {})
+}
+
+/// 1.1.0 added `trading_fee_percent`, `fee_recipient`, `max_royalty_rate` and
+/// `require_factory_check` to `SudoParams`, and rebuilds the existing `bids_count`,
+/// `content_type` and `asks__expiration` indexes by rewriting the records
+fn migrate_1_1_0(deps: DepsMut) -> Result<(), ContractError> {
+    // Re-serialize so the fields added with serde defaults are persisted
+    let params = SUDO_PARAMS.load(deps.storage)?;
+    SUDO_PARAMS.save(deps.storage, &params)?;
+
+    // Asks and bids are too many to rewrite in one transaction, operators
+    // work through them with `MigrateRecords`
+    start_record_migration(deps.storage)?;
+
+    Ok(())
+}
+
+/// 1.2.0 added the `asks__traits` index, asks listed before it read their traits from the NFT,
+/// custody records, kept for asks listed before them from the migration on, and the running
+/// escrow totals, counted for the records stored before them
+fn migrate_1_2_0(deps: DepsMut) -> Result<(), ContractError> {
+    start_record_migration(deps.storage)?;
+
+    Ok(())
+}
+
+/// Starts rewriting the stored records. The escrow totals are counted again from zero along
+/// the way, a record changed before the count reaches it is left to the count.
+fn start_record_migration(store: &mut dyn Storage) -> StdResult<()> {
+    RECORD_MIGRATION.save(store, &RecordMigration::Asks { start_after: None })?;
+    ESCROW_TOTALS.save(store, &EscrowTotals::default())
+}
+
+/// `Ask` as stored by earlier versions, with fields added since then defaulted
+#[derive(Serialize, Deserialize)]
+struct StoredAsk {
+    sale_type: SaleType,
+    collection: Addr,
+    token_id: TokenId,
+    #[serde(default)]
+    img_url: String,
+    seller: Addr,
+    price: Uint128,
+    funds_recipient: Option<Addr>,
+    expires_at: Timestamp,
+    max_bid: Option<Uint128>,
+    max_bidder: Option<Addr>,
+    #[serde(default)]
+    bid_count: Uint128,
+    #[serde(default)]
+    content_type: String,
+    #[serde(default)]
+    traits: Vec<Trait>,
+}
+
+impl From<StoredAsk> for Ask {
+    fn from(ask: StoredAsk) -> Self {
+        Ask {
+            sale_type: ask.sale_type,
+            collection: ask.collection,
+            token_id: ask.token_id,
+            img_url: ask.img_url,
+            seller: ask.seller,
+            price: ask.price,
+            funds_recipient: ask.funds_recipient,
+            expires_at: ask.expires_at,
+            max_bid: ask.max_bid,
+            max_bidder: ask.max_bidder,
+            bid_count: ask.bid_count,
+            content_type: ask.content_type,
+            traits: ask.traits,
+        }
+    }
+}
+
+/// `Bid` as stored by earlier versions, bids without an `active` flag are live
+#[derive(Serialize, Deserialize)]
+struct StoredBid {
+    collection: Addr,
+    token_id: TokenId,
+    bidder: Addr,
+    price: Uint128,
+    active: Option<bool>,
+    #[serde(default)]
+    time: Timestamp,
+}
+
+impl From<StoredBid> for Bid {
+    fn from(bid: StoredBid) -> Self {
+        Bid::new(
+            bid.collection,
+            bid.token_id,
+            bid.bidder,
+            bid.price,
+            bid.active.unwrap_or(true),
+            bid.time,
+        )
+    }
+}
+
+// Raw views of the primary `asks` and `bids` maps
+const STORED_ASKS: Map<AskKey, StoredAsk> = Map::new("asks");
+const STORED_BIDS: Map<BidKey, StoredBid> = Map::new("bids");
+
+/// Rewrites the next batch of asks or bids into the current schema, then counts the funds
+/// escrowed by the next batch of records. Saving a record through its `IndexedMap` also
+/// rebuilds every index entry for it. Operator maintenance is not covered by the pause
+/// switches, records may need migrating while trading is halted.
+pub fn execute_migrate_records(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    only_operator(deps.storage, &info)?;
+
+    let stage = RECORD_MIGRATION
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoRecordMigration {})?;
+    let limit = limit.unwrap_or(DEFAULT_BATCH_LIMIT).min(MAX_BATCH_LIMIT) as usize;
+
+    let (migrated, next) = match stage {
+        RecordMigration::Asks { start_after } => {
+            let keys = rewrite_asks(deps.branch(), &env, start_after, limit)?;
+            let next = continue_after(&keys, limit, |k| RecordMigration::Asks { start_after: k })
+                .or(Some(RecordMigration::Bids { start_after: None }));
+            (keys.len(), next)
+        }
+        RecordMigration::Bids { start_after } => {
+            let keys = rewrite_bids(deps.storage, start_after, limit)?;
+            let next = continue_after(&keys, limit, |k| RecordMigration::Bids { start_after: k })
+                .or(Some(RecordMigration::Bundles { start_after: None }));
+            (keys.len(), next)
+        }
+        RecordMigration::Bundles { start_after } => {
+            let keys = count_bundles(deps.storage, &env.contract.address, start_after, limit)?;
+            let next = continue_after(&keys, limit, |k| RecordMigration::Bundles { start_after: k })
+                .or(Some(RecordMigration::Offers { start_after: None }));
+            (keys.len(), next)
+        }
+        RecordMigration::Offers { start_after } => {
+            let keys = count_offers(deps.storage, start_after, limit)?;
+            let next = continue_after(&keys, limit, |k| RecordMigration::Offers { start_after: k })
+                .or(Some(RecordMigration::Swaps { start_after: None }));
+            (keys.len(), next)
+        }
+        RecordMigration::Swaps { start_after } => {
+            let keys = count_swaps(deps.storage, start_after, limit)?;
+            let next = continue_after(&keys, limit, |k| RecordMigration::Swaps { start_after: k })
+                .or(Some(RecordMigration::TraitBids { start_after: None }));
+            (keys.len(), next)
+        }
+        RecordMigration::TraitBids { start_after } => {
+            let keys = count_trait_bids(deps.storage, start_after, limit)?;
+            let next =
+                continue_after(&keys, limit, |k| RecordMigration::TraitBids { start_after: k });
+            (keys.len(), next)
+        }
+    };
+
+    match next {
+        Some(next) => RECORD_MIGRATION.save(deps.storage, &next)?,
+        None => RECORD_MIGRATION.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("human_action", "human_marketplace_migrate_records")
+        .add_attribute("migrated", migrated.to_string())
+        .add_attribute("done", RECORD_MIGRATION.may_load(deps.storage)?.is_none().to_string()))
+}
+
+/// The stage resuming after the last key of a full batch, none once a batch comes up short
+fn continue_after<K: Clone>(
+    keys: &[K],
+    limit: usize,
+    stage: impl Fn(Option<K>) -> RecordMigration,
+) -> Option<RecordMigration> {
+    match keys.last() {
+        Some(last) if keys.len() == limit => Some(stage(Some(last.clone()))),
+        _ => None,
+    }
+}
+
+fn rewrite_asks(
+    deps: DepsMut,
+    env: &Env,
+    start_after: Option<AskKey>,
+    limit: usize,
+) -> Result<Vec<AskKey>, ContractError> {
+    let stored = STORED_ASKS
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut keys = vec![];
+    for (key, mut stored_ask) in stored {
+        if stored_ask.traits.is_empty() {
+            stored_ask.traits = nft_traits(deps.as_ref(), &stored_ask);
+        }
+        let ask: Ask = stored_ask.into();
+        asks().replace(deps.storage, key.clone(), Some(&ask), Some(&ask))?;
+        // Asks listed before custody records hold the seller's NFT since an unknown time
+        if !CUSTODY.has(deps.storage, key.clone()) {
+            let custody = Custody {
+                depositor: ask.seller,
+                deposited_at: env.block.time,
+            };
+            CUSTODY.save(deps.storage, key.clone(), &custody)?;
+        }
+        keys.push(key);
+    }
+    Ok(keys)
+}
+
+/// Traits of a listed NFT. A collection that no longer answers must not stall the
+/// migration, its asks stay out of the trait index instead.
+fn nft_traits(deps: Deps, ask: &StoredAsk) -> Vec<Trait> {
+    deps.querier
+        .query_wasm_smart::<NftInfoResponse<Metadata>>(
+            &ask.collection,
+            &Cw721QueryMsg::NftInfo {
+                token_id: ask.token_id.clone(),
+            },
+        )
+        .map(|nft_info| nft_info.extension.attributes)
+        .unwrap_or_default()
+}
+
+fn rewrite_bids(
+    store: &mut dyn Storage,
+    start_after: Option<BidKey>,
+    limit: usize,
+) -> Result<Vec<BidKey>, ContractError> {
+    let stored = STORED_BIDS
+        .range(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut keys = vec![];
+    for (key, stored_bid) in stored {
+        let bid: Bid = stored_bid.into();
+        bids().replace(store, key.clone(), Some(&bid), Some(&bid))?;
+        add_counted_escrow(store, Escrowed::Bid(&key), bid_escrow(&bid))?;
+        keys.push(key);
+    }
+    Ok(keys)
+}
+
+fn count_bundles(
+    store: &mut dyn Storage,
+    marketplace: &Addr,
+    start_after: Option<u64>,
+    limit: usize,
+) -> StdResult<Vec<u64>> {
+    let bundles = bundle_asks()
+        .range(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut keys = vec![];
+    for (id, bundle) in bundles {
+        add_counted_escrow(store, Escrowed::Bundle(id), bundle_escrow(&bundle, marketplace))?;
+        keys.push(id);
+    }
+    Ok(keys)
+}
+
+fn count_offers(
+    store: &mut dyn Storage,
+    start_after: Option<OfferKey>,
+    limit: usize,
+) -> StdResult<Vec<OfferKey>> {
+    let stored = offers()
+        .range(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut keys = vec![];
+    for (key, offer) in stored {
+        add_counted_escrow(store, Escrowed::Offer(&key), offer.price)?;
+        keys.push(key);
+    }
+    Ok(keys)
+}
+
+fn count_swaps(
+    store: &mut dyn Storage,
+    start_after: Option<u64>,
+    limit: usize,
+) -> StdResult<Vec<u64>> {
+    let stored = swaps()
+        .range(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut keys = vec![];
+    for (id, swap) in stored {
+        add_counted_escrow(store, Escrowed::Swap(id), swap.offered_funds)?;
+        keys.push(id);
+    }
+    Ok(keys)
+}
+
+fn count_trait_bids(
+    store: &mut dyn Storage,
+    start_after: Option<u64>,
+    limit: usize,
+) -> StdResult<Vec<u64>> {
+    let stored = trait_bids()
+        .range(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut keys = vec![];
+    for (id, bid) in stored {
+        add_counted_escrow(store, Escrowed::TraitBid(id), bid.price)?;
+        keys.push(id);
+    }
+    Ok(keys)
+}
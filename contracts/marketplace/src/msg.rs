@@ -1,11 +1,17 @@
 use crate::{
+    error::ContractError,
     helpers::ExpiryRange,
-    state::{Ask, Bid, CollectionBid, SudoParams, TokenId},
+    state::{
+        Ask, Bid, BundleAsk, CollectionBid, Custody, NonceStatus, Offer, CollectionConfig, PauseState, RecordMigration, SaleOrigin, SaleType,
+        SudoParams, Swap, TokenId, TraitBid,
+    },
 };
-use cosmwasm_std::{to_binary, Addr, Binary, Coin, StdResult, Uint128};
+use cosmwasm_std::{from_slice, to_binary, Addr, Binary, Coin, Decimal, StdResult, Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use cw2::ContractVersion;
 use cw721::Cw721ReceiveMsg;
+use std::fmt;
 
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -14,9 +20,11 @@ pub struct MigrateMsg {}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
-    /// Fair Burn fee for winning bids
+    /// Protocol fee for winning bids
     /// 0.25% = 25, 0.5% = 50, 1% = 100, 2.5% = 250
     pub trading_fee_bps: u64,
+    /// Receives the protocol fee, no fee is charged while unset
+    pub fee_recipient: Option<String>,
     /// Valid time range for Asks
     /// (min, max) in seconds
     pub ask_expiry: ExpiryRange,
@@ -70,6 +78,171 @@ pub enum ExecuteMsg {
         collection: String,
         token_id: TokenId,
     },
+    /// Bid on or buy several NFTs at once, the funds sent must equal the summed prices.
+    /// If `atomic` is false, bids that cannot be placed are skipped and refunded.
+    BatchSetBid { bids: Vec<BatchBid>, atomic: bool },
+    /// Buy the cheapest fixed price NFTs of a collection, at most `max_items` of them costing no
+    /// more than `max_unit_price` each. The funds sent must equal `max_total`, the rest is refunded.
+    SweepFloor {
+        collection: String,
+        max_items: u32,
+        max_total: Uint128,
+        max_unit_price: Uint128,
+    },
+    /// List several NFTs as one ask. The marketplace must be approved for every token,
+    /// which is escrowed when the bundle is created. `allocations` splits the price between
+    /// the collections for royalties and may be left empty for a single collection bundle.
+    SetBundleAsk {
+        sale_type: SaleType,
+        items: Vec<NftRef>,
+        allocations: Vec<BundleShare>,
+        price: Coin,
+        funds_recipient: Option<String>,
+        expires: u64,
+    },
+    /// Buy a fixed price bundle or bid on a bundle auction
+    SetBundleBid { id: u64 },
+    /// Remove a bundle ask, returning its NFTs and refunding the highest bid
+    RemoveBundleAsk { id: u64 },
+    /// Settle an ended bundle auction, or take back the NFTs of an unsold bundle
+    AcceptBundleBid { id: u64 },
+    /// Offer NFTs, and optionally the funds sent, in exchange for the requested NFTs. The
+    /// marketplace must be approved for the offered NFTs, which are escrowed until the swap
+    /// is accepted or cancelled. Only `counterparty` may accept if set.
+    CreateSwap {
+        offered: Vec<NftRef>,
+        requested: Vec<NftRef>,
+        counterparty: Option<String>,
+        expires: u64,
+    },
+    /// Accept a swap, the marketplace must be approved for the requested NFTs.
+    /// A swap can also be accepted by sending a requested NFT with `ReceiveNftMsgV1::AcceptSwap`.
+    AcceptSwap { id: u64 },
+    /// Cancel a swap and return its escrow to the proposer. Anyone may cancel an expired swap.
+    CancelSwap { id: u64 },
+    /// Make an offer below the price of a fixed price ask, escrowing the funds sent
+    SetOffer {
+        collection: String,
+        token_id: TokenId,
+        expires: u64,
+    },
+    /// Withdraw an offer and its funds. Offers stay withdrawable after the ask is gone.
+    RemoveOffer {
+        collection: String,
+        token_id: TokenId,
+    },
+    /// Sell the NFT of a fixed price ask for an offer, only callable by the seller
+    AcceptOffer {
+        collection: String,
+        token_id: TokenId,
+        buyer: String,
+    },
+    /// Reply to an offer with a private price only its buyer can fill, only callable by the seller
+    CounterOffer {
+        collection: String,
+        token_id: TokenId,
+        buyer: String,
+        price: Uint128,
+    },
+    /// Buy the NFT at the seller's counter price, sending the difference to the offer
+    AcceptCounterOffer {
+        collection: String,
+        token_id: TokenId,
+    },
+    /// Bid on any NFT of a collection with the trait `trait_type`: `value`, escrowing the
+    /// funds sent. Holders accept it by sending a matching NFT with `ReceiveNftMsgV1::AcceptTraitBid`.
+    SetTraitBid {
+        collection: String,
+        trait_type: String,
+        value: String,
+        expires: u64,
+    },
+    /// Withdraw a trait bid and its funds. Anyone may remove an expired trait bid.
+    RemoveTraitBid { id: u64 },
+    /// Register the secp256k1 public key the sender signs orders with. Replacing the key
+    /// invalidates every order signed with the previous one.
+    SetOrderKey { pubkey: Binary },
+    /// Cancel signed orders of the sender by nonce
+    CancelSignedOrders { nonces: Vec<u64> },
+    /// Buy an NFT through an order signed off-chain by its seller, sending the order price.
+    /// The seller must have approved the marketplace for the NFT.
+    FillSignedOrder {
+        order: SignedOrder,
+        /// 64 byte compact secp256k1 signature of the SHA-256 of `OrderSignDoc`
+        signature: Binary,
+    },
+    /// List several NFTs of the sender at once, sending the summed listing fees.
+    /// The sender must have approved the marketplace for every NFT.
+    BatchSetAsk { asks: Vec<AskInfo> },
+    /// Remove several asks of the sender at once
+    BatchRemoveAsk { asks: Vec<NftRef> },
+    /// Update the price of several asks of the sender at once
+    BatchUpdateAskPrice { updates: Vec<AskPriceUpdate> },
+    /// Override the listing policy for a collection, only callable by the collection admin.
+    /// An empty config removes the overrides.
+    SetCollectionConfig {
+        collection: String,
+        config: CollectionConfig,
+    },
+    /// Rewrite the next batch of stored asks and bids after a migration, then count the escrow
+    /// of the next batch of records. Only callable by operators
+    MigrateRecords { limit: Option<u32> },
+    /// Return an NFT held by the marketplace without an ask to the address that listed it,
+    /// only callable by operators
+    RescueNft {
+        collection: String,
+        token_id: TokenId,
+        recipient: String,
+    },
+}
+
+/// A single bid of a `BatchSetBid`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BatchBid {
+    pub collection: String,
+    pub token_id: TokenId,
+    pub price: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NftRef {
+    pub collection: String,
+    pub token_id: TokenId,
+}
+
+/// A fixed price sale signed off-chain by the seller
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SignedOrder {
+    pub seller: String,
+    pub collection: String,
+    pub token_id: TokenId,
+    pub price: Coin,
+    pub expires_at: Timestamp,
+    pub nonce: u64,
+}
+
+/// What a seller signs for an order, binding it to one marketplace on one chain.
+/// The signed message is the SHA-256 of its JSON serialization.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderSignDoc {
+    pub chain_id: String,
+    pub marketplace: String,
+    pub order: SignedOrder,
+}
+
+/// Share of a bundle price declared for one of its collections
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BundleShare {
+    pub collection: String,
+    pub share: Decimal,
+}
+
+/// A single price update of a `BatchUpdateAskPrice`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AskPriceUpdate {
+    pub collection: String,
+    pub token_id: TokenId,
+    pub price: Coin,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -78,11 +251,44 @@ pub enum SudoMsg {
     /// Update the contract parameters
     /// Can only be called by governance
     UpdateParams {
+        trading_fee_bps: Option<u64>,
         ask_expiry: Option<ExpiryRange>,
         bid_expiry: Option<ExpiryRange>,
         operators: Option<Vec<String>>,
         min_price: Option<Uint128>,
         listing_fee: Option<Uint128>,
+        require_factory_check: Option<bool>,
+        max_royalty_rate: Option<Decimal>,
+        /// Removes the royalty cap, takes precedence over `max_royalty_rate`
+        #[serde(default)]
+        remove_max_royalty_rate: bool,
+        fee_recipient: Option<String>,
+        /// Removes the fee recipient, which stops the protocol fee. Takes precedence over
+        /// `fee_recipient`
+        #[serde(default)]
+        remove_fee_recipient: bool,
+    },
+    /// Put a collection on the marketplace allowlist
+    AllowCollection { collection: String },
+    /// Put a collection on the marketplace denylist
+    DenyCollection { collection: String },
+    /// Remove a collection from the allow or deny list
+    RemoveCollection { collection: String },
+    /// Re-enable listing for a disabled collection
+    EnableCollection { collection: String },
+    /// Disable listing for a collection
+    DisableCollection { collection: String, reason: String },
+    /// Override the listing policy for a collection. An empty config removes the overrides.
+    SetCollectionConfig {
+        collection: String,
+        config: CollectionConfig,
+    },
+    /// Set the emergency pause switches
+    SetPause {
+        listing: bool,
+        bidding: bool,
+        settlement: bool,
+        removal: bool,
     },
     /// Add a new operator
     AddOperator { operator: String },
@@ -100,6 +306,87 @@ pub enum SudoMsg {
     AddSaleHook { hook: String },
     /// Remove a trade hook
     RemoveSaleHook { hook: String },
+    /// Add a new hook to be informed of all trades with the `SaleHookMsgV2` payload
+    AddSaleHookV2 { hook: String },
+    /// Remove a v2 trade hook
+    RemoveSaleHookV2 { hook: String },
+    /// Add a new hook to be informed of all swaps
+    AddSwapHook { hook: String },
+    /// Remove a swap hook
+    RemoveSwapHook { hook: String },
+    /// Add a new hook to be informed of all offers
+    AddOfferHook { hook: String },
+    /// Remove an offer hook
+    RemoveOfferHook { hook: String },
+    /// Add a new hook to be informed of all trait bids
+    AddTraitBidHook { hook: String },
+    /// Remove a trait bid hook
+    RemoveTraitBidHook { hook: String },
+    /// Send funds above the escrow liabilities, like collected fees, to `recipient`.
+    /// Sends the whole surplus unless `amount` is set.
+    RecoverSurplus {
+        recipient: String,
+        amount: Option<Uint128>,
+    },
+    /// Send an NFT held by the marketplace without an ask to any recipient
+    RescueNft {
+        collection: String,
+        token_id: TokenId,
+        recipient: String,
+    },
+}
+
+/// Listing of an NFT sent to the marketplace
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AskInfo {
+    pub sale_type: SaleType,
+    /// Must be the collection sending the NFT
+    pub collection: Addr,
+    /// Must be the token sent
+    pub token_id: TokenId,
+    pub price: Coin,
+    pub funds_recipient: Option<Addr>,
+    /// Seconds from now the ask expires in
+    pub expires: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BidInfo {
+    pub collection: Addr,
+    pub token_id: TokenId,
+}
+
+/// Payload of the `Cw721ReceiveMsg` sent along with an NFT, tagged with its version.
+/// A bare `AskInfo`, the payload of earlier versions, is still read as a v1 `SetAsk`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveNftMsg {
+    V1(ReceiveNftMsgV1),
+}
+
+/// Offers and auction bids are made on asks whose NFT the marketplace already holds, so they
+/// are accepted with `ExecuteMsg::AcceptOffer` and `ExecuteMsg::AcceptBid` instead. Bids on a
+/// whole collection are trait bids, accepted with `AcceptTraitBid`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveNftMsgV1 {
+    /// List the sent NFT
+    SetAsk(AskInfo),
+    /// Accept a swap with the sent NFT, any other requested NFTs must be approved
+    AcceptSwap { id: u64 },
+    /// Sell the sent NFT to a trait bid, the NFT must have the trait of the bid
+    AcceptTraitBid { id: u64 },
+}
+
+impl ReceiveNftMsg {
+    pub fn decode(msg: &Binary) -> Result<ReceiveNftMsgV1, ContractError> {
+        match from_slice::<ReceiveNftMsg>(msg) {
+            Ok(ReceiveNftMsg::V1(msg)) => Ok(msg),
+            Err(err) => from_slice::<AskInfo>(msg)
+                .map(ReceiveNftMsgV1::SetAsk)
+                .map_err(|_| ContractError::InvalidReceiveNftMsg(err.to_string())),
+        }
+    }
 }
 
 pub type Collection = String;
@@ -197,12 +484,24 @@ impl CollectionBidOffset {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    /// List of collections that have asks on them
+    /// List of collections that have asks on them, with their verification status
     /// Return type: `CollectionsResponse`
     Collections {
         start_after: Option<Collection>,
         limit: Option<u32>,
     },
+    /// List of collections in the marketplace registry
+    /// Return type: `CollectionsResponse`
+    RegisteredCollections {
+        start_after: Option<Collection>,
+        limit: Option<u32>,
+    },
+    /// Get the verification status of a collection
+    /// Return type: `CollectionStatusResponse`
+    CollectionStatus { collection: Collection },
+    /// Get the policy overrides for a collection
+    /// Return type: `CollectionConfigResponse`
+    CollectionConfig { collection: Collection },
     /// Get the current ask for specific NFT
     /// Return type: `CurrentAskResponse`
     Ask {
@@ -257,6 +556,15 @@ pub enum QueryMsg {
         start_after: Option<AskOffsetSellPrice>,
         limit: Option<u32>
     },
+    /// Get the asks of a collection whose NFT has the trait, by token id
+    /// Return type: `AsksResponse`
+    AsksByTrait {
+        collection: Collection,
+        trait_type: String,
+        value: String,
+        start_after: Option<TokenId>,
+        limit: Option<u32>,
+    },
     //Get top asks which are base on the content-type
     AsksSortedByContentType{
       content_type: String,
@@ -295,6 +603,35 @@ pub enum QueryMsg {
         start_after: Option<Bidder>,
         limit: Option<u32>,
     },
+    /// Get the offer of a buyer on a specific NFT
+    /// Return type: `OfferResponse`
+    Offer {
+        collection: Collection,
+        token_id: TokenId,
+        buyer: String,
+    },
+    /// Get all offers on a specific NFT
+    /// Return type: `OffersResponse`
+    Offers {
+        collection: Collection,
+        token_id: TokenId,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Get all offers made by a buyer
+    /// Return type: `OffersResponse`
+    OffersByBuyer {
+        buyer: String,
+        start_after: Option<CollectionOffset>,
+        limit: Option<u32>,
+    },
+    /// Get all offers made to a seller
+    /// Return type: `OffersResponse`
+    OffersBySeller {
+        seller: String,
+        start_after: Option<OfferOffset>,
+        limit: Option<u32>,
+    },
     /// Get all bids for a collection, sorted by price
     /// Return type: `BidsResponse`
     BidsSortedByPrice {
@@ -318,9 +655,109 @@ pub enum QueryMsg {
     /// Show all registered sale hooks
     /// Return type: `HooksResponse`
     SaleHooks {},
+    /// Show all registered v2 sale hooks
+    /// Return type: `HooksResponse`
+    SaleHooksV2 {},
+    /// Show all registered swap hooks
+    /// Return type: `HooksResponse`
+    SwapHooks {},
+    /// Show all registered offer hooks
+    /// Return type: `HooksResponse`
+    OfferHooks {},
+    /// Show all registered trait bid hooks
+    /// Return type: `HooksResponse`
+    TraitBidHooks {},
+    /// Get a trait bid
+    /// Return type: `TraitBidResponse`
+    TraitBid { id: u64 },
+    /// Get the bids on a trait of a collection, sorted by price
+    /// Return type: `TraitBidsResponse`
+    TraitBidsSortedByPrice {
+        collection: Collection,
+        trait_type: String,
+        value: String,
+        start_after: Option<TraitBidOffset>,
+        limit: Option<u32>,
+    },
+    /// Get the bids on a trait of a collection, sorted by price in reverse
+    /// Return type: `TraitBidsResponse`
+    ReverseTraitBidsSortedByPrice {
+        collection: Collection,
+        trait_type: String,
+        value: String,
+        start_before: Option<TraitBidOffset>,
+        limit: Option<u32>,
+    },
+    /// Get all trait bids of a bidder
+    /// Return type: `TraitBidsResponse`
+    TraitBidsByBidder {
+        bidder: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get a swap
+    /// Return type: `SwapResponse`
+    Swap { id: u64 },
+    /// Get all swaps of a proposer
+    /// Return type: `SwapsResponse`
+    SwapsByProposer {
+        proposer: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get all swaps only the given counterparty may accept
+    /// Return type: `SwapsResponse`
+    SwapsByCounterparty {
+        counterparty: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get a bundle ask
+    /// Return type: `BundleAskResponse`
+    BundleAsk { id: u64 },
+    /// Get all bundle asks of a seller
+    /// Return type: `BundleAsksResponse`
+    BundleAsksBySeller {
+        seller: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get the public key a seller signs orders with
+    /// Return type: `OrderKeyResponse`
+    OrderKey { seller: String },
+    /// Get whether a signed order nonce of a seller was used or cancelled
+    /// Return type: `OrderNonceResponse`
+    OrderNonce { seller: String, nonce: u64 },
+    /// Get the digest a seller has to sign for an order
+    /// Return type: `OrderDigestResponse`
+    OrderDigest { order: SignedOrder },
     /// Get the config for the contract
     /// Return type: `ParamsResponse`
     Params {},
+    /// Get the contract version and pending record migration
+    /// Return type: `MigrationStatusResponse`
+    MigrationStatus {},
+    /// Get the emergency pause switches
+    /// Return type: `PauseStatusResponse`
+    PauseStatus {},
+    /// Breakdown of how a payment for an NFT would be split between
+    /// protocol fee, royalties and seller
+    /// Return type: `PayoutSimulationResponse`
+    PayoutSimulation {
+        collection: Collection,
+        token_id: TokenId,
+        price: Uint128,
+    },
+    /// Get who deposited an NFT the marketplace holds for an ask
+    /// Return type: `CustodyResponse`
+    Custody {
+        collection: Collection,
+        token_id: TokenId,
+    },
+    /// Compare the contract balance with the funds escrowed for bidders, buyers and
+    /// swap proposers
+    /// Return type: `EscrowSummaryResponse`
+    EscrowSummary {},
 }
 
 
@@ -333,6 +770,22 @@ pub enum CreateCollectionQueryMsg {
       CheckCollection{address: String}
 }
 
+/// CW2981 royalty standard query, answered by collections implementing it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw2981QueryMsg {
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltiesInfoResponse {
+    pub address: String,
+    pub royalty_amount: Uint128,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct AskResponse {
     pub ask: Option<Ask>,
@@ -343,14 +796,132 @@ pub struct AsksResponse {
     pub asks: Vec<Ask>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BundleAskResponse {
+    pub bundle: Option<BundleAsk>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BundleAsksResponse {
+    pub bundles: Vec<BundleAsk>,
+}
+
+/// Response of the hook queries, mirroring the one `cw_controllers::Hooks` returns
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HooksResponse {
+    pub hooks: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderKeyResponse {
+    pub pubkey: Option<Binary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderNonceResponse {
+    pub status: Option<NonceStatus>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderDigestResponse {
+    pub digest: Binary,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OfferResponse {
+    pub offer: Option<Offer>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OffersResponse {
+    pub offers: Vec<Offer>,
+}
+
+/// Offset for trait bid pagination by price
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TraitBidOffset {
+    pub price: Uint128,
+    pub id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TraitBidResponse {
+    pub bid: Option<TraitBid>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TraitBidsResponse {
+    pub bids: Vec<TraitBid>,
+}
+
+/// Offset for offer pagination by seller
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OfferOffset {
+    pub collection: Collection,
+    pub token_id: TokenId,
+    pub buyer: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapResponse {
+    pub swap: Option<Swap>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapsResponse {
+    pub swaps: Vec<Swap>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct AskCountResponse {
     pub count: u32,
 }
 
+/// How a collection was (or was not) verified for listing
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionVerification {
+    /// On the marketplace allowlist
+    Allowed,
+    /// Passed the factory `CheckCollection` query
+    FactoryVerified,
+    /// Neither allowlisted nor known to the factory
+    Unverified,
+    /// On the marketplace denylist
+    Denied,
+    /// Disabled by governance
+    Disabled,
+}
+
+impl CollectionVerification {
+    pub fn is_verified(&self) -> bool {
+        matches!(
+            self,
+            CollectionVerification::Allowed | CollectionVerification::FactoryVerified
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionStatus {
+    pub collection: Addr,
+    pub verification: CollectionVerification,
+    pub reason: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct CollectionsResponse {
-    pub collections: Vec<Addr>,
+    pub collections: Vec<CollectionStatus>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionStatusResponse {
+    pub status: CollectionStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionConfigResponse {
+    pub config: Option<CollectionConfig>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -368,6 +939,84 @@ pub struct ParamsResponse {
     pub params: SudoParams,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrationStatusResponse {
+    pub version: ContractVersion,
+    pub records: Option<RecordMigration>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseStatusResponse {
+    pub pause: PauseState,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CustodyResponse {
+    pub custody: Option<Custody>,
+}
+
+/// Native funds the contract holds for others, against its balance
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EscrowSummaryResponse {
+    pub balance: Uint128,
+    /// Highest bids of live and ended, unsettled auctions
+    pub auction_escrow: Uint128,
+    /// Highest bids of bundle auctions
+    pub bundle_escrow: Uint128,
+    pub offer_escrow: Uint128,
+    pub swap_escrow: Uint128,
+    pub trait_bid_escrow: Uint128,
+    /// Sum of all escrows
+    pub liabilities: Uint128,
+    /// Balance above the liabilities, the fees collected so far
+    pub surplus: Uint128,
+    /// Liabilities not covered by the balance, should always be zero
+    pub deficit: Uint128,
+    /// True until `MigrateRecords` has counted the escrow of records stored by earlier
+    /// versions, the liabilities are incomplete until then
+    pub counting: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltyPayout {
+    pub recipient: Addr,
+    pub amount: Uint128,
+}
+
+/// Where the royalty of a sale was looked up
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RoyaltySource {
+    /// CW2981 `RoyaltyInfo` query
+    Cw2981,
+    /// cw721-base `GetCollectionState` query
+    CollectionState,
+    /// The collection supports neither, no royalty is paid
+    None,
+}
+
+impl fmt::Display for RoyaltySource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RoyaltySource::Cw2981 => write!(f, "cw2981"),
+            RoyaltySource::CollectionState => write!(f, "collection_state"),
+            RoyaltySource::None => write!(f, "none"),
+        }
+    }
+}
+
+/// Settlement breakdown, `protocol_fee + royalties + seller_amount == price`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PayoutSimulationResponse {
+    pub price: Uint128,
+    pub protocol_fee: Uint128,
+    /// Receives the protocol fee, which is zero while no recipient is set
+    pub fee_recipient: Option<Addr>,
+    pub royalty_source: RoyaltySource,
+    pub royalties: Vec<RoyaltyPayout>,
+    pub seller_amount: Uint128,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct CollectionBidResponse {
     pub bid: Option<CollectionBid>,
@@ -417,6 +1066,35 @@ impl SaleHookMsg {
 #[serde(rename_all = "snake_case")]
 pub enum SaleExecuteMsg {
     SaleHook(SaleHookMsg),
+    SaleHookV2(SaleHookMsgV2),
+}
+
+/// Sale hook payload describing how the paid amount was settled
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct SaleHookMsgV2 {
+    pub collection: String,
+    pub token_id: String,
+    pub origin: SaleOrigin,
+    /// What the buyer paid, the winning bid for auctions
+    pub price: Coin,
+    pub seller: String,
+    pub buyer: String,
+    pub royalties: Vec<RoyaltyPayout>,
+    pub protocol_fee: Uint128,
+    /// Zero until finder fees are supported, part of the payload so v2 stays stable once they are
+    pub finders_fee: Uint128,
+    /// What the seller, or the ask's funds recipient, received
+    pub seller_amount: Uint128,
+    pub timestamp: Timestamp,
+}
+
+impl SaleHookMsgV2 {
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        let msg = SaleExecuteMsg::SaleHookV2(self);
+        to_binary(&msg)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -489,6 +1167,99 @@ pub enum BidExecuteMsg {
     BidDeletedHook(BidHookMsg),
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct OfferHookMsg {
+    pub offer: Offer,
+}
+
+impl OfferHookMsg {
+    pub fn new(offer: Offer) -> Self {
+        OfferHookMsg { offer }
+    }
+
+    /// serializes the message
+    pub fn into_binary(self, action: HookAction) -> StdResult<Binary> {
+        let msg = match action {
+            HookAction::Create => OfferExecuteMsg::OfferCreatedHook(self),
+            HookAction::Update => OfferExecuteMsg::OfferUpdatedHook(self),
+            HookAction::Delete => OfferExecuteMsg::OfferDeletedHook(self),
+        };
+        to_binary(&msg)
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum OfferExecuteMsg {
+    OfferCreatedHook(OfferHookMsg),
+    OfferUpdatedHook(OfferHookMsg),
+    OfferDeletedHook(OfferHookMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct TraitBidHookMsg {
+    pub trait_bid: TraitBid,
+}
+
+impl TraitBidHookMsg {
+    pub fn new(trait_bid: TraitBid) -> Self {
+        TraitBidHookMsg { trait_bid }
+    }
+
+    /// serializes the message
+    pub fn into_binary(self, action: HookAction) -> StdResult<Binary> {
+        let msg = match action {
+            HookAction::Create => TraitBidExecuteMsg::TraitBidCreatedHook(self),
+            HookAction::Update => TraitBidExecuteMsg::TraitBidUpdatedHook(self),
+            HookAction::Delete => TraitBidExecuteMsg::TraitBidDeletedHook(self),
+        };
+        to_binary(&msg)
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TraitBidExecuteMsg {
+    TraitBidCreatedHook(TraitBidHookMsg),
+    TraitBidUpdatedHook(TraitBidHookMsg),
+    TraitBidDeletedHook(TraitBidHookMsg),
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct SwapHookMsg {
+    pub swap: Swap,
+}
+
+impl SwapHookMsg {
+    pub fn new(swap: Swap) -> Self {
+        SwapHookMsg { swap }
+    }
+
+    /// serializes the message
+    pub fn into_binary(self, action: HookAction) -> StdResult<Binary> {
+        let msg = match action {
+            HookAction::Create => SwapExecuteMsg::SwapCreatedHook(self),
+            HookAction::Update => SwapExecuteMsg::SwapUpdatedHook(self),
+            HookAction::Delete => SwapExecuteMsg::SwapDeletedHook(self),
+        };
+        to_binary(&msg)
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapExecuteMsg {
+    SwapCreatedHook(SwapHookMsg),
+    SwapUpdatedHook(SwapHookMsg),
+    SwapDeletedHook(SwapHookMsg),
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub struct CollectionBidHookMsg {
@@ -540,5 +1311,22 @@ pub struct NftInfoResponse<T> {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Metadata {
     pub image_url: String,
-    pub minter: String
+    pub minter: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub animation_url: Option<String>,
+    #[serde(default)]
+    pub attributes: Vec<Trait>,
+}
+
+/// An NFT attribute, like `Trait` of cw721-base
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Trait {
+    #[serde(default)]
+    pub display_type: Option<String>,
+    pub trait_type: String,
+    pub value: String,
 }
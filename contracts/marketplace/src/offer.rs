@@ -0,0 +1,270 @@
+use crate::error::ContractError;
+use crate::escrow::{track_escrow, Escrowed};
+use crate::execute::{
+    finalize_sale, only_owner_nft, only_unpaused, prepare_offer_hook, remove_ask, Sale,
+    NATIVE_DENOM,
+};
+use crate::msg::HookAction;
+use crate::state::{
+    ask_key, asks, collection_params, offer_key, offers, Ask, Offer, OrderExpire, SaleOrigin,
+    SaleType, TokenId, PAUSE,
+};
+use cosmwasm_std::{coin, Addr, BankMsg, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+use cw_utils::{must_pay, nonpayable};
+
+/// Escrows an offer below the price of a fixed price ask, replacing and refunding an
+/// earlier offer of the same buyer
+pub fn execute_set_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    token_id: TokenId,
+    expires: u64,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.bidding, "bidding")?;
+
+    let price = must_pay(&info, NATIVE_DENOM)?;
+    let ask = load_fixed_price_ask(deps.as_ref(), &env, &collection, &token_id)?;
+
+    let (params, _) = collection_params(deps.storage, &collection)?;
+    if price < params.min_price {
+        return Err(ContractError::PriceTooSmall(price));
+    }
+    if price >= ask.price {
+        return Err(ContractError::OfferNotBelowAsk {});
+    }
+    if info.sender == ask.seller {
+        return Err(ContractError::UnauthorizedOwner {});
+    }
+    params.bid_expiry.is_valid(expires)?;
+
+    let buyer = info.sender;
+    let key = offer_key(&collection, &token_id, &buyer);
+    let mut res = Response::new();
+
+    let existing = offers().may_load(deps.storage, key.clone())?;
+    if let Some(existing) = &existing {
+        res = res.add_message(BankMsg::Send {
+            to_address: buyer.to_string(),
+            amount: vec![coin(existing.price.u128(), NATIVE_DENOM)],
+        });
+    }
+
+    let offer = Offer {
+        collection: collection.clone(),
+        token_id: token_id.clone(),
+        buyer: buyer.clone(),
+        seller: ask.seller,
+        price,
+        counter_price: None,
+        expires_at: env.block.time.plus_seconds(expires),
+    };
+    offers().save(deps.storage, key.clone(), &offer)?;
+    let before = existing.as_ref().map_or(Uint128::zero(), |o| o.price);
+    track_escrow(deps.storage, Escrowed::Offer(&key), before, price)?;
+
+    let action = if existing.is_some() {
+        HookAction::Update
+    } else {
+        HookAction::Create
+    };
+    let hook = prepare_offer_hook(deps.as_ref(), &offer, action)?;
+
+    Ok(res
+        .add_submessages(hook)
+        .add_attribute("human_action", "human_marketplace_set_offer")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("token_id", token_id)
+        .add_attribute("buyer", buyer)
+        .add_attribute("price", price.to_string())
+        .add_attribute("expires", expires.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// Withdraws an offer and refunds the buyer
+pub fn execute_remove_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    token_id: TokenId,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.removal, "removal")?;
+
+    nonpayable(&info)?;
+
+    let key = offer_key(&collection, &token_id, &info.sender);
+    let offer = offers()
+        .may_load(deps.storage, key.clone())?
+        .ok_or(ContractError::OfferNotFound {})?;
+    offers().remove(deps.storage, key.clone())?;
+    track_escrow(deps.storage, Escrowed::Offer(&key), offer.price, Uint128::zero())?;
+
+    let refund_msg = BankMsg::Send {
+        to_address: offer.buyer.to_string(),
+        amount: vec![coin(offer.price.u128(), NATIVE_DENOM)],
+    };
+
+    let hook = prepare_offer_hook(deps.as_ref(), &offer, HookAction::Delete)?;
+
+    Ok(Response::new()
+        .add_message(refund_msg)
+        .add_submessages(hook)
+        .add_attribute("human_action", "human_marketplace_remove_offer")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("token_id", token_id)
+        .add_attribute("buyer", offer.buyer)
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// Sells the NFT to the buyer of an offer at the offered price
+pub fn execute_accept_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    token_id: TokenId,
+    buyer: Addr,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.settlement, "settlement")?;
+
+    nonpayable(&info)?;
+
+    let ask = load_fixed_price_ask(deps.as_ref(), &env, &collection, &token_id)?;
+    only_owner_nft(&info, ask.seller.clone())?;
+
+    let offer = load_live_offer(deps.as_ref(), &env, &ask, &buyer)?;
+    let price = offer.price;
+
+    settle_offer(deps, env, ask, offer, price, "human_marketplace_accept_offer")
+}
+
+/// Replies to an offer with a private counter price only its buyer can fill
+#[allow(clippy::too_many_arguments)]
+pub fn execute_counter_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    token_id: TokenId,
+    buyer: Addr,
+    price: Uint128,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    // A counter price reprices the ask for one buyer
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.listing, "listing")?;
+
+    let ask = load_fixed_price_ask(deps.as_ref(), &env, &collection, &token_id)?;
+    only_owner_nft(&info, ask.seller.clone())?;
+
+    let mut offer = load_live_offer(deps.as_ref(), &env, &ask, &buyer)?;
+    if price <= offer.price || price >= ask.price {
+        return Err(ContractError::InvalidCounterPrice {});
+    }
+
+    offer.counter_price = Some(price);
+    offers().save(deps.storage, offer_key(&collection, &token_id, &buyer), &offer)?;
+
+    let hook = prepare_offer_hook(deps.as_ref(), &offer, HookAction::Update)?;
+
+    Ok(Response::new()
+        .add_submessages(hook)
+        .add_attribute("human_action", "human_marketplace_counter_offer")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("token_id", token_id)
+        .add_attribute("buyer", buyer)
+        .add_attribute("price", price.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// Buys the NFT at the counter price, the funds sent top up the escrowed offer
+pub fn execute_accept_counter_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    token_id: TokenId,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.settlement, "settlement")?;
+
+    let ask = load_fixed_price_ask(deps.as_ref(), &env, &collection, &token_id)?;
+    let offer = load_live_offer(deps.as_ref(), &env, &ask, &info.sender)?;
+    let counter_price = offer.counter_price.ok_or(ContractError::NoCounterOffer {})?;
+
+    let top_up = must_pay(&info, NATIVE_DENOM)?;
+    if offer.price + top_up != counter_price {
+        return Err(ContractError::InvalidPrice {});
+    }
+
+    settle_offer(deps, env, ask, offer, counter_price, "human_marketplace_accept_counter_offer")
+}
+
+fn settle_offer(
+    deps: DepsMut,
+    env: Env,
+    ask: Ask,
+    offer: Offer,
+    price: Uint128,
+    action: &str,
+) -> Result<Response, ContractError> {
+    let key = offer_key(&offer.collection, &offer.token_id, &offer.buyer);
+    offers().remove(deps.storage, key.clone())?;
+    track_escrow(deps.storage, Escrowed::Offer(&key), offer.price, Uint128::zero())?;
+    remove_ask(deps.storage, ask_key(&ask.collection, &ask.token_id))?;
+
+    let mut res = Response::new();
+    let sale = Sale::of_ask(&ask, price, offer.buyer.clone(), SaleOrigin::Offer);
+    finalize_sale(deps.as_ref(), &env, sale, &mut res)?;
+
+    let hook = prepare_offer_hook(deps.as_ref(), &offer, HookAction::Delete)?;
+
+    Ok(res
+        .add_submessages(hook)
+        .add_attribute("human_action", action)
+        .add_attribute("collection", ask.collection.to_string())
+        .add_attribute("token_id", ask.token_id)
+        .add_attribute("buyer", offer.buyer)
+        .add_attribute("seller", ask.seller)
+        .add_attribute("price", price.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+fn load_fixed_price_ask(
+    deps: Deps,
+    env: &Env,
+    collection: &Addr,
+    token_id: &TokenId,
+) -> Result<Ask, ContractError> {
+    let ask = asks()
+        .may_load(deps.storage, ask_key(collection, token_id))?
+        .ok_or(ContractError::AskNotFound {})?;
+
+    if ask.sale_type != SaleType::FixedPrice {
+        return Err(ContractError::OfferOnAuction {});
+    }
+    if ask.is_expired(&env.block) {
+        return Err(ContractError::AskExpired {});
+    }
+
+    Ok(ask)
+}
+
+/// Loads an unexpired offer made on the current listing of the ask
+fn load_live_offer(deps: Deps, env: &Env, ask: &Ask, buyer: &Addr) -> Result<Offer, ContractError> {
+    let offer = offers()
+        .may_load(deps.storage, offer_key(&ask.collection, &ask.token_id, buyer))?
+        .filter(|offer| offer.seller == ask.seller)
+        .ok_or(ContractError::OfferNotFound {})?;
+
+    if offer.is_expired(&env.block) {
+        return Err(ContractError::OfferExpired {});
+    }
+
+    Ok(offer)
+}
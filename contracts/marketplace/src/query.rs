@@ -1,12 +1,17 @@
+use crate::escrow::escrow_summary;
+use crate::execute::{simulate_payout, verify_collection};
+use crate::signed_order::order_digest;
 use crate::msg::{
-    AskCountResponse, AskOffset, AskResponse, AsksResponse, BidOffset, BidResponse, Bidder,
-    BidsResponse, Collection, CollectionOffset, CollectionsResponse, ParamsResponse, QueryMsg, AskOffsetBidCount, AskOffsetSellPrice, AsksOffsetExpiration,
+    AskCountResponse, CollectionConfigResponse, MigrationStatusResponse, PauseStatusResponse, CollectionStatusResponse, AskOffset, AskResponse, AsksResponse, BidOffset, BidResponse, Bidder,
+    BidsResponse, OrderDigestResponse, OrderKeyResponse, OrderNonceResponse, OfferOffset, OfferResponse, OffersResponse, BundleAskResponse, BundleAsksResponse, CustodyResponse, SwapResponse, SwapsResponse, TraitBidOffset, TraitBidResponse, TraitBidsResponse, Collection, CollectionOffset, CollectionsResponse, ParamsResponse, QueryMsg, AskOffsetBidCount, AskOffsetSellPrice,
 };
 use crate::state::{
-    ask_key, asks, bid_key, bids, BidKey, TokenId, ASK_HOOKS, BID_HOOKS, SALE_HOOKS, SUDO_PARAMS, Ask,OrderExpire 
+    ask_key, asks, bid_key, bids, bundle_asks, offer_key, offers, swaps, trait_bids, CUSTODY, OFFER_HOOKS, TRAIT_BID_HOOKS, ORDER_KEYS, ORDER_NONCES, SWAP_HOOKS, BidKey, TokenId, ASK_HOOKS, BID_HOOKS, SALE_HOOKS, SALE_HOOKS_V2, SUDO_PARAMS, Ask,OrderExpire,
+    COLLECTION_CONFIGS, COLLECTION_REGISTRY, PAUSE, RECORD_MIGRATION,
 };
-use cosmwasm_std::{entry_point, to_binary, Addr, Binary, Deps, Env, Order, StdResult,  Uint128, StdError};
+use cosmwasm_std::{entry_point, to_binary, Addr, Binary, Deps, Env, Order, StdResult};
 use cw_storage_plus::{Bound, PrefixBound};
+use cw2::get_contract_version;
 use cw_utils::maybe_addr;
 
 // Query limits
@@ -21,6 +26,15 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Collections { start_after, limit } => {
             to_binary(&query_collections(deps, start_after, limit)?)
         }
+        QueryMsg::RegisteredCollections { start_after, limit } => {
+            to_binary(&query_registered_collections(deps, start_after, limit)?)
+        }
+        QueryMsg::CollectionStatus { collection } => {
+            to_binary(&query_collection_status(deps, api.addr_validate(&collection)?)?)
+        }
+        QueryMsg::CollectionConfig { collection } => {
+            to_binary(&query_collection_config(deps, api.addr_validate(&collection)?)?)
+        }
         QueryMsg::Ask {
             collection,
             token_id,
@@ -91,6 +105,20 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_binary(&query_asks_sorted_by_expiration(deps, env,  limit)?)   
         }
         ,
+        QueryMsg::AsksByTrait {
+            collection,
+            trait_type,
+            value,
+            start_after,
+            limit,
+        } => to_binary(&query_asks_by_trait(
+            deps,
+            api.addr_validate(&collection)?,
+            trait_type,
+            value,
+            start_after,
+            limit,
+        )?),
         QueryMsg::AsksSortedByContentType { 
             content_type, 
             start_after, 
@@ -143,6 +171,48 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
         )?),
+        QueryMsg::Offer {
+            collection,
+            token_id,
+            buyer,
+        } => to_binary(&query_offer(
+            deps,
+            api.addr_validate(&collection)?,
+            token_id,
+            api.addr_validate(&buyer)?,
+        )?),
+        QueryMsg::Offers {
+            collection,
+            token_id,
+            start_after,
+            limit,
+        } => to_binary(&query_offers(
+            deps,
+            api.addr_validate(&collection)?,
+            token_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::OffersByBuyer {
+            buyer,
+            start_after,
+            limit,
+        } => to_binary(&query_offers_by_buyer(
+            deps,
+            api.addr_validate(&buyer)?,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::OffersBySeller {
+            seller,
+            start_after,
+            limit,
+        } => to_binary(&query_offers_by_seller(
+            deps,
+            api.addr_validate(&seller)?,
+            start_after,
+            limit,
+        )?),
         QueryMsg::BidsSortedByPrice {
             collection,
             start_after,
@@ -166,7 +236,108 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::AskHooks {} => to_binary(&ASK_HOOKS.query_hooks(deps)?),
         QueryMsg::BidHooks {} => to_binary(&BID_HOOKS.query_hooks(deps)?),
         QueryMsg::SaleHooks {} => to_binary(&SALE_HOOKS.query_hooks(deps)?),
+        QueryMsg::SaleHooksV2 {} => to_binary(&SALE_HOOKS_V2.query_hooks(deps)?),
+        QueryMsg::SwapHooks {} => to_binary(&SWAP_HOOKS.query_hooks(deps)?),
+        QueryMsg::OfferHooks {} => to_binary(&OFFER_HOOKS.query_hooks(deps)?),
+        QueryMsg::TraitBidHooks {} => to_binary(&TRAIT_BID_HOOKS.query_hooks(deps)?),
+        QueryMsg::Swap { id } => to_binary(&query_swap(deps, id)?),
+        QueryMsg::TraitBid { id } => to_binary(&query_trait_bid(deps, id)?),
+        QueryMsg::TraitBidsSortedByPrice {
+            collection,
+            trait_type,
+            value,
+            start_after,
+            limit,
+        } => to_binary(&query_trait_bids_sorted_by_price(
+            deps,
+            api.addr_validate(&collection)?,
+            trait_type,
+            value,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ReverseTraitBidsSortedByPrice {
+            collection,
+            trait_type,
+            value,
+            start_before,
+            limit,
+        } => to_binary(&reverse_query_trait_bids_sorted_by_price(
+            deps,
+            api.addr_validate(&collection)?,
+            trait_type,
+            value,
+            start_before,
+            limit,
+        )?),
+        QueryMsg::TraitBidsByBidder {
+            bidder,
+            start_after,
+            limit,
+        } => to_binary(&query_trait_bids_by_bidder(
+            deps,
+            api.addr_validate(&bidder)?,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::SwapsByProposer {
+            proposer,
+            start_after,
+            limit,
+        } => to_binary(&query_swaps_by_proposer(
+            deps,
+            api.addr_validate(&proposer)?,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::SwapsByCounterparty {
+            counterparty,
+            start_after,
+            limit,
+        } => to_binary(&query_swaps_by_counterparty(
+            deps,
+            api.addr_validate(&counterparty)?,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::BundleAsk { id } => to_binary(&query_bundle_ask(deps, id)?),
+        QueryMsg::BundleAsksBySeller {
+            seller,
+            start_after,
+            limit,
+        } => to_binary(&query_bundle_asks_by_seller(
+            deps,
+            api.addr_validate(&seller)?,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::OrderKey { seller } => {
+            to_binary(&query_order_key(deps, api.addr_validate(&seller)?)?)
+        }
+        QueryMsg::OrderNonce { seller, nonce } => {
+            to_binary(&query_order_nonce(deps, api.addr_validate(&seller)?, nonce)?)
+        }
+        QueryMsg::OrderDigest { order } => to_binary(&OrderDigestResponse {
+            digest: Binary::from(order_digest(&env, &order)?.as_slice()),
+        }),
         QueryMsg::Params {} => to_binary(&query_params(deps)?),
+        QueryMsg::Custody {
+            collection,
+            token_id,
+        } => to_binary(&query_custody(deps, api.addr_validate(&collection)?, token_id)?),
+        QueryMsg::EscrowSummary {} => to_binary(&escrow_summary(deps, &env)?),
+        QueryMsg::MigrationStatus {} => to_binary(&query_migration_status(deps)?),
+        QueryMsg::PauseStatus {} => to_binary(&query_pause_status(deps)?),
+        QueryMsg::PayoutSimulation {
+            collection,
+            token_id,
+            price,
+        } => to_binary(&simulate_payout(
+            deps,
+            &api.addr_validate(&collection)?,
+            &token_id,
+            price,
+        )?),
     }
 }
 
@@ -178,7 +349,7 @@ pub fn query_collections(
     let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
     let start_addr = maybe_addr(deps.api, start_after)?;
 
-    let collections = asks()
+    let mut collections = asks()
         .prefix_range(
             deps.storage,
             start_addr.map(PrefixBound::exclusive),
@@ -188,10 +359,56 @@ pub fn query_collections(
         .take(limit)
         .map(|item| item.map(|(key, _)| key.0))
         .collect::<StdResult<Vec<_>>>()?;
+    collections.dedup();
+
+    let collections = collections
+        .iter()
+        .map(|collection| verify_collection(deps, collection))
+        .collect::<StdResult<Vec<_>>>()?;
 
     Ok(CollectionsResponse { collections })
 }
 
+pub fn query_registered_collections(
+    deps: Deps,
+    start_after: Option<Collection>,
+    limit: Option<u32>,
+) -> StdResult<CollectionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start_addr = maybe_addr(deps.api, start_after)?;
+
+    let collections = COLLECTION_REGISTRY
+        .keys(
+            deps.storage,
+            start_addr.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|collection| verify_collection(deps, &collection?))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(CollectionsResponse { collections })
+}
+
+pub fn query_collection_status(
+    deps: Deps,
+    collection: Addr,
+) -> StdResult<CollectionStatusResponse> {
+    let status = verify_collection(deps, &collection)?;
+
+    Ok(CollectionStatusResponse { status })
+}
+
+pub fn query_collection_config(
+    deps: Deps,
+    collection: Addr,
+) -> StdResult<CollectionConfigResponse> {
+    let config = COLLECTION_CONFIGS.may_load(deps.storage, &collection)?;
+
+    Ok(CollectionConfigResponse { config })
+}
+
 pub fn query_asks(
     deps: Deps,
     collection: Addr,
@@ -296,6 +513,90 @@ pub fn reverse_query_asks_sorted_by_price(
     Ok(AsksResponse { asks })
 }
 
+pub fn query_bundle_ask(deps: Deps, id: u64) -> StdResult<BundleAskResponse> {
+    let bundle = bundle_asks().may_load(deps.storage, id)?;
+
+    Ok(BundleAskResponse { bundle })
+}
+
+pub fn query_bundle_asks_by_seller(
+    deps: Deps,
+    seller: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<BundleAsksResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    let bundles = bundle_asks()
+        .idx
+        .seller
+        .prefix(seller)
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(BundleAsksResponse { bundles })
+}
+
+pub fn query_swap(deps: Deps, id: u64) -> StdResult<SwapResponse> {
+    let swap = swaps().may_load(deps.storage, id)?;
+
+    Ok(SwapResponse { swap })
+}
+
+pub fn query_swaps_by_proposer(
+    deps: Deps,
+    proposer: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SwapsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    let swaps = swaps()
+        .idx
+        .proposer
+        .prefix(proposer)
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(SwapsResponse { swaps })
+}
+
+pub fn query_swaps_by_counterparty(
+    deps: Deps,
+    counterparty: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SwapsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    let swaps = swaps()
+        .idx
+        .counterparty
+        .prefix(counterparty.to_string())
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(SwapsResponse { swaps })
+}
+
+pub fn query_order_key(deps: Deps, seller: Addr) -> StdResult<OrderKeyResponse> {
+    let pubkey = ORDER_KEYS.may_load(deps.storage, &seller)?;
+
+    Ok(OrderKeyResponse { pubkey })
+}
+
+pub fn query_order_nonce(deps: Deps, seller: Addr, nonce: u64) -> StdResult<OrderNonceResponse> {
+    let status = ORDER_NONCES.may_load(deps.storage, (&seller, nonce))?;
+
+    Ok(OrderNonceResponse { status })
+}
+
 pub fn query_ask_count(deps: Deps, collection: Addr) -> StdResult<AskCountResponse> {
     let count = asks()
         .idx
@@ -364,6 +665,29 @@ pub fn query_asks_by_content_type(
 }
 
 
+pub fn query_asks_by_trait(
+    deps: Deps,
+    collection: Addr,
+    trait_type: String,
+    value: String,
+    start_after: Option<TokenId>,
+    limit: Option<u32>,
+) -> StdResult<AsksResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let asks = asks()
+        .idx
+        .traits
+        .prefix(collection.clone(), trait_type, value)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|token_id| asks().load(deps.storage, ask_key(&collection, &token_id?)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AsksResponse { asks })
+}
+
 pub fn query_ask(deps: Deps, collection: Addr, token_id: TokenId) -> StdResult<AskResponse> {
     let ask = asks().may_load(deps.storage, ask_key(&collection, &token_id))?;
 
@@ -412,10 +736,7 @@ pub fn query_asks_sorted_by_expiration(
          Ok(AsksResponse { asks })
     }
     else{
-        let mut result :Vec<Ask> = Vec::new();
-        for i in 0..limit {
-            result.push(asks[i].clone());
-        }
+        let result: Vec<Ask> = asks.into_iter().take(limit).collect();
         Ok(AsksResponse { asks: result })
     }
    
@@ -622,3 +943,186 @@ pub fn query_params(deps: Deps) -> StdResult<ParamsResponse> {
 
     Ok(ParamsResponse { params: config })
 }
+
+pub fn query_pause_status(deps: Deps) -> StdResult<PauseStatusResponse> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(PauseStatusResponse { pause })
+}
+
+pub fn query_migration_status(deps: Deps) -> StdResult<MigrationStatusResponse> {
+    let version = get_contract_version(deps.storage)?;
+    let records = RECORD_MIGRATION.may_load(deps.storage)?;
+
+    Ok(MigrationStatusResponse { version, records })
+}
+
+pub fn query_offer(
+    deps: Deps,
+    collection: Addr,
+    token_id: TokenId,
+    buyer: Addr,
+) -> StdResult<OfferResponse> {
+    let offer = offers().may_load(deps.storage, offer_key(&collection, &token_id, &buyer))?;
+
+    Ok(OfferResponse { offer })
+}
+
+pub fn query_offers(
+    deps: Deps,
+    collection: Addr,
+    token_id: TokenId,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    let start = maybe_addr(deps.api, start_after)?
+        .map(|buyer| Bound::exclusive(offer_key(&collection, &token_id, &buyer)));
+
+    let offers = offers()
+        .idx
+        .collection_token_id
+        .prefix((collection, token_id))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, o)| o))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(OffersResponse { offers })
+}
+
+pub fn query_offers_by_buyer(
+    deps: Deps,
+    buyer: Addr,
+    start_after: Option<CollectionOffset>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    let start = if let Some(start) = start_after {
+        let collection = deps.api.addr_validate(&start.collection)?;
+        Some(Bound::exclusive(offer_key(&collection, &start.token_id, &buyer)))
+    } else {
+        None
+    };
+
+    let offers = offers()
+        .idx
+        .buyer
+        .prefix(buyer)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, o)| o))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(OffersResponse { offers })
+}
+
+pub fn query_offers_by_seller(
+    deps: Deps,
+    seller: Addr,
+    start_after: Option<OfferOffset>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    let start = if let Some(start) = start_after {
+        let collection = deps.api.addr_validate(&start.collection)?;
+        let buyer = deps.api.addr_validate(&start.buyer)?;
+        Some(Bound::exclusive(offer_key(&collection, &start.token_id, &buyer)))
+    } else {
+        None
+    };
+
+    let offers = offers()
+        .idx
+        .seller
+        .prefix(seller)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, o)| o))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(OffersResponse { offers })
+}
+
+pub fn query_trait_bid(deps: Deps, id: u64) -> StdResult<TraitBidResponse> {
+    let bid = trait_bids().may_load(deps.storage, id)?;
+
+    Ok(TraitBidResponse { bid })
+}
+
+pub fn query_trait_bids_sorted_by_price(
+    deps: Deps,
+    collection: Addr,
+    trait_type: String,
+    value: String,
+    start_after: Option<TraitBidOffset>,
+    limit: Option<u32>,
+) -> StdResult<TraitBidsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    let start = start_after.map(|offset| Bound::exclusive((offset.price.u128(), offset.id)));
+
+    let bids = trait_bids()
+        .idx
+        .trait_price
+        .sub_prefix((collection, (trait_type, value)))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TraitBidsResponse { bids })
+}
+
+pub fn reverse_query_trait_bids_sorted_by_price(
+    deps: Deps,
+    collection: Addr,
+    trait_type: String,
+    value: String,
+    start_before: Option<TraitBidOffset>,
+    limit: Option<u32>,
+) -> StdResult<TraitBidsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    let end = start_before.map(|offset| Bound::exclusive((offset.price.u128(), offset.id)));
+
+    let bids = trait_bids()
+        .idx
+        .trait_price
+        .sub_prefix((collection, (trait_type, value)))
+        .range(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TraitBidsResponse { bids })
+}
+
+pub fn query_trait_bids_by_bidder(
+    deps: Deps,
+    bidder: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<TraitBidsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    let bids = trait_bids()
+        .idx
+        .bidder
+        .prefix(bidder)
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TraitBidsResponse { bids })
+}
+
+pub fn query_custody(deps: Deps, collection: Addr, token_id: TokenId) -> StdResult<CustodyResponse> {
+    let custody = CUSTODY.may_load(deps.storage, ask_key(&collection, &token_id))?;
+
+    Ok(CustodyResponse { custody })
+}
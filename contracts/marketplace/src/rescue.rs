@@ -0,0 +1,91 @@
+use crate::error::ContractError;
+use crate::execute::only_operator;
+use crate::msg::Metadata;
+use crate::state::{ask_key, asks, TokenId, CUSTODY};
+use cosmwasm_std::{to_binary, Addr, DepsMut, Env, MessageInfo, Response, WasmMsg};
+use cw721::OwnerOfResponse;
+use cw721_base::ExecuteMsg as Cw721ExecuteMsg;
+use cw721_base::QueryMsg as Cw721QueryMsg;
+use cw_utils::nonpayable;
+
+/// Lets an operator return an NFT whose ask was lost to the address that deposited it
+pub fn execute_rescue_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    token_id: TokenId,
+    recipient: Addr,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    only_operator(deps.storage, &info)?;
+
+    let custody = CUSTODY
+        .may_load(deps.storage, ask_key(&collection, &token_id))?
+        .ok_or(ContractError::NoCustodyRecord {})?;
+    if recipient != custody.depositor {
+        return Err(ContractError::RescueRecipientNotDepositor {});
+    }
+
+    rescue_nft(deps, env, collection, token_id, recipient)
+}
+
+/// Lets governance send an NFT whose ask was lost to any recipient
+pub fn sudo_rescue_nft(
+    deps: DepsMut,
+    env: Env,
+    collection: Addr,
+    token_id: TokenId,
+    recipient: Addr,
+) -> Result<Response, ContractError> {
+    rescue_nft(deps, env, collection, token_id, recipient)
+}
+
+/// Transfers an NFT the marketplace holds without an ask. Only NFTs with a custody record
+/// qualify, so NFTs escrowed by bundles or swaps can never be moved this way.
+fn rescue_nft(
+    deps: DepsMut,
+    env: Env,
+    collection: Addr,
+    token_id: TokenId,
+    recipient: Addr,
+) -> Result<Response, ContractError> {
+    let key = ask_key(&collection, &token_id);
+    if asks().has(deps.storage, key.clone()) {
+        return Err(ContractError::AskStillListed {});
+    }
+    let custody = CUSTODY
+        .may_load(deps.storage, key.clone())?
+        .ok_or(ContractError::NoCustodyRecord {})?;
+
+    let owner: OwnerOfResponse = deps.querier.query_wasm_smart(
+        &collection,
+        &Cw721QueryMsg::OwnerOf {
+            token_id: token_id.clone(),
+            include_expired: None,
+        },
+    )?;
+    if owner.owner != env.contract.address {
+        return Err(ContractError::NftNotInCustody {});
+    }
+
+    CUSTODY.remove(deps.storage, key);
+
+    let transfer = WasmMsg::Execute {
+        contract_addr: collection.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::<Metadata>::TransferNft {
+            recipient: recipient.to_string(),
+            token_id: token_id.clone(),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(transfer)
+        .add_attribute("human_action", "human_marketplace_rescue_nft")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("token_id", token_id)
+        .add_attribute("depositor", custody.depositor)
+        .add_attribute("recipient", recipient)
+        .add_attribute("time", env.block.time.to_string()))
+}
@@ -0,0 +1,155 @@
+use crate::error::ContractError;
+use crate::execute::{
+    finalize_sale, only_nft_owner_of, only_unpaused, only_verified_collection, price_validate,
+    Sale, NATIVE_DENOM,
+};
+use crate::msg::{OrderSignDoc, SignedOrder};
+use crate::state::{
+    collection_params, NftItem, NonceStatus, SaleOrigin, SaleType, ORDER_KEYS, ORDER_NONCES,
+    PAUSE,
+};
+use cosmwasm_std::{to_vec, Binary, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+use cw_utils::{must_pay, nonpayable};
+use sha2::{Digest, Sha256};
+
+/// Registers the public key the sender signs orders with
+pub fn execute_set_order_key(
+    deps: DepsMut,
+    info: MessageInfo,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    // A new key makes new signed listings fillable
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.listing, "listing")?;
+
+    if pubkey.len() != 33 && pubkey.len() != 65 {
+        return Err(ContractError::InvalidOrderKey {});
+    }
+    ORDER_KEYS.save(deps.storage, &info.sender, &pubkey)?;
+
+    Ok(Response::new()
+        .add_attribute("human_action", "human_marketplace_set_order_key")
+        .add_attribute("seller", info.sender)
+        .add_attribute("pubkey", pubkey.to_base64()))
+}
+
+/// Cancels signed orders of the sender, a nonce that was already used stays used
+pub fn execute_cancel_signed_orders(
+    deps: DepsMut,
+    info: MessageInfo,
+    nonces: Vec<u64>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.removal, "removal")?;
+
+    for nonce in &nonces {
+        let key = (&info.sender, *nonce);
+        if !ORDER_NONCES.has(deps.storage, key) {
+            ORDER_NONCES.save(deps.storage, key, &NonceStatus::Cancelled)?;
+        }
+    }
+
+    let nonces: Vec<String> = nonces.iter().map(u64::to_string).collect();
+    Ok(Response::new()
+        .add_attribute("human_action", "human_marketplace_cancel_signed_orders")
+        .add_attribute("seller", info.sender)
+        .add_attribute("nonces", nonces.join(",")))
+}
+
+/// Buys an NFT through an order its seller signed off-chain. The order settles like a fixed
+/// price ask, with the NFT moved from the seller through their approval of the marketplace.
+pub fn execute_fill_signed_order(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order: SignedOrder,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.settlement, "settlement")?;
+
+    let seller = deps.api.addr_validate(&order.seller)?;
+    let collection = deps.api.addr_validate(&order.collection)?;
+
+    match ORDER_NONCES.may_load(deps.storage, (&seller, order.nonce))? {
+        Some(NonceStatus::Used) => return Err(ContractError::OrderNonceUsed(order.nonce)),
+        Some(NonceStatus::Cancelled) => {
+            return Err(ContractError::OrderNonceCancelled(order.nonce))
+        }
+        None => {}
+    }
+    if order.expires_at <= env.block.time {
+        return Err(ContractError::OrderExpired {});
+    }
+
+    let pubkey = ORDER_KEYS
+        .may_load(deps.storage, &seller)?
+        .ok_or(ContractError::NoOrderKey {})?;
+    let digest = order_digest(&env, &order)?;
+    if !deps
+        .api
+        .secp256k1_verify(&digest, &signature, &pubkey)
+        .map_err(StdError::from)?
+    {
+        return Err(ContractError::InvalidOrderSignature {});
+    }
+
+    // The order must still be acceptable as a fixed price listing
+    only_verified_collection(deps.as_ref(), &collection)?;
+    price_validate(deps.storage, &collection, &order.price)?;
+    let (_, config) = collection_params(deps.storage, &collection)?;
+    if !config.allows_sale_type(&SaleType::FixedPrice) {
+        return Err(ContractError::SaleTypeNotAllowed {});
+    }
+
+    let buyer = info.sender.clone();
+    if buyer == seller {
+        return Err(ContractError::UnauthorizedOwner {});
+    }
+    if must_pay(&info, NATIVE_DENOM)? != order.price.amount {
+        return Err(ContractError::InvalidPrice {});
+    }
+
+    let item = NftItem {
+        collection: collection.clone(),
+        token_id: order.token_id.clone(),
+    };
+    only_nft_owner_of(deps.as_ref(), &item, &seller)?;
+
+    ORDER_NONCES.save(deps.storage, (&seller, order.nonce), &NonceStatus::Used)?;
+
+    let mut res = Response::new();
+    let sale = Sale {
+        collection: collection.clone(),
+        token_id: order.token_id.clone(),
+        seller: seller.clone(),
+        funds_recipient: seller.clone(),
+        price: order.price.amount,
+        buyer: buyer.clone(),
+        origin: SaleOrigin::SignedOrder,
+    };
+    finalize_sale(deps.as_ref(), &env, sale, &mut res)?;
+
+    Ok(res
+        .add_attribute("human_action", "human_marketplace_fill_signed_order")
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("token_id", order.token_id)
+        .add_attribute("buyer", buyer)
+        .add_attribute("seller", seller)
+        .add_attribute("price", order.price.amount.to_string())
+        .add_attribute("nonce", order.nonce.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// SHA-256 of the `OrderSignDoc` of an order on this marketplace, the message sellers sign
+pub fn order_digest(env: &Env, order: &SignedOrder) -> StdResult<Vec<u8>> {
+    let sign_doc = OrderSignDoc {
+        chain_id: env.block.chain_id.clone(),
+        marketplace: env.contract.address.to_string(),
+        order: order.clone(),
+    };
+
+    Ok(Sha256::digest(&to_vec(&sign_doc)?).to_vec())
+}
@@ -1,15 +1,20 @@
-use cosmwasm_std::{Addr, BlockInfo, Timestamp, Uint128};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use cosmwasm_std::{Addr, Binary, BlockInfo, Decimal, Empty, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, Prefix};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use cw_controllers::Hooks;
 
 use crate::helpers::ExpiryRange;
+use crate::msg::Trait;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct SudoParams {
-    /// Fair Burn fee for winning bids
-    // pub trading_fee_percent: Decimal,
+    /// Protocol fee for winning bids, paid to `fee_recipient`
+    #[serde(default)]
+    pub trading_fee_percent: Decimal,
+    /// Receives the protocol fee, no fee is charged while unset
+    #[serde(default)]
+    pub fee_recipient: Option<Addr>,
     /// Valid time range for Asks
     /// (min, max) in seconds
     pub ask_expiry: ExpiryRange,
@@ -26,15 +31,149 @@ pub struct SudoParams {
     /// Listing fee to reduce spam
     pub listing_fee: Uint128,
 
-    pub create_collection_address: String
+    pub create_collection_address: String,
+    /// When set, allowlisted collections must also pass the factory `CheckCollection` query
+    #[serde(default)]
+    pub require_factory_check: bool,
+    /// Royalty rates reported by collections are capped at this rate
+    #[serde(default)]
+    pub max_royalty_rate: Option<Decimal>,
 }
 
 pub const SUDO_PARAMS: Item<SudoParams> = Item::new("sudo-params");
 
+/// Marketplace-local list a collection has been put on by governance
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CollectionList {
+    Allow,
+    Deny,
+}
+
+/// Registry entry for a collection, managed via `SudoMsg`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectionRecord {
+    pub list: Option<CollectionList>,
+    /// Disabled collections cannot be listed, regardless of list membership
+    pub enabled: bool,
+    /// Reason given when the collection was disabled
+    pub reason: Option<String>,
+}
+
+impl Default for CollectionRecord {
+    fn default() -> Self {
+        CollectionRecord {
+            list: None,
+            enabled: true,
+            reason: None,
+        }
+    }
+}
+
+pub const COLLECTION_REGISTRY: Map<&Addr, CollectionRecord> = Map::new("collection-registry");
+
+/// Per-collection overrides of the global `SudoParams`, set by governance or the collection admin
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct CollectionConfig {
+    pub min_price: Option<Uint128>,
+    pub listing_fee: Option<Uint128>,
+    pub ask_expiry: Option<ExpiryRange>,
+    pub bid_expiry: Option<ExpiryRange>,
+    /// Sale types asks in this collection may use, all when unset
+    pub sale_types: Option<Vec<SaleType>>,
+    /// Discount on the protocol fee in basis points, only settable by governance
+    pub protocol_fee_discount_bps: Option<u64>,
+}
+
+impl CollectionConfig {
+    /// Applies the overrides on top of the global params
+    pub fn apply(&self, params: SudoParams) -> SudoParams {
+        SudoParams {
+            min_price: self.min_price.unwrap_or(params.min_price),
+            listing_fee: self.listing_fee.unwrap_or(params.listing_fee),
+            ask_expiry: self.ask_expiry.clone().unwrap_or(params.ask_expiry),
+            bid_expiry: self.bid_expiry.clone().unwrap_or(params.bid_expiry),
+            ..params
+        }
+    }
+
+    pub fn allows_sale_type(&self, sale_type: &SaleType) -> bool {
+        self.sale_types
+            .as_ref()
+            .map_or(true, |sale_types| sale_types.contains(sale_type))
+    }
+}
+
+pub const COLLECTION_CONFIGS: Map<&Addr, CollectionConfig> = Map::new("collection-configs");
+
+/// Loads the global params with the collection's overrides applied
+pub fn collection_params(
+    store: &dyn Storage,
+    collection: &Addr,
+) -> StdResult<(SudoParams, CollectionConfig)> {
+    let config = COLLECTION_CONFIGS
+        .may_load(store, collection)?
+        .unwrap_or_default();
+    let params = config.apply(SUDO_PARAMS.load(store)?);
+    Ok((params, config))
+}
+
+/// Emergency switches, each halting one group of `execute_*` handlers.
+/// Pausing listing, bidding or settlement never blocks sellers from removing their asks,
+/// which refunds the highest bidder, nor buyers from withdrawing their offers.
+/// Outbid bidders are refunded as soon as they are outbid.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct PauseState {
+    /// Creating asks and updating their price, including counter offers and order keys
+    pub listing: bool,
+    /// Placing bids on auctions
+    pub bidding: bool,
+    /// Fixed price purchases and accepting winning bids
+    pub settlement: bool,
+    /// Removing asks, withdrawing offers and cancelling signed orders, only meant for when the
+    /// removal path itself misbehaves
+    pub removal: bool,
+}
+
+pub const PAUSE: Item<PauseState> = Item::new("pause");
+
+/// Progress of rewriting stored asks and bids into their current schema after a migration,
+/// then of counting the funds escrowed by every kind of record into `ESCROW_TOTALS`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordMigration {
+    Asks { start_after: Option<AskKey> },
+    Bids { start_after: Option<BidKey> },
+    Bundles { start_after: Option<u64> },
+    Offers { start_after: Option<OfferKey> },
+    Swaps { start_after: Option<u64> },
+    TraitBids { start_after: Option<u64> },
+}
+
+pub const RECORD_MIGRATION: Item<RecordMigration> = Item::new("record-migration");
+
+/// Running totals of the native funds held for others, by the kind of record escrowing them
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct EscrowTotals {
+    /// Active bids, the highest bid of every auction
+    pub auction: Uint128,
+    /// Highest bids of bundle auctions
+    pub bundle: Uint128,
+    pub offer: Uint128,
+    pub swap: Uint128,
+    pub trait_bid: Uint128,
+}
+
+pub const ESCROW_TOTALS: Item<EscrowTotals> = Item::new("escrow-totals");
+
 pub const ASK_HOOKS: Hooks = Hooks::new("ask-hooks");
 pub const BID_HOOKS: Hooks = Hooks::new("bid-hooks");
 pub const SALE_HOOKS: Hooks = Hooks::new("sale-hooks");
+pub const SALE_HOOKS_V2: Hooks = Hooks::new("sale-hooks-v2");
 pub const COLLECTION_BID_HOOKS: Hooks = Hooks::new("collection-bid-hooks");
+pub const SWAP_HOOKS: Hooks = Hooks::new("swap-hooks");
+pub const OFFER_HOOKS: Hooks = Hooks::new("offer-hooks");
+pub const TRAIT_BID_HOOKS: Hooks = Hooks::new("trait-bid-hooks");
 
 pub type TokenId = String;
 
@@ -53,6 +192,22 @@ pub enum SaleType {
     Auction,
 }
 
+/// How a settled sale came about, reported to the v2 sale hooks
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SaleOrigin {
+    /// Bought at the price of a fixed price ask
+    FixedPrice,
+    /// Won by the highest bid of an auction
+    Auction,
+    /// An offer, or a counter offer, accepted on a fixed price ask
+    Offer,
+    /// A holder of an NFT with the trait sold it to a trait bid
+    TraitBid,
+    /// Bought through an order the seller signed off-chain
+    SignedOrder,
+}
+
 /// Represents an ask on the marketplace
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Ask {
@@ -67,7 +222,10 @@ pub struct Ask {
     pub max_bid: Option<Uint128>,
     pub max_bidder: Option<Addr>,
     pub bid_count: Uint128,
-    pub content_type: String
+    pub content_type: String,
+    /// Traits of the NFT when it was listed, indexed for `AsksByTrait`
+    #[serde(default)]
+    pub traits: Vec<Trait>,
 }
 
 impl OrderExpire for Ask {
@@ -83,6 +241,17 @@ pub fn ask_key(collection: &Addr, token_id: &TokenId) -> AskKey {
     (collection.clone(), token_id.clone())
 }
 
+/// Who sent an NFT held for an ask, kept while the marketplace holds the NFT
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Custody {
+    pub depositor: Addr,
+    /// When the NFT was listed, or when the 1.2.0 record migration reached an older ask
+    pub deposited_at: Timestamp,
+}
+
+/// Custody of listed NFTs by ask key, outlives a lost ask so the NFT can be rescued
+pub const CUSTODY: Map<AskKey, Custody> = Map::new("custody");
+
 /// Defines indices for accessing Asks
 pub struct AskIndicies<'a> {
     pub collection: MultiIndex<'a, Addr, Ask, AskKey>,
@@ -91,12 +260,13 @@ pub struct AskIndicies<'a> {
     pub bid_count: MultiIndex<'a, u128, Ask, AskKey>,
     pub content_type: MultiIndex<'a, String, Ask, AskKey>,
     pub price: MultiIndex<'a, u128, Ask, AskKey>,
-    pub expiration: MultiIndex<'a, u64, Ask, AskKey>
+    pub expiration: MultiIndex<'a, u64, Ask, AskKey>,
+    pub traits: TraitIndex<'a>,
 }
 
 impl<'a> IndexList<Ask> for AskIndicies<'a> {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Ask>> + '_> {
-        let v: Vec<&dyn Index<Ask>> = vec![&self.collection, &self.collection_price, &self.seller, &self.bid_count, &self.content_type, &self.price, &self.expiration];
+        let v: Vec<&dyn Index<Ask>> = vec![&self.collection, &self.collection_price, &self.seller, &self.bid_count, &self.content_type, &self.price, &self.expiration, &self.traits];
         Box::new(v.into_iter())
     }
 }
@@ -113,11 +283,55 @@ pub fn asks<'a>() -> IndexedMap<'a, AskKey, Ask, AskIndicies<'a>> {
         bid_count: MultiIndex::new(|d: &Ask| d.bid_count.u128() , "asks", "bids_count"),
         content_type: MultiIndex::new(|d: &Ask| d.content_type.clone() , "asks", "content_type"),
         price: MultiIndex::new(|d: &Ask| d.price.u128() , "asks", "asks__price"),
-        expiration: MultiIndex::new(|d: &Ask| d.expires_at.seconds() , "asks", "asks__expiration")
+        expiration: MultiIndex::new(|d: &Ask| d.expires_at.seconds() , "asks", "asks__expiration"),
+        traits: TraitIndex::new("asks__traits"),
     };
     IndexedMap::new("asks", indexes)
 }
 
+/// Most traits of one ask that are indexed, so a listing's storage writes stay bounded
+pub const MAX_INDEXED_TRAITS: usize = 32;
+
+/// Indexes an ask under each of its traits. A `MultiIndex` maps every ask to a single
+/// index key, so the (collection, (trait_type, value), token_id) keys are kept here.
+pub struct TraitIndex<'a> {
+    idx_map: Map<'a, (Addr, (String, String), TokenId), Empty>,
+}
+
+impl<'a> TraitIndex<'a> {
+    pub const fn new(idx_namespace: &'a str) -> Self {
+        TraitIndex { idx_map: Map::new(idx_namespace) }
+    }
+
+    /// Token ids of the collection's asks with the trait, in ascending order
+    pub fn prefix(&self, collection: Addr, trait_type: String, value: String) -> Prefix<TokenId, Empty, TokenId> {
+        self.idx_map.prefix((collection, (trait_type, value)))
+    }
+
+    fn keys(ask: &Ask) -> impl Iterator<Item = (Addr, (String, String), TokenId)> + '_ {
+        ask.traits
+            .iter()
+            .take(MAX_INDEXED_TRAITS)
+            .map(move |t| (ask.collection.clone(), (t.trait_type.clone(), t.value.clone()), ask.token_id.clone()))
+    }
+}
+
+impl<'a> Index<Ask> for TraitIndex<'a> {
+    fn save(&self, store: &mut dyn Storage, _pk: &[u8], data: &Ask) -> StdResult<()> {
+        for key in TraitIndex::keys(data) {
+            self.idx_map.save(store, key, &Empty {})?;
+        }
+        Ok(())
+    }
+
+    fn remove(&self, store: &mut dyn Storage, _pk: &[u8], old_data: &Ask) -> StdResult<()> {
+        for key in TraitIndex::keys(old_data) {
+            self.idx_map.remove(store, key);
+        }
+        Ok(())
+    }
+}
+
 /// Represents a bid (offer) on the marketplace
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Bid {
@@ -265,3 +479,226 @@ pub fn collection_bids<'a>(
     };
     IndexedMap::new("col_bids", indexes)
 }
+
+/// An NFT traded as part of a bundle ask or swap
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NftItem {
+    pub collection: Addr,
+    pub token_id: TokenId,
+}
+
+/// Share of a bundle's price attributed to one of its collections. Protocol fees and
+/// royalties of each collection are computed on its share.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BundleAllocation {
+    pub collection: Addr,
+    pub share: Decimal,
+}
+
+/// Represents an ask selling several NFTs together for one price
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BundleAsk {
+    pub id: u64,
+    pub sale_type: SaleType,
+    pub items: Vec<NftItem>,
+    pub allocations: Vec<BundleAllocation>,
+    pub seller: Addr,
+    pub price: Uint128,
+    pub funds_recipient: Option<Addr>,
+    pub expires_at: Timestamp,
+    pub max_bid: Option<Uint128>,
+    pub max_bidder: Option<Addr>,
+    pub bid_count: Uint128,
+}
+
+impl OrderExpire for BundleAsk {
+    fn expires_at(&self) -> Timestamp {
+        self.expires_at
+    }
+}
+
+/// Defines indices for accessing bundle asks
+pub struct BundleAskIndicies<'a> {
+    pub seller: MultiIndex<'a, Addr, BundleAsk, u64>,
+}
+
+impl<'a> IndexList<BundleAsk> for BundleAskIndicies<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<BundleAsk>> + '_> {
+        let v: Vec<&dyn Index<BundleAsk>> = vec![&self.seller];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn bundle_asks<'a>() -> IndexedMap<'a, u64, BundleAsk, BundleAskIndicies<'a>> {
+    let indexes = BundleAskIndicies {
+        seller: MultiIndex::new(|d: &BundleAsk| d.seller.clone(), "bundles", "bundles__seller"),
+    };
+    IndexedMap::new("bundles", indexes)
+}
+
+/// Id of the last bundle ask created
+pub const BUNDLE_COUNT: Item<u64> = Item::new("bundle-count");
+
+/// Represents an offer to swap escrowed NFTs, and optionally funds, for a set of NFTs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Swap {
+    pub id: u64,
+    pub proposer: Addr,
+    /// Only this address may accept the swap if set
+    pub counterparty: Option<Addr>,
+    pub offered: Vec<NftItem>,
+    pub offered_funds: Uint128,
+    pub requested: Vec<NftItem>,
+    pub expires_at: Timestamp,
+}
+
+impl OrderExpire for Swap {
+    fn expires_at(&self) -> Timestamp {
+        self.expires_at
+    }
+}
+
+/// Defines indices for accessing swaps
+pub struct SwapIndicies<'a> {
+    pub proposer: MultiIndex<'a, Addr, Swap, u64>,
+    // Open swaps are indexed under an empty counterparty
+    pub counterparty: MultiIndex<'a, String, Swap, u64>,
+}
+
+impl<'a> IndexList<Swap> for SwapIndicies<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Swap>> + '_> {
+        let v: Vec<&dyn Index<Swap>> = vec![&self.proposer, &self.counterparty];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn swaps<'a>() -> IndexedMap<'a, u64, Swap, SwapIndicies<'a>> {
+    let indexes = SwapIndicies {
+        proposer: MultiIndex::new(|d: &Swap| d.proposer.clone(), "swaps", "swaps__proposer"),
+        counterparty: MultiIndex::new(
+            |d: &Swap| d.counterparty.as_ref().map(Addr::to_string).unwrap_or_default(),
+            "swaps",
+            "swaps__counterparty",
+        ),
+    };
+    IndexedMap::new("swaps", indexes)
+}
+
+/// Id of the last swap created
+pub const SWAP_COUNT: Item<u64> = Item::new("swap-count");
+
+/// Represents an escrowed bid on any NFT of a collection with the trait `trait_type`: `value`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TraitBid {
+    pub id: u64,
+    pub collection: Addr,
+    pub trait_type: String,
+    pub value: String,
+    pub bidder: Addr,
+    pub price: Uint128,
+    pub expires_at: Timestamp,
+}
+
+impl OrderExpire for TraitBid {
+    fn expires_at(&self) -> Timestamp {
+        self.expires_at
+    }
+}
+
+/// Defines indices for accessing trait bids
+pub struct TraitBidIndicies<'a> {
+    pub trait_price: MultiIndex<'a, (Addr, (String, String), u128), TraitBid, u64>,
+    pub bidder: MultiIndex<'a, Addr, TraitBid, u64>,
+}
+
+impl<'a> IndexList<TraitBid> for TraitBidIndicies<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TraitBid>> + '_> {
+        let v: Vec<&dyn Index<TraitBid>> = vec![&self.trait_price, &self.bidder];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn trait_bids<'a>() -> IndexedMap<'a, u64, TraitBid, TraitBidIndicies<'a>> {
+    let indexes = TraitBidIndicies {
+        trait_price: MultiIndex::new(
+            |d: &TraitBid| {
+                (d.collection.clone(), (d.trait_type.clone(), d.value.clone()), d.price.u128())
+            },
+            "trait_bids",
+            "trait_bids__trait_price",
+        ),
+        bidder: MultiIndex::new(|d: &TraitBid| d.bidder.clone(), "trait_bids", "trait_bids__bidder"),
+    };
+    IndexedMap::new("trait_bids", indexes)
+}
+
+/// Id of the last trait bid created
+pub const TRAIT_BID_COUNT: Item<u64> = Item::new("trait-bid-count");
+
+/// Represents an escrowed offer below the price of a fixed price ask
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Offer {
+    pub collection: Addr,
+    pub token_id: TokenId,
+    pub buyer: Addr,
+    /// Seller of the ask the offer was made on
+    pub seller: Addr,
+    pub price: Uint128,
+    /// Private price the seller offered back, only this buyer can fill it
+    pub counter_price: Option<Uint128>,
+    pub expires_at: Timestamp,
+}
+
+impl OrderExpire for Offer {
+    fn expires_at(&self) -> Timestamp {
+        self.expires_at
+    }
+}
+
+/// Primary key for offers: (collection, token_id, buyer)
+pub type OfferKey = (Addr, TokenId, Addr);
+/// Convenience offer key constructor
+pub fn offer_key(collection: &Addr, token_id: &TokenId, buyer: &Addr) -> OfferKey {
+    (collection.clone(), token_id.clone(), buyer.clone())
+}
+
+/// Defines indices for accessing offers
+pub struct OfferIndicies<'a> {
+    pub collection_token_id: MultiIndex<'a, (Addr, TokenId), Offer, OfferKey>,
+    pub buyer: MultiIndex<'a, Addr, Offer, OfferKey>,
+    pub seller: MultiIndex<'a, Addr, Offer, OfferKey>,
+}
+
+impl<'a> IndexList<Offer> for OfferIndicies<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Offer>> + '_> {
+        let v: Vec<&dyn Index<Offer>> = vec![&self.collection_token_id, &self.buyer, &self.seller];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn offers<'a>() -> IndexedMap<'a, OfferKey, Offer, OfferIndicies<'a>> {
+    let indexes = OfferIndicies {
+        collection_token_id: MultiIndex::new(
+            |d: &Offer| (d.collection.clone(), d.token_id.clone()),
+            "offers",
+            "offers__collection_token_id",
+        ),
+        buyer: MultiIndex::new(|d: &Offer| d.buyer.clone(), "offers", "offers__buyer"),
+        seller: MultiIndex::new(|d: &Offer| d.seller.clone(), "offers", "offers__seller"),
+    };
+    IndexedMap::new("offers", indexes)
+}
+
+/// secp256k1 public keys sellers sign their off-chain orders with
+pub const ORDER_KEYS: Map<&Addr, Binary> = Map::new("order-keys");
+
+/// Why a signed order nonce can no longer be filled
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NonceStatus {
+    Used,
+    Cancelled,
+}
+
+/// Spent nonces of signed orders, keyed by (seller, nonce)
+pub const ORDER_NONCES: Map<(&Addr, u64), NonceStatus> = Map::new("order-nonces");
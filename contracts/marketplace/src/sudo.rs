@@ -1,15 +1,28 @@
 use crate::error::ContractError;
+use crate::escrow::sudo_recover_surplus;
+use crate::rescue::sudo_rescue_nft;
+use crate::execute::{store_collection_config, MAX_FEE_BPS};
 use crate::helpers::ExpiryRange;
 use crate::msg::SudoMsg;
-use crate::state::{ASK_HOOKS, BID_HOOKS, SALE_HOOKS, SUDO_PARAMS};
-use cosmwasm_std::{entry_point, Addr, DepsMut, Env, Uint128, Response};
+use crate::state::{
+    CollectionConfig, CollectionList, CollectionRecord, PauseState, ASK_HOOKS, BID_HOOKS,
+    COLLECTION_REGISTRY, OFFER_HOOKS, PAUSE, SALE_HOOKS, SALE_HOOKS_V2, SUDO_PARAMS, SWAP_HOOKS,
+    TRAIT_BID_HOOKS,
+};
+use cosmwasm_std::{entry_point, Addr, Decimal, DepsMut, Env, Uint128, Response};
 
 pub struct ParamInfo {
+    trading_fee_bps: Option<u64>,
     ask_expiry: Option<ExpiryRange>,
     bid_expiry: Option<ExpiryRange>,
     operators: Option<Vec<String>>,
     min_price: Option<Uint128>,
     listing_fee: Option<Uint128>,
+    require_factory_check: Option<bool>,
+    max_royalty_rate: Option<Decimal>,
+    remove_max_royalty_rate: bool,
+    fee_recipient: Option<Addr>,
+    remove_fee_recipient: bool,
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -18,21 +31,72 @@ pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractE
 
     match msg {
         SudoMsg::UpdateParams {
+            trading_fee_bps,
             ask_expiry,
             bid_expiry,
             operators,
             min_price,
             listing_fee,
+            require_factory_check,
+            max_royalty_rate,
+            remove_max_royalty_rate,
+            fee_recipient,
+            remove_fee_recipient,
         } => sudo_update_params(
             deps,
             env,
             ParamInfo {
+                trading_fee_bps,
                 ask_expiry,
                 bid_expiry,
                 operators,
                 // max_finders_fee_bps,
                 min_price,
                 listing_fee,
+                require_factory_check,
+                max_royalty_rate,
+                remove_max_royalty_rate,
+                fee_recipient: fee_recipient.map(|r| api.addr_validate(&r)).transpose()?,
+                remove_fee_recipient,
+            },
+        ),
+        SudoMsg::AllowCollection { collection } => sudo_set_collection_list(
+            deps,
+            api.addr_validate(&collection)?,
+            Some(CollectionList::Allow),
+        ),
+        SudoMsg::DenyCollection { collection } => sudo_set_collection_list(
+            deps,
+            api.addr_validate(&collection)?,
+            Some(CollectionList::Deny),
+        ),
+        SudoMsg::RemoveCollection { collection } => {
+            sudo_set_collection_list(deps, api.addr_validate(&collection)?, None)
+        }
+        SudoMsg::EnableCollection { collection } => {
+            sudo_set_collection_enabled(deps, api.addr_validate(&collection)?, true, None)
+        }
+        SudoMsg::DisableCollection { collection, reason } => sudo_set_collection_enabled(
+            deps,
+            api.addr_validate(&collection)?,
+            false,
+            Some(reason),
+        ),
+        SudoMsg::SetCollectionConfig { collection, config } => {
+            sudo_set_collection_config(deps, api.addr_validate(&collection)?, config)
+        }
+        SudoMsg::SetPause {
+            listing,
+            bidding,
+            settlement,
+            removal,
+        } => sudo_set_pause(
+            deps,
+            PauseState {
+                listing,
+                bidding,
+                settlement,
+                removal,
             },
         ),
         SudoMsg::AddOperator { operator } => sudo_add_operator(deps, api.addr_validate(&operator)?),
@@ -43,8 +107,36 @@ pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractE
         SudoMsg::AddAskHook { hook } => sudo_add_ask_hook(deps, env, api.addr_validate(&hook)?),
         SudoMsg::AddBidHook { hook } => sudo_add_bid_hook(deps, env, api.addr_validate(&hook)?),
         SudoMsg::RemoveSaleHook { hook } => sudo_remove_sale_hook(deps, api.addr_validate(&hook)?),
+        SudoMsg::AddSaleHookV2 { hook } => sudo_add_sale_hook_v2(deps, api.addr_validate(&hook)?),
+        SudoMsg::RemoveSaleHookV2 { hook } => {
+            sudo_remove_sale_hook_v2(deps, api.addr_validate(&hook)?)
+        }
         SudoMsg::RemoveAskHook { hook } => sudo_remove_ask_hook(deps, api.addr_validate(&hook)?),
         SudoMsg::RemoveBidHook { hook } => sudo_remove_bid_hook(deps, api.addr_validate(&hook)?),
+        SudoMsg::AddOfferHook { hook } => sudo_add_offer_hook(deps, api.addr_validate(&hook)?),
+        SudoMsg::RemoveOfferHook { hook } => sudo_remove_offer_hook(deps, api.addr_validate(&hook)?),
+        SudoMsg::AddTraitBidHook { hook } => {
+            sudo_add_trait_bid_hook(deps, api.addr_validate(&hook)?)
+        }
+        SudoMsg::RemoveTraitBidHook { hook } => {
+            sudo_remove_trait_bid_hook(deps, api.addr_validate(&hook)?)
+        }
+        SudoMsg::AddSwapHook { hook } => sudo_add_swap_hook(deps, api.addr_validate(&hook)?),
+        SudoMsg::RemoveSwapHook { hook } => sudo_remove_swap_hook(deps, api.addr_validate(&hook)?),
+        SudoMsg::RecoverSurplus { recipient, amount } => {
+            sudo_recover_surplus(deps, env, api.addr_validate(&recipient)?, amount)
+        }
+        SudoMsg::RescueNft {
+            collection,
+            token_id,
+            recipient,
+        } => sudo_rescue_nft(
+            deps,
+            env,
+            api.addr_validate(&collection)?,
+            token_id,
+            api.addr_validate(&recipient)?,
+        ),
     }
 }
 
@@ -55,26 +147,35 @@ pub fn sudo_update_params(
     param_info: ParamInfo,
 ) -> Result<Response, ContractError> {
     let ParamInfo {
+        trading_fee_bps,
         ask_expiry,
         bid_expiry,
         operators: _operators,
         min_price,
         listing_fee,
+        require_factory_check,
+        max_royalty_rate,
+        remove_max_royalty_rate,
+        fee_recipient,
+        remove_fee_recipient,
     } = param_info;
-    // if let Some(max_finders_fee_bps) = max_finders_fee_bps {
-    //     if max_finders_fee_bps > MAX_FEE_BPS {
-    //         return Err(ContractError::InvalidFindersFeeBps(max_finders_fee_bps));
-    //     }
-    // }
+    if let Some(trading_fee_bps) = trading_fee_bps {
+        if trading_fee_bps > MAX_FEE_BPS {
+            return Err(ContractError::InvalidTradingFeeBps(trading_fee_bps));
+        }
+    }
+    if max_royalty_rate.map_or(false, |rate| rate > Decimal::one()) {
+        return Err(ContractError::InvalidRoyaltyCap {});
+    }
 
     ask_expiry.as_ref().map(|a| a.validate()).transpose()?;
     bid_expiry.as_ref().map(|b| b.validate()).transpose()?;
 
     let mut params = SUDO_PARAMS.load(deps.storage)?;
 
-    // params.trading_fee_percent = trading_fee_bps
-    //     .map(Decimal::percent)
-    //     .unwrap_or(params.trading_fee_percent);
+    params.trading_fee_percent = trading_fee_bps
+        .map(|bps| Decimal::from_ratio(bps, MAX_FEE_BPS))
+        .unwrap_or(params.trading_fee_percent);
 
     params.ask_expiry = ask_expiry.unwrap_or(params.ask_expiry);
     params.bid_expiry = bid_expiry.unwrap_or(params.bid_expiry);
@@ -87,11 +188,112 @@ pub fn sudo_update_params(
 
     params.listing_fee = listing_fee.unwrap_or(params.listing_fee);
 
+    params.require_factory_check = require_factory_check.unwrap_or(params.require_factory_check);
+
+    params.max_royalty_rate = if remove_max_royalty_rate {
+        None
+    } else {
+        max_royalty_rate.or(params.max_royalty_rate)
+    };
+
+    params.fee_recipient = if remove_fee_recipient {
+        None
+    } else {
+        fee_recipient.or(params.fee_recipient)
+    };
+
     SUDO_PARAMS.save(deps.storage, &params)?;
 
     Ok(Response::new().add_attribute("action", "update_params"))
 }
 
+/// Moves a collection onto the allow or deny list, or off both lists when `list` is `None`
+pub fn sudo_set_collection_list(
+    deps: DepsMut,
+    collection: Addr,
+    list: Option<CollectionList>,
+) -> Result<Response, ContractError> {
+    let mut record = COLLECTION_REGISTRY
+        .may_load(deps.storage, &collection)?
+        .unwrap_or_default();
+    record.list = list.clone();
+    save_collection_record(deps, &collection, record)?;
+
+    let action = match list {
+        Some(CollectionList::Allow) => "allow_collection",
+        Some(CollectionList::Deny) => "deny_collection",
+        None => "remove_collection",
+    };
+    let res = Response::new()
+        .add_attribute("action", action)
+        .add_attribute("collection", collection);
+    Ok(res)
+}
+
+pub fn sudo_set_collection_enabled(
+    deps: DepsMut,
+    collection: Addr,
+    enabled: bool,
+    reason: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut record = COLLECTION_REGISTRY
+        .may_load(deps.storage, &collection)?
+        .unwrap_or_default();
+    record.enabled = enabled;
+    record.reason = reason.clone();
+    save_collection_record(deps, &collection, record)?;
+
+    let action = if enabled {
+        "enable_collection"
+    } else {
+        "disable_collection"
+    };
+    let res = Response::new()
+        .add_attribute("action", action)
+        .add_attribute("collection", collection)
+        .add_attribute("reason", reason.unwrap_or_default());
+    Ok(res)
+}
+
+pub fn sudo_set_collection_config(
+    deps: DepsMut,
+    collection: Addr,
+    config: CollectionConfig,
+) -> Result<Response, ContractError> {
+    store_collection_config(deps.storage, &collection, &config)?;
+
+    let res = Response::new()
+        .add_attribute("action", "set_collection_config")
+        .add_attribute("collection", collection);
+    Ok(res)
+}
+
+/// Records equal to the default carry no information, so they are removed instead
+fn save_collection_record(
+    deps: DepsMut,
+    collection: &Addr,
+    record: CollectionRecord,
+) -> Result<(), ContractError> {
+    if record == CollectionRecord::default() {
+        COLLECTION_REGISTRY.remove(deps.storage, collection);
+    } else {
+        COLLECTION_REGISTRY.save(deps.storage, collection, &record)?;
+    }
+    Ok(())
+}
+
+pub fn sudo_set_pause(deps: DepsMut, pause: PauseState) -> Result<Response, ContractError> {
+    PAUSE.save(deps.storage, &pause)?;
+
+    let res = Response::new()
+        .add_attribute("action", "set_pause")
+        .add_attribute("listing", pause.listing.to_string())
+        .add_attribute("bidding", pause.bidding.to_string())
+        .add_attribute("settlement", pause.settlement.to_string())
+        .add_attribute("removal", pause.removal.to_string());
+    Ok(res)
+}
+
 pub fn sudo_add_operator(deps: DepsMut, operator: Addr) -> Result<Response, ContractError> {
     let mut params = SUDO_PARAMS.load(deps.storage)?;
     if !params.operators.iter().any(|o| o == &operator) {
@@ -129,6 +331,15 @@ pub fn sudo_add_sale_hook(deps: DepsMut, hook: Addr) -> Result<Response, Contrac
     Ok(res)
 }
 
+pub fn sudo_add_sale_hook_v2(deps: DepsMut, hook: Addr) -> Result<Response, ContractError> {
+    SALE_HOOKS_V2.add_hook(deps.storage, hook.clone())?;
+
+    let res = Response::new()
+        .add_attribute("action", "add_sale_hook_v2")
+        .add_attribute("hook", hook);
+    Ok(res)
+}
+
 pub fn sudo_add_ask_hook(deps: DepsMut, _env: Env, hook: Addr) -> Result<Response, ContractError> {
     ASK_HOOKS.add_hook(deps.storage, hook.clone())?;
 
@@ -147,6 +358,24 @@ pub fn sudo_add_bid_hook(deps: DepsMut, _env: Env, hook: Addr) -> Result<Respons
     Ok(res)
 }
 
+pub fn sudo_add_offer_hook(deps: DepsMut, hook: Addr) -> Result<Response, ContractError> {
+    OFFER_HOOKS.add_hook(deps.storage, hook.clone())?;
+
+    let res = Response::new()
+        .add_attribute("action", "add_offer_hook")
+        .add_attribute("hook", hook);
+    Ok(res)
+}
+
+pub fn sudo_add_swap_hook(deps: DepsMut, hook: Addr) -> Result<Response, ContractError> {
+    SWAP_HOOKS.add_hook(deps.storage, hook.clone())?;
+
+    let res = Response::new()
+        .add_attribute("action", "add_swap_hook")
+        .add_attribute("hook", hook);
+    Ok(res)
+}
+
 pub fn sudo_remove_sale_hook(deps: DepsMut, hook: Addr) -> Result<Response, ContractError> {
     SALE_HOOKS.remove_hook(deps.storage, hook.clone())?;
 
@@ -156,6 +385,15 @@ pub fn sudo_remove_sale_hook(deps: DepsMut, hook: Addr) -> Result<Response, Cont
     Ok(res)
 }
 
+pub fn sudo_remove_sale_hook_v2(deps: DepsMut, hook: Addr) -> Result<Response, ContractError> {
+    SALE_HOOKS_V2.remove_hook(deps.storage, hook.clone())?;
+
+    let res = Response::new()
+        .add_attribute("action", "remove_sale_hook_v2")
+        .add_attribute("hook", hook);
+    Ok(res)
+}
+
 pub fn sudo_remove_ask_hook(deps: DepsMut, hook: Addr) -> Result<Response, ContractError> {
     ASK_HOOKS.remove_hook(deps.storage, hook.clone())?;
 
@@ -173,3 +411,39 @@ pub fn sudo_remove_bid_hook(deps: DepsMut, hook: Addr) -> Result<Response, Contr
         .add_attribute("hook", hook);
     Ok(res)
 }
+
+pub fn sudo_remove_swap_hook(deps: DepsMut, hook: Addr) -> Result<Response, ContractError> {
+    SWAP_HOOKS.remove_hook(deps.storage, hook.clone())?;
+
+    let res = Response::new()
+        .add_attribute("action", "remove_swap_hook")
+        .add_attribute("hook", hook);
+    Ok(res)
+}
+
+pub fn sudo_remove_offer_hook(deps: DepsMut, hook: Addr) -> Result<Response, ContractError> {
+    OFFER_HOOKS.remove_hook(deps.storage, hook.clone())?;
+
+    let res = Response::new()
+        .add_attribute("action", "remove_offer_hook")
+        .add_attribute("hook", hook);
+    Ok(res)
+}
+
+pub fn sudo_add_trait_bid_hook(deps: DepsMut, hook: Addr) -> Result<Response, ContractError> {
+    TRAIT_BID_HOOKS.add_hook(deps.storage, hook.clone())?;
+
+    let res = Response::new()
+        .add_attribute("action", "add_trait_bid_hook")
+        .add_attribute("hook", hook);
+    Ok(res)
+}
+
+pub fn sudo_remove_trait_bid_hook(deps: DepsMut, hook: Addr) -> Result<Response, ContractError> {
+    TRAIT_BID_HOOKS.remove_hook(deps.storage, hook.clone())?;
+
+    let res = Response::new()
+        .add_attribute("action", "remove_trait_bid_hook")
+        .add_attribute("hook", hook);
+    Ok(res)
+}
@@ -0,0 +1,232 @@
+use crate::error::ContractError;
+use crate::escrow::{track_escrow, Escrowed};
+use crate::execute::{
+    only_nft_owner_of, only_unpaused, only_verified_collection, prepare_swap_hook,
+    transfer_items, NATIVE_DENOM,
+};
+use crate::msg::{HookAction, NftRef};
+use crate::state::{collection_params, swaps, NftItem, OrderExpire, Swap, PAUSE, SWAP_COUNT};
+use cosmwasm_std::{coin, Addr, BankMsg, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+use cw721::Cw721ReceiveMsg;
+use cw_utils::{maybe_addr, may_pay, nonpayable};
+
+/// Most NFTs a swap can offer, and request
+pub const MAX_SWAP_ITEMS: u32 = 25;
+
+/// Escrows the offered NFTs and funds against the requested NFTs. Every collection on
+/// either side must pass the checks of a listing, including its ask expiry range.
+pub fn execute_create_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offered: Vec<NftRef>,
+    requested: Vec<NftRef>,
+    counterparty: Option<String>,
+    expires: u64,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.listing, "listing")?;
+
+    let proposer = info.sender.clone();
+    let offered = validate_items(deps.as_ref(), offered, expires)?;
+    let requested = validate_items(deps.as_ref(), requested, expires)?;
+    for item in &offered {
+        only_nft_owner_of(deps.as_ref(), item, &proposer)?;
+    }
+
+    let id = SWAP_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    SWAP_COUNT.save(deps.storage, &id)?;
+
+    let swap = Swap {
+        id,
+        proposer: proposer.clone(),
+        counterparty: maybe_addr(deps.api, counterparty)?,
+        offered,
+        offered_funds: may_pay(&info, NATIVE_DENOM)?,
+        requested,
+        expires_at: env.block.time.plus_seconds(expires),
+    };
+    swaps().save(deps.storage, id, &swap)?;
+    track_escrow(deps.storage, Escrowed::Swap(id), Uint128::zero(), swap.offered_funds)?;
+
+    let mut res = Response::new();
+    transfer_items(&swap.offered, &env.contract.address, &mut res)?;
+
+    let hook = prepare_swap_hook(deps.as_ref(), &swap, HookAction::Create)?;
+
+    Ok(res
+        .add_submessages(hook)
+        .add_attribute("human_action", "human_marketplace_create_swap")
+        .add_attribute("id", id.to_string())
+        .add_attribute("proposer", proposer)
+        .add_attribute("offered", swap.offered.len().to_string())
+        .add_attribute("offered_funds", swap.offered_funds.to_string())
+        .add_attribute("requested", swap.requested.len().to_string())
+        .add_attribute("expires", expires.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// Accepts a swap with requested NFTs the marketplace is approved for
+pub fn execute_accept_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    accept_swap(deps, env, info.sender, id, None)
+}
+
+/// Accepts a swap with a requested NFT sent to the marketplace, the marketplace must be
+/// approved for any other requested NFTs
+pub fn execute_accept_swap_with_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rcv_msg: Cw721ReceiveMsg,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let acceptor = deps.api.addr_validate(&rcv_msg.sender)?;
+    let received = NftItem {
+        collection: info.sender,
+        token_id: rcv_msg.token_id,
+    };
+
+    accept_swap(deps, env, acceptor, id, Some(received))
+}
+
+/// Cancels a swap, returning the escrowed NFTs and funds to the proposer. Anyone may clean
+/// up an expired swap.
+pub fn execute_cancel_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.removal, "removal")?;
+
+    nonpayable(&info)?;
+
+    let swap = swaps()
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::SwapNotFound {})?;
+    if info.sender != swap.proposer && !swap.is_expired(&env.block) {
+        return Err(ContractError::UnauthorizedOwner {});
+    }
+
+    swaps().remove(deps.storage, id)?;
+    track_escrow(deps.storage, Escrowed::Swap(id), swap.offered_funds, Uint128::zero())?;
+
+    let mut res = Response::new();
+    transfer_items(&swap.offered, &swap.proposer, &mut res)?;
+    if !swap.offered_funds.is_zero() {
+        res = res.add_message(BankMsg::Send {
+            to_address: swap.proposer.to_string(),
+            amount: vec![coin(swap.offered_funds.u128(), NATIVE_DENOM)],
+        });
+    }
+
+    let hook = prepare_swap_hook(deps.as_ref(), &swap, HookAction::Delete)?;
+
+    Ok(res
+        .add_submessages(hook)
+        .add_attribute("human_action", "human_marketplace_cancel_swap")
+        .add_attribute("id", id.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+fn accept_swap(
+    deps: DepsMut,
+    env: Env,
+    acceptor: Addr,
+    id: u64,
+    received: Option<NftItem>,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.settlement, "settlement")?;
+
+    let swap = swaps()
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::SwapNotFound {})?;
+
+    if swap.is_expired(&env.block) {
+        return Err(ContractError::SwapExpired {});
+    }
+    if acceptor == swap.proposer {
+        return Err(ContractError::SwapSelfAccept {});
+    }
+    if swap.counterparty.as_ref().map_or(false, |c| *c != acceptor) {
+        return Err(ContractError::UnauthorizedCounterparty {});
+    }
+    if received.as_ref().map_or(false, |item| !swap.requested.contains(item)) {
+        return Err(ContractError::SwapTokenNotRequested {});
+    }
+
+    // The received NFT is already held by the marketplace, the others are moved through approvals
+    for item in &swap.requested {
+        if received.as_ref() != Some(item) {
+            only_nft_owner_of(deps.as_ref(), item, &acceptor)?;
+        }
+    }
+
+    swaps().remove(deps.storage, id)?;
+    track_escrow(deps.storage, Escrowed::Swap(id), swap.offered_funds, Uint128::zero())?;
+
+    let mut res = Response::new();
+    transfer_items(&swap.requested, &swap.proposer, &mut res)?;
+    transfer_items(&swap.offered, &acceptor, &mut res)?;
+    if !swap.offered_funds.is_zero() {
+        res = res.add_message(BankMsg::Send {
+            to_address: acceptor.to_string(),
+            amount: vec![coin(swap.offered_funds.u128(), NATIVE_DENOM)],
+        });
+    }
+
+    let hook = prepare_swap_hook(deps.as_ref(), &swap, HookAction::Delete)?;
+
+    Ok(res
+        .add_submessages(hook)
+        .add_attribute("human_action", "human_marketplace_accept_swap")
+        .add_attribute("id", id.to_string())
+        .add_attribute("proposer", swap.proposer.to_string())
+        .add_attribute("acceptor", acceptor)
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+fn validate_items(
+    deps: Deps,
+    items: Vec<NftRef>,
+    expires: u64,
+) -> Result<Vec<NftItem>, ContractError> {
+    if items.is_empty() {
+        return Err(ContractError::EmptyBatch {});
+    }
+    if items.len() > MAX_SWAP_ITEMS as usize {
+        return Err(ContractError::SwapTooLarge(MAX_SWAP_ITEMS));
+    }
+
+    let mut nfts: Vec<NftItem> = vec![];
+    for NftRef {
+        collection,
+        token_id,
+    } in items
+    {
+        let item = NftItem {
+            collection: deps.api.addr_validate(&collection)?,
+            token_id,
+        };
+        if nfts.contains(&item) {
+            return Err(ContractError::DuplicateNft {});
+        }
+        if !nfts.iter().any(|nft| nft.collection == item.collection) {
+            only_verified_collection(deps, &item.collection)?;
+            let (params, _) = collection_params(deps.storage, &item.collection)?;
+            params.ask_expiry.is_valid(expires)?;
+        }
+        nfts.push(item);
+    }
+
+    Ok(nfts)
+}
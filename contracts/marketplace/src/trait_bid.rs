@@ -0,0 +1,182 @@
+use crate::error::ContractError;
+use crate::escrow::{track_escrow, Escrowed};
+use crate::execute::{
+    finalize_sale, only_unpaused, only_verified_collection, prepare_trait_bid_hook, Sale,
+    NATIVE_DENOM,
+};
+use crate::msg::{HookAction, Metadata, NftInfoResponse};
+use crate::state::{
+    collection_params, trait_bids, OrderExpire, SaleOrigin, TraitBid, PAUSE, TRAIT_BID_COUNT,
+};
+use cosmwasm_std::{coin, Addr, BankMsg, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+use cw721::Cw721ReceiveMsg;
+use cw721_base::QueryMsg as Cw721QueryMsg;
+use cw_utils::{must_pay, nonpayable};
+
+/// Escrows a bid on any NFT of a collection that has the trait
+pub fn execute_set_trait_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    collection: Addr,
+    trait_type: String,
+    value: String,
+    expires: u64,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.bidding, "bidding")?;
+
+    let price = must_pay(&info, NATIVE_DENOM)?;
+    only_verified_collection(deps.as_ref(), &collection)?;
+
+    let (params, _) = collection_params(deps.storage, &collection)?;
+    if price < params.min_price {
+        return Err(ContractError::PriceTooSmall(price));
+    }
+    params.bid_expiry.is_valid(expires)?;
+
+    let id = TRAIT_BID_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    TRAIT_BID_COUNT.save(deps.storage, &id)?;
+
+    let bid = TraitBid {
+        id,
+        collection: collection.clone(),
+        trait_type,
+        value,
+        bidder: info.sender.clone(),
+        price,
+        expires_at: env.block.time.plus_seconds(expires),
+    };
+    trait_bids().save(deps.storage, id, &bid)?;
+    track_escrow(deps.storage, Escrowed::TraitBid(id), Uint128::zero(), price)?;
+
+    let hook = prepare_trait_bid_hook(deps.as_ref(), &bid, HookAction::Create)?;
+
+    Ok(Response::new()
+        .add_submessages(hook)
+        .add_attribute("human_action", "human_marketplace_set_trait_bid")
+        .add_attribute("id", id.to_string())
+        .add_attribute("collection", collection.to_string())
+        .add_attribute("trait_type", bid.trait_type)
+        .add_attribute("value", bid.value)
+        .add_attribute("bidder", bid.bidder)
+        .add_attribute("price", price.to_string())
+        .add_attribute("expires", expires.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// Withdraws a trait bid and refunds the bidder. Anyone may clean up an expired trait bid.
+pub fn execute_remove_trait_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.removal, "removal")?;
+
+    nonpayable(&info)?;
+
+    let bid = trait_bids()
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::TraitBidNotFound {})?;
+    if info.sender != bid.bidder && !bid.is_expired(&env.block) {
+        return Err(ContractError::UnauthorizedOwner {});
+    }
+
+    trait_bids().remove(deps.storage, id)?;
+    track_escrow(deps.storage, Escrowed::TraitBid(id), bid.price, Uint128::zero())?;
+
+    let refund_msg = BankMsg::Send {
+        to_address: bid.bidder.to_string(),
+        amount: vec![coin(bid.price.u128(), NATIVE_DENOM)],
+    };
+
+    let hook = prepare_trait_bid_hook(deps.as_ref(), &bid, HookAction::Delete)?;
+
+    Ok(Response::new()
+        .add_message(refund_msg)
+        .add_submessages(hook)
+        .add_attribute("human_action", "human_marketplace_remove_trait_bid")
+        .add_attribute("id", id.to_string())
+        .add_attribute("bidder", bid.bidder)
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// Sells the NFT sent to the marketplace to a trait bid, once the collection confirms the
+/// NFT has the trait
+pub fn execute_accept_trait_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rcv_msg: Cw721ReceiveMsg,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let pause = PAUSE.may_load(deps.storage)?.unwrap_or_default();
+    only_unpaused(pause.settlement, "settlement")?;
+
+    let bid = trait_bids()
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::TraitBidNotFound {})?;
+    if bid.is_expired(&env.block) {
+        return Err(ContractError::TraitBidExpired {});
+    }
+    if info.sender != bid.collection {
+        return Err(ContractError::CollectionMismatch {});
+    }
+    let seller = deps.api.addr_validate(&rcv_msg.sender)?;
+    if seller == bid.bidder {
+        return Err(ContractError::UnauthorizedOwner {});
+    }
+    only_verified_collection(deps.as_ref(), &bid.collection)?;
+    only_matching_trait(deps.as_ref(), &bid, &rcv_msg.token_id)?;
+
+    trait_bids().remove(deps.storage, id)?;
+    track_escrow(deps.storage, Escrowed::TraitBid(id), bid.price, Uint128::zero())?;
+
+    let mut res = Response::new();
+    let sale = Sale {
+        collection: bid.collection.clone(),
+        token_id: rcv_msg.token_id.clone(),
+        seller: seller.clone(),
+        funds_recipient: seller.clone(),
+        price: bid.price,
+        buyer: bid.bidder.clone(),
+        origin: SaleOrigin::TraitBid,
+    };
+    finalize_sale(deps.as_ref(), &env, sale, &mut res)?;
+
+    let hook = prepare_trait_bid_hook(deps.as_ref(), &bid, HookAction::Delete)?;
+
+    Ok(res
+        .add_submessages(hook)
+        .add_attribute("human_action", "human_marketplace_accept_trait_bid")
+        .add_attribute("id", id.to_string())
+        .add_attribute("collection", bid.collection.to_string())
+        .add_attribute("token_id", rcv_msg.token_id)
+        .add_attribute("bidder", bid.bidder)
+        .add_attribute("seller", seller)
+        .add_attribute("price", bid.price.to_string())
+        .add_attribute("time", env.block.time.to_string()))
+}
+
+/// Checks the trait against the NFT metadata of the collection, not against any listing
+fn only_matching_trait(deps: Deps, bid: &TraitBid, token_id: &str) -> Result<(), ContractError> {
+    let nft_info: NftInfoResponse<Metadata> = deps.querier.query_wasm_smart(
+        bid.collection.clone(),
+        &Cw721QueryMsg::NftInfo {
+            token_id: token_id.to_string(),
+        },
+    )?;
+
+    let matches = nft_info
+        .extension
+        .attributes
+        .iter()
+        .any(|t| t.trait_type == bid.trait_type && t.value == bid.value);
+    if !matches {
+        return Err(ContractError::TraitMismatch {});
+    }
+
+    Ok(())
+}
@@ -1,44 +1,191 @@
-use crate::execute::AskInfo;
 #[cfg(test)]
 use crate::execute::{execute, instantiate};
-use crate::msg::{ExecuteMsg, InstantiateMsg, };
-use crate::query::{ query_ask,  query_bids, query_asks_by_bid_count, query_all_bids, query_asks_sorted_by_expiration};
-use crate::state:: SaleType;
-use crate::helpers::ExpiryRange;
+use crate::msg::{AskInfo, ReceiveNftMsg, ReceiveNftMsgV1, ExecuteMsg, SignedOrder, OrderSignDoc, InstantiateMsg, MigrateMsg, BatchBid, NftRef, AskPriceUpdate, BundleShare, CreateCollectionQueryMsg, NftInfoResponse, Metadata, Trait, SudoMsg, CollectionVerification, RoyaltyPayout, Cw2981QueryMsg, RoyaltiesInfoResponse, RoyaltySource, SaleExecuteMsg, SaleHookMsg, SaleHookMsgV2};
+use crate::query::{ query_ask,  query_bids, query_asks_by_bid_count, query_all_bids, query_asks_sorted_by_expiration, query_collection_status, query_registered_collections, query_collection_config, query_pause_status, query_asks_by_content_type, query_migration_status, query_bundle_ask, query_bundle_asks_by_seller, query_swap, query_swaps_by_counterparty, query_offer, query_offers, query_offers_by_seller, query_order_key, query_order_nonce, query_asks_by_trait, query_trait_bid, query_trait_bids_sorted_by_price, query_trait_bids_by_bidder, query_custody};
+use crate::execute::{simulate_payout, MAX_BATCH};
+use crate::state::{ask_key, asks, CollectionConfig, Custody, CUSTODY, SaleOrigin, SaleType, RecordMigration, NonceStatus, SALE_HOOKS_V2, SUDO_PARAMS};
+use crate::sudo::sudo;
+use crate::escrow::escrow_summary;
+use crate::migrate::migrate;
+use crate::helpers::{ExpiryRange, ExpiryRangeError};
+use crate::ContractError;
 
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{ Addr, DepsMut,Uint128,to_binary,CosmosMsg, WasmMsg,  Coin, BankMsg};
-use cw721::{Cw721ReceiveMsg,Cw721ExecuteMsg};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{ Addr, DepsMut, Response,Uint128,to_binary,CosmosMsg, WasmMsg,  Coin, BankMsg, Decimal, from_binary, OwnedDeps, WasmQuery, SystemResult, ContractResult, SystemError, Binary, to_vec, StdError};
+use cw721::{Cw721ReceiveMsg,Cw721ExecuteMsg,OwnerOfResponse};
+use cw721_base::{CollectionInfoResponse, QueryMsg as Cw721QueryMsg, Royalty, RoyaltyShare};
+use cw721_base::msg::{AdminResponse, CollectionInfo};
+use cw2::set_contract_version;
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const FACTORY: &str = "create_collection_address";
+const UNKNOWN_COLLECTION: &str = "unknown_collection";
+const FACTORY_ERROR_COLLECTION: &str = "factory_error_collection";
+const ROYALTY_COLLECTION: &str = "royalty_collection";
+const FULL_ROYALTY_COLLECTION: &str = "full_royalty_collection";
+const CW2981_COLLECTION: &str = "cw2981_collection";
+const NO_STATE_COLLECTION: &str = "no_state_collection";
+
+// Answers the factory and cw721 queries the marketplace makes while listing and settling.
+// Every collection except `UNKNOWN_COLLECTION` is known to the factory, which fails to
+// answer for `FACTORY_ERROR_COLLECTION`. Only
+// `CW2981_COLLECTION` answers the CW2981 royalty query, and only `ROYALTY_COLLECTION`
+// and `FULL_ROYALTY_COLLECTION` report a royalty in their collection state. Tokens are
+// owned by seller1, except those whose id starts with "Other", which belong to seller2,
+// and those whose id starts with "Held", which the marketplace holds.
+// Tokens whose id contains "Gold" have a gold background trait, all others a blue one.
+fn mock_deps() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+  let mut deps = mock_dependencies();
+  deps.querier.update_wasm(|query| {
+    let (contract_addr, msg) = match query {
+      WasmQuery::Smart { contract_addr, msg } => (contract_addr, msg),
+      _ => return SystemResult::Err(SystemError::Unknown {}),
+    };
+    let res: Binary = if contract_addr == FACTORY {
+      let CreateCollectionQueryMsg::CheckCollection { address } = from_binary(msg).unwrap();
+      if address == FACTORY_ERROR_COLLECTION {
+        return SystemResult::Err(SystemError::InvalidRequest { error: "factory misconfigured".to_string(), request: msg.clone() });
+      }
+      to_binary(&(address != UNKNOWN_COLLECTION)).unwrap()
+    } else if let Ok(Cw2981QueryMsg::RoyaltyInfo { sale_price, .. }) = from_binary(msg) {
+      if contract_addr != CW2981_COLLECTION {
+        return SystemResult::Err(SystemError::Unknown {});
+      }
+      to_binary(&RoyaltiesInfoResponse { address: "artist3".to_string(), royalty_amount: sale_price * Decimal::percent(7) }).unwrap()
+    } else {
+      let royalty_info = match contract_addr.as_str() {
+        ROYALTY_COLLECTION => Some(Royalty {
+          address: "artist1".to_string(),
+          royalty_rate: Decimal::percent(10),
+          shares: Some(vec![RoyaltyShare { address: "artist2".to_string(), share: Decimal::from_ratio(1u128, 3u128) }]),
+        }),
+        FULL_ROYALTY_COLLECTION => Some(Royalty {
+          address: "artist1".to_string(),
+          royalty_rate: Decimal::one(),
+          shares: None,
+        }),
+        _ => None,
+      };
+      match from_binary(msg).unwrap() {
+        Cw721QueryMsg::NftInfo { token_id } => to_binary(&NftInfoResponse {
+          token_uri: None,
+          content_type: "image".to_string(),
+          created_time: 0,
+          extension: Metadata {
+            image_url: format!("{}.png", token_id),
+            minter: "minter".to_string(),
+            name: None,
+            description: None,
+            animation_url: None,
+            attributes: vec![Trait {
+              display_type: None,
+              trait_type: "background".to_string(),
+              value: if token_id.contains("Gold") { "gold" } else { "blue" }.to_string(),
+            }],
+          },
+        }).unwrap(),
+        Cw721QueryMsg::GetCollectionState {} if contract_addr != NO_STATE_COLLECTION => to_binary(&CollectionInfoResponse {
+          collection_info: CollectionInfo {
+            title: None,
+            creator: None,
+            image_url: None,
+            background_url: None,
+            logo_url: None,
+            collection_id: None,
+            metadata_url: None,
+            social_links: None,
+            description: None,
+            is_launch: None,
+          },
+          mint_info: None,
+          minter: "minter".to_string(),
+          royalty_info,
+        }).unwrap(),
+        Cw721QueryMsg::Admin {} => to_binary(&AdminResponse { admin: "admin".to_string() }).unwrap(),
+        Cw721QueryMsg::OwnerOf { token_id, .. } => to_binary(&OwnerOfResponse {
+          owner: if token_id.starts_with("Other") {
+            "seller2"
+          } else if token_id.starts_with("Held") {
+            MOCK_CONTRACT_ADDR
+          } else {
+            "seller1"
+          }.to_string(),
+          approvals: vec![],
+        }).unwrap(),
+        _ => return SystemResult::Err(SystemError::Unknown {}),
+      }
+    };
+    SystemResult::Ok(ContractResult::Ok(res))
+  });
+  deps
+}
+
+fn list_nft(deps: DepsMut, collection: &str, token_id: &str, seller: &str) -> Result<(), ContractError> {
+  list_nft_for(deps, collection, token_id, seller, SaleType::Auction, 300)
+}
+
+fn list_nft_for(deps: DepsMut, collection: &str, token_id: &str, seller: &str, sale_type: SaleType, price: u128) -> Result<(), ContractError> {
+  let sell_msg = AskInfo{
+    sale_type,
+    collection: Addr::unchecked(collection.to_string()),
+    token_id: token_id.to_string(),
+    price: Coin { denom: "uheart".to_string(), amount: Uint128::new(price) },
+    funds_recipient: None,
+    expires: 300,
+  };
+
+  let info = mock_info(collection, &[]);
+  let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg{
+      sender: seller.to_string(),
+      token_id: token_id.to_string(),
+      msg:to_binary(&sell_msg).unwrap()
+  });
+  execute(deps, mock_env(), info, msg).map(|_| ())
+}
+
+/// The origin reported to the v2 sale hook registered as "hook_v2"
+fn v2_sale_origin(res: &Response) -> SaleOrigin {
+  res.messages.iter().find_map(|m| match &m.msg {
+    CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) if contract_addr == "hook_v2" => match from_binary(msg).unwrap() {
+      SaleExecuteMsg::SaleHookV2(hook) => Some(hook.origin),
+      SaleExecuteMsg::SaleHook(_) => None,
+    },
+    _ => None,
+  }).unwrap()
+}
 
 
 fn setup_contract(deps: DepsMut){
    let instantiate_msg = InstantiateMsg {
         trading_fee_bps: 25,
-        /// Valid time range for Asks
-        /// (min, max) in seconds
+        fee_recipient: Some("fee_recipient".to_string()),
+        // Valid time range for Asks
+        // (min, max) in seconds
         ask_expiry: ExpiryRange{
           min:100,
           max:500
         },
-        /// Valid time range for Bids
-        /// (min, max) in seconds
+        // Valid time range for Bids
+        // (min, max) in seconds
         bid_expiry: ExpiryRange{
           min: 100,
           max: 500
         },
-        /// Operators are entites that are responsible for maintaining the active state of Asks.
-        /// They listen to NFT transfer events, and update the active state of Asks.
+        // Operators are entites that are responsible for maintaining the active state of Asks.
+        // They listen to NFT transfer events, and update the active state of Asks.
         operators: vec![],
-        /// The address of the airdrop claim contract to detect sales
+        // The address of the airdrop claim contract to detect sales
         sale_hook: Some("hook".to_string()),
-        /// Max basis points for the finders fee
+        // Max basis points for the finders fee
         //  max_finders_fee_bps: u64,
-        /// Min value for bids and asks
+        // Min value for bids and asks
         min_price: Uint128::new(10),
-        /// Listing fee to reduce spam
+        // Listing fee to reduce spam
         listing_fee: Uint128::zero(),
 
-        create_collection_address: "create_collection_address".to_string()
+        create_collection_address: FACTORY.to_string()
     };
     let info = mock_info("owner", &[]);
     let res = instantiate(deps, mock_env(), info, instantiate_msg).unwrap();
@@ -52,28 +199,29 @@ fn init_contract() {
     let mut deps = mock_dependencies();
     let instantiate_msg = InstantiateMsg {
          trading_fee_bps: 25,
-        /// Valid time range for Asks
-        /// (min, max) in seconds
+        fee_recipient: None,
+        // Valid time range for Asks
+        // (min, max) in seconds
         ask_expiry: ExpiryRange{
           min:100,
           max:500
         },
-        /// Valid time range for Bids
-        /// (min, max) in seconds
+        // Valid time range for Bids
+        // (min, max) in seconds
         bid_expiry: ExpiryRange{
           min: 100,
           max: 500
         },
-        /// Operators are entites that are responsible for maintaining the active state of Asks.
-        /// They listen to NFT transfer events, and update the active state of Asks.
+        // Operators are entites that are responsible for maintaining the active state of Asks.
+        // They listen to NFT transfer events, and update the active state of Asks.
         operators: vec![],
-        /// The address of the airdrop claim contract to detect sales
+        // The address of the airdrop claim contract to detect sales
         sale_hook: Some("hook".to_string()),
-        /// Max basis points for the finders fee
+        // Max basis points for the finders fee
         //  max_finders_fee_bps: u64,
-        /// Min value for bids and asks
+        // Min value for bids and asks
         min_price: Uint128::new(10),
-        /// Listing fee to reduce spam
+        // Listing fee to reduce spam
         listing_fee: Uint128::zero(),
         create_collection_address: "create_collection_address".to_string()
     };
@@ -86,7 +234,7 @@ fn init_contract() {
 
 #[test]
 fn test_ask(){
-  let mut deps = mock_dependencies();
+  let mut deps = mock_deps();
   let env = mock_env();
   setup_contract(deps.as_mut());
 
@@ -217,9 +365,46 @@ fn test_ask(){
   println!("{:?}",result)
 }
 
+#[test]
+fn test_receive_nft_msg(){
+  let mut deps = mock_deps();
+  setup_contract(deps.as_mut());
+
+  let ask_info = |token_id: &str| AskInfo{
+    sale_type: SaleType::FixedPrice,
+    collection: Addr::unchecked("collection1"),
+    token_id: token_id.to_string(),
+    price: Coin { denom: "uheart".to_string(), amount: Uint128::new(1000) },
+    funds_recipient: None,
+    expires: 300,
+  };
+  let receive = |token_id: &str, msg: Binary| ExecuteMsg::ReceiveNft(Cw721ReceiveMsg{
+    sender: "seller1".to_string(),
+    token_id: token_id.to_string(),
+    msg,
+  });
+
+  // both the versioned payload and a bare legacy `AskInfo` list the NFT
+  let msg = receive("Test.1", to_binary(&ReceiveNftMsg::V1(ReceiveNftMsgV1::SetAsk(ask_info("Test.1")))).unwrap());
+  execute(deps.as_mut(), mock_env(), mock_info("collection1", &[]), msg).unwrap();
+  let msg = receive("Test.2", to_binary(&ask_info("Test.2")).unwrap());
+  execute(deps.as_mut(), mock_env(), mock_info("collection1", &[]), msg).unwrap();
+  assert!(query_ask(deps.as_ref(), Addr::unchecked("collection1"), "Test.1".to_string()).unwrap().ask.is_some());
+  assert!(query_ask(deps.as_ref(), Addr::unchecked("collection1"), "Test.2".to_string()).unwrap().ask.is_some());
+
+  // an unversioned v1 payload is not read
+  let msg = receive("Test.3", to_binary(&ReceiveNftMsgV1::SetAsk(ask_info("Test.3"))).unwrap());
+  let err = execute(deps.as_mut(), mock_env(), mock_info("collection1", &[]), msg).unwrap_err();
+  assert!(matches!(err, ContractError::InvalidReceiveNftMsg(_)));
+
+  let msg = receive("Test.3", Binary::from(br#"{"v1":{"set_bid":{}}}"#));
+  let err = execute(deps.as_mut(), mock_env(), mock_info("collection1", &[]), msg).unwrap_err();
+  assert!(matches!(err, ContractError::InvalidReceiveNftMsg(_)));
+}
+
 #[test]
 fn test_accept_bid_without_bid(){
-  let mut deps = mock_dependencies();
+  let mut deps = mock_deps();
   let mut env = mock_env();
   setup_contract(deps.as_mut());
 
@@ -260,7 +445,7 @@ fn test_accept_bid_without_bid(){
 
 #[test]
 fn test_asks_sort_by_expiration(){
-  let mut deps = mock_dependencies();
+  let mut deps = mock_deps();
   let mut env = mock_env();
   setup_contract(deps.as_mut());
 
@@ -324,8 +509,8 @@ fn test_asks_sort_by_expiration(){
 
 #[test]
 fn test_remove_ask() {
-  let mut deps = mock_dependencies();
-  let mut env = mock_env();
+  let mut deps = mock_deps();
+  let env = mock_env();
   setup_contract(deps.as_mut());
 
   let sell_msg = AskInfo{
@@ -377,8 +562,8 @@ fn test_remove_ask() {
 
 #[test]
 fn test_remove_bids(){
-  let mut deps = mock_dependencies();
-  let mut env = mock_env();
+  let mut deps = mock_deps();
+  let env = mock_env();
   setup_contract(deps.as_mut());
 
   let sell_msg = AskInfo{
@@ -456,4 +641,1230 @@ fn test_remove_bids(){
 
   // let all_bids = query_all_bids(deps.as_ref(), Addr::unchecked("collection1") , "Test.1".to_string()).unwrap();
   // println!("all bids after accept the max bid {:?}", all_bids);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_collection_registry(){
+  let mut deps = mock_deps();
+  setup_contract(deps.as_mut());
+
+  // unregistered collections fall back to the factory check
+  list_nft(deps.as_mut(), "collection1", "Test.1", "seller1").unwrap();
+  let err = list_nft(deps.as_mut(), UNKNOWN_COLLECTION, "Test.1", "seller1").unwrap_err();
+  assert_eq!(err, ContractError::ContractNotFound {});
+
+  // a failing factory is reported as such rather than as an unregistered collection
+  let err = list_nft(deps.as_mut(), FACTORY_ERROR_COLLECTION, "Test.1", "seller1").unwrap_err();
+  assert!(matches!(err, ContractError::Std(_)), "{:?}", err);
+  query_collection_status(deps.as_ref(), Addr::unchecked(FACTORY_ERROR_COLLECTION)).unwrap_err();
+
+  // allowlisted collections skip the factory check unless it is required
+  sudo(deps.as_mut(), mock_env(), SudoMsg::AllowCollection { collection: UNKNOWN_COLLECTION.to_string() }).unwrap();
+  list_nft(deps.as_mut(), UNKNOWN_COLLECTION, "Test.1", "seller1").unwrap();
+
+  sudo(deps.as_mut(), mock_env(), SudoMsg::UpdateParams {
+    trading_fee_bps: None,
+    ask_expiry: None,
+    bid_expiry: None,
+    operators: None,
+    min_price: None,
+    listing_fee: None,
+    require_factory_check: Some(true),
+    max_royalty_rate: None,
+    remove_max_royalty_rate: false,
+    fee_recipient: None,
+    remove_fee_recipient: false,
+  }).unwrap();
+  let err = list_nft(deps.as_mut(), UNKNOWN_COLLECTION, "Test.2", "seller1").unwrap_err();
+  assert_eq!(err, ContractError::ContractNotFound {});
+
+  sudo(deps.as_mut(), mock_env(), SudoMsg::DenyCollection { collection: "collection2".to_string() }).unwrap();
+  let err = list_nft(deps.as_mut(), "collection2", "Test.1", "seller2").unwrap_err();
+  assert_eq!(err, ContractError::CollectionDenied {});
+
+  sudo(deps.as_mut(), mock_env(), SudoMsg::DisableCollection {
+    collection: "collection3".to_string(),
+    reason: "royalty contract misbehaving".to_string(),
+  }).unwrap();
+  let err = list_nft(deps.as_mut(), "collection3", "Test.1", "seller3").unwrap_err();
+  assert_eq!(err, ContractError::CollectionDisabled { reason: "royalty contract misbehaving".to_string() });
+
+  let status = query_collection_status(deps.as_ref(), Addr::unchecked("collection3")).unwrap().status;
+  assert_eq!(status.verification, CollectionVerification::Disabled);
+
+  sudo(deps.as_mut(), mock_env(), SudoMsg::EnableCollection { collection: "collection3".to_string() }).unwrap();
+  list_nft(deps.as_mut(), "collection3", "Test.1", "seller3").unwrap();
+
+  // enabling an otherwise unlisted collection leaves nothing in the registry
+  let registered = query_registered_collections(deps.as_ref(), None, None).unwrap();
+  let verifications: Vec<_> = registered.collections.into_iter().map(|c| (c.collection.to_string(), c.verification)).collect();
+  assert_eq!(verifications, vec![
+    ("collection2".to_string(), CollectionVerification::Denied),
+    (UNKNOWN_COLLECTION.to_string(), CollectionVerification::Unverified),
+  ]);
+}
+
+#[test]
+fn test_collection_config(){
+  let mut deps = mock_deps();
+  setup_contract(deps.as_mut());
+
+  let fixed_price_only = CollectionConfig {
+    sale_types: Some(vec![SaleType::FixedPrice]),
+    ..CollectionConfig::default()
+  };
+  let msg = ExecuteMsg::SetCollectionConfig { collection: "collection1".to_string(), config: fixed_price_only.clone() };
+  let err = execute(deps.as_mut(), mock_env(), mock_info("seller1", &[]), msg.clone()).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedAdmin {});
+  execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+  let err = list_nft(deps.as_mut(), "collection1", "Test.1", "seller1").unwrap_err();
+  assert_eq!(err, ContractError::SaleTypeNotAllowed {});
+  list_nft_for(deps.as_mut(), "collection1", "Test.9", "seller1", SaleType::FixedPrice, 300).unwrap();
+
+  // an empty set of sale types would block every listing
+  let msg = ExecuteMsg::SetCollectionConfig {
+    collection: "collection1".to_string(),
+    config: CollectionConfig { sale_types: Some(vec![]), ..CollectionConfig::default() },
+  };
+  let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::SaleTypeNotAllowed {});
+
+  // the collection admin cannot grant themselves a protocol fee discount
+  let msg = ExecuteMsg::SetCollectionConfig {
+    collection: "collection1".to_string(),
+    config: CollectionConfig { protocol_fee_discount_bps: Some(5000), ..fixed_price_only },
+  };
+  let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::FeeDiscountGovernanceOnly {});
+
+  let min_price = CollectionConfig {
+    min_price: Some(Uint128::new(400)),
+    protocol_fee_discount_bps: Some(5000),
+    ..CollectionConfig::default()
+  };
+  sudo(deps.as_mut(), mock_env(), SudoMsg::SetCollectionConfig { collection: "collection1".to_string(), config: min_price.clone() }).unwrap();
+  let err = list_nft(deps.as_mut(), "collection1", "Test.1", "seller1").unwrap_err();
+  assert_eq!(err, ContractError::PriceTooSmall(Uint128::new(300)));
+  list_nft(deps.as_mut(), "collection2", "Test.1", "seller1").unwrap();
+
+  let config = query_collection_config(deps.as_ref(), Addr::unchecked("collection1")).unwrap().config;
+  assert_eq!(config, Some(min_price));
+
+  sudo(deps.as_mut(), mock_env(), SudoMsg::SetCollectionConfig { collection: "collection1".to_string(), config: CollectionConfig::default() }).unwrap();
+  list_nft(deps.as_mut(), "collection1", "Test.1", "seller1").unwrap();
+  let config = query_collection_config(deps.as_ref(), Addr::unchecked("collection1")).unwrap().config;
+  assert_eq!(config, None);
+}
+
+#[test]
+fn test_payout_accounting(){
+  let mut deps = mock_deps();
+  setup_contract(deps.as_mut());
+
+  // 0.25% protocol fee, 10% royalty split a third to artist2 with the dust going to artist1
+  let breakdown = simulate_payout(deps.as_ref(), &Addr::unchecked(ROYALTY_COLLECTION), "Test.1", Uint128::new(1000)).unwrap();
+  assert_eq!(breakdown.protocol_fee, Uint128::new(2));
+  assert_eq!(breakdown.royalty_source, RoyaltySource::CollectionState);
+  assert_eq!(breakdown.royalties, vec![
+    RoyaltyPayout { recipient: Addr::unchecked("artist2"), amount: Uint128::new(33) },
+    RoyaltyPayout { recipient: Addr::unchecked("artist1"), amount: Uint128::new(67) },
+  ]);
+  assert_eq!(breakdown.seller_amount, Uint128::new(898));
+
+  // a 100% royalty never takes the protocol fee
+  let breakdown = simulate_payout(deps.as_ref(), &Addr::unchecked(FULL_ROYALTY_COLLECTION), "Test.1", Uint128::new(1000)).unwrap();
+  assert_eq!(breakdown.protocol_fee, Uint128::new(2));
+  assert_eq!(breakdown.royalties, vec![RoyaltyPayout { recipient: Addr::unchecked("artist1"), amount: Uint128::new(998) }]);
+  assert_eq!(breakdown.seller_amount, Uint128::zero());
+
+  // the cap is a rate, so it can never exceed the payment
+  let err = sudo(deps.as_mut(), mock_env(), SudoMsg::UpdateParams {
+    trading_fee_bps: None,
+    ask_expiry: None,
+    bid_expiry: None,
+    operators: None,
+    min_price: None,
+    listing_fee: None,
+    require_factory_check: None,
+    max_royalty_rate: Some(Decimal::percent(101)),
+    remove_max_royalty_rate: false,
+    fee_recipient: None,
+    remove_fee_recipient: false,
+  }).unwrap_err();
+  assert_eq!(err, ContractError::InvalidRoyaltyCap {});
+
+  sudo(deps.as_mut(), mock_env(), SudoMsg::UpdateParams {
+    trading_fee_bps: None,
+    ask_expiry: None,
+    bid_expiry: None,
+    operators: None,
+    min_price: None,
+    listing_fee: None,
+    require_factory_check: None,
+    max_royalty_rate: Some(Decimal::percent(5)),
+    remove_max_royalty_rate: false,
+    fee_recipient: None,
+    remove_fee_recipient: false,
+  }).unwrap();
+  let breakdown = simulate_payout(deps.as_ref(), &Addr::unchecked(FULL_ROYALTY_COLLECTION), "Test.1", Uint128::new(1000)).unwrap();
+  assert_eq!(breakdown.royalties, vec![RoyaltyPayout { recipient: Addr::unchecked("artist1"), amount: Uint128::new(50) }]);
+  assert_eq!(breakdown.seller_amount, Uint128::new(948));
+
+  // a fixed price sale pays out exactly the simulated breakdown
+  let sell_msg = AskInfo{
+    sale_type: SaleType::FixedPrice,
+    collection: Addr::unchecked(ROYALTY_COLLECTION),
+    token_id: "Test.1".to_string(),
+    price: Coin { denom: "uheart".to_string(), amount: Uint128::new(1000) },
+    funds_recipient: None,
+    expires: 300,
+  };
+  let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg{
+      sender: "seller1".to_string(),
+      token_id: "Test.1".to_string(),
+      msg:to_binary(&sell_msg).unwrap()
+  });
+  execute(deps.as_mut(), mock_env(), mock_info(ROYALTY_COLLECTION, &[]), msg).unwrap();
+
+  let info = mock_info("buyer1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(1000) }]);
+  let msg = ExecuteMsg::SetBid { collection: ROYALTY_COLLECTION.to_string(), token_id: "Test.1".to_string() };
+  let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+  let sends: Vec<_> = res.messages.iter().filter_map(|m| match &m.msg {
+    CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => Some((to_address.as_str(), amount[0].amount.u128())),
+    _ => None,
+  }).collect();
+  assert_eq!(sends, vec![("fee_recipient", 2), ("artist2", 16), ("artist1", 34), ("seller1", 948)]);
+
+  // governance can lift the royalty cap again
+  sudo(deps.as_mut(), mock_env(), SudoMsg::UpdateParams {
+    trading_fee_bps: None,
+    ask_expiry: None,
+    bid_expiry: None,
+    operators: None,
+    min_price: None,
+    listing_fee: None,
+    require_factory_check: None,
+    max_royalty_rate: None,
+    remove_max_royalty_rate: true,
+    fee_recipient: None,
+    remove_fee_recipient: false,
+  }).unwrap();
+  assert_eq!(SUDO_PARAMS.load(deps.as_ref().storage).unwrap().max_royalty_rate, None);
+  let breakdown = simulate_payout(deps.as_ref(), &Addr::unchecked(FULL_ROYALTY_COLLECTION), "Test.1", Uint128::new(1000)).unwrap();
+  assert_eq!(breakdown.royalties, vec![RoyaltyPayout { recipient: Addr::unchecked("artist1"), amount: Uint128::new(998) }]);
+
+  // without a fee recipient no protocol fee is charged
+  sudo(deps.as_mut(), mock_env(), SudoMsg::UpdateParams {
+    trading_fee_bps: None,
+    ask_expiry: None,
+    bid_expiry: None,
+    operators: None,
+    min_price: None,
+    listing_fee: None,
+    require_factory_check: None,
+    max_royalty_rate: None,
+    remove_max_royalty_rate: false,
+    fee_recipient: Some("fee_recipient2".to_string()),
+    remove_fee_recipient: true,
+  }).unwrap();
+  assert_eq!(SUDO_PARAMS.load(deps.as_ref().storage).unwrap().fee_recipient, None);
+  let breakdown = simulate_payout(deps.as_ref(), &Addr::unchecked(ROYALTY_COLLECTION), "Test.1", Uint128::new(1000)).unwrap();
+  assert_eq!(breakdown.protocol_fee, Uint128::zero());
+  assert_eq!(breakdown.fee_recipient, None);
+  assert_eq!(breakdown.seller_amount, Uint128::new(900));
+}
+
+#[test]
+fn test_royalty_sources(){
+  let mut deps = mock_deps();
+  setup_contract(deps.as_mut());
+
+  let breakdown = simulate_payout(deps.as_ref(), &Addr::unchecked(CW2981_COLLECTION), "Test.1", Uint128::new(1000)).unwrap();
+  assert_eq!(breakdown.royalty_source, RoyaltySource::Cw2981);
+  assert_eq!(breakdown.royalties, vec![RoyaltyPayout { recipient: Addr::unchecked("artist3"), amount: Uint128::new(70) }]);
+  assert_eq!(breakdown.seller_amount, Uint128::new(928));
+
+  let breakdown = simulate_payout(deps.as_ref(), &Addr::unchecked("collection1"), "Test.1", Uint128::new(1000)).unwrap();
+  assert_eq!(breakdown.royalty_source, RoyaltySource::CollectionState);
+  assert_eq!(breakdown.royalties, vec![]);
+
+  // collections implementing neither query still sell, without royalties
+  let breakdown = simulate_payout(deps.as_ref(), &Addr::unchecked(NO_STATE_COLLECTION), "Test.1", Uint128::new(1000)).unwrap();
+  assert_eq!(breakdown.royalty_source, RoyaltySource::None);
+  assert_eq!(breakdown.royalties, vec![]);
+  assert_eq!(breakdown.seller_amount, Uint128::new(998));
+
+  let sell_msg = AskInfo{
+    sale_type: SaleType::FixedPrice,
+    collection: Addr::unchecked(CW2981_COLLECTION),
+    token_id: "Test.1".to_string(),
+    price: Coin { denom: "uheart".to_string(), amount: Uint128::new(1000) },
+    funds_recipient: None,
+    expires: 300,
+  };
+  let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg{
+      sender: "seller1".to_string(),
+      token_id: "Test.1".to_string(),
+      msg:to_binary(&sell_msg).unwrap()
+  });
+  execute(deps.as_mut(), mock_env(), mock_info(CW2981_COLLECTION, &[]), msg).unwrap();
+
+  let info = mock_info("buyer1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(1000) }]);
+  let msg = ExecuteMsg::SetBid { collection: CW2981_COLLECTION.to_string(), token_id: "Test.1".to_string() };
+  let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+  let royalty_event = res.events.iter().find(|e| e.ty == "royalty-payout").unwrap();
+  assert!(royalty_event.attributes.iter().any(|a| a.key == "source" && a.value == "cw2981"));
+  let source_event = res.events.iter().find(|e| e.ty == "royalty-source").unwrap();
+  assert!(source_event.attributes.iter().any(|a| a.key == "source" && a.value == "cw2981"));
+
+  // a sale without any royalty still reports where royalties were looked up
+  let sell_msg = AskInfo{
+    sale_type: SaleType::FixedPrice,
+    collection: Addr::unchecked(NO_STATE_COLLECTION),
+    token_id: "Test.1".to_string(),
+    price: Coin { denom: "uheart".to_string(), amount: Uint128::new(1000) },
+    funds_recipient: None,
+    expires: 300,
+  };
+  let msg = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg{
+      sender: "seller1".to_string(),
+      token_id: "Test.1".to_string(),
+      msg:to_binary(&sell_msg).unwrap()
+  });
+  execute(deps.as_mut(), mock_env(), mock_info(NO_STATE_COLLECTION, &[]), msg).unwrap();
+
+  let info = mock_info("buyer1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(1000) }]);
+  let msg = ExecuteMsg::SetBid { collection: NO_STATE_COLLECTION.to_string(), token_id: "Test.1".to_string() };
+  let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+  assert!(res.events.iter().all(|e| e.ty != "royalty-payout"));
+  let source_event = res.events.iter().find(|e| e.ty == "royalty-source").unwrap();
+  assert!(source_event.attributes.iter().any(|a| a.key == "source" && a.value == "none"));
+}
+
+#[test]
+fn test_pause(){
+  let mut deps = mock_deps();
+  let mut env = mock_env();
+  setup_contract(deps.as_mut());
+
+  list_nft(deps.as_mut(), "collection1", "Test.1", "seller1").unwrap();
+  list_nft(deps.as_mut(), "collection1", "Test.2", "seller1").unwrap();
+  let info = mock_info("bider1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(350) }]);
+  let msg = ExecuteMsg::SetBid { collection: "collection1".to_string(), token_id: "Test.1".to_string() };
+  execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+
+  sudo(deps.as_mut(), env.clone(), SudoMsg::SetPause { listing: true, bidding: true, settlement: true, removal: false }).unwrap();
+  assert!(query_pause_status(deps.as_ref()).unwrap().pause.settlement);
+
+  let err = list_nft(deps.as_mut(), "collection1", "Test.3", "seller1").unwrap_err();
+  assert_eq!(err, ContractError::Paused("listing".to_string()));
+  let msg = ExecuteMsg::SetOrderKey { pubkey: Binary::from(&[2u8; 33]) };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::Paused("listing".to_string()));
+  // signed orders can still be cancelled while trading is halted
+  let msg = ExecuteMsg::CancelSignedOrders { nonces: vec![1] };
+  execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  let msg = ExecuteMsg::SetBid { collection: "collection1".to_string(), token_id: "Test.2".to_string() };
+  let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+  assert_eq!(err, ContractError::Paused("bidding".to_string()));
+
+  env.block.time = env.block.time.plus_seconds(350);
+  let msg = ExecuteMsg::AcceptBid { collection: "collection1".to_string(), token_id: "Test.1".to_string() };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::Paused("settlement".to_string()));
+
+  // sellers can still exit: unsold NFTs are returned and asks with bids are removed with a refund
+  let msg = ExecuteMsg::AcceptBid { collection: "collection1".to_string(), token_id: "Test.2".to_string() };
+  execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  let msg = ExecuteMsg::RemoveAsk { collection: "collection1".to_string(), token_id: "Test.1".to_string() };
+  let res = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  assert_eq!(res.messages[1].msg, CosmosMsg::Bank(BankMsg::Send { to_address: "bider1".to_string(), amount: vec![Coin{
+      denom: "uheart".to_string(),
+      amount: Uint128::new(350)
+  }] }));
+}
+
+#[test]
+fn test_batch(){
+  let mut deps = mock_deps();
+  let env = mock_env();
+  setup_contract(deps.as_mut());
+
+  list_nft(deps.as_mut(), "collection1", "Test.1", "seller1").unwrap();
+  list_nft(deps.as_mut(), "collection1", "Test.2", "seller1").unwrap();
+  let batch_bid = |token_id: &str, price: u128| BatchBid {
+    collection: "collection1".to_string(),
+    token_id: token_id.to_string(),
+    price: Uint128::new(price),
+  };
+  let bids = vec![batch_bid("Test.3", 500), batch_bid("Test.1", 350), batch_bid("Test.2", 400)];
+
+  // the summed price must not overflow, and batches are bounded
+  let info = mock_info("bider1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(1000) }]);
+  let msg = ExecuteMsg::BatchSetBid { bids: vec![batch_bid("Test.1", u128::MAX), batch_bid("Test.2", 1)], atomic: true };
+  let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+  assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+  let msg = ExecuteMsg::BatchSetBid { bids: vec![batch_bid("Test.1", 1); MAX_BATCH as usize + 1], atomic: true };
+  let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+  assert_eq!(err, ContractError::BatchTooLarge(MAX_BATCH));
+
+  let info = mock_info("bider1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(1000) }]);
+  let msg = ExecuteMsg::BatchSetBid { bids: bids.clone(), atomic: false };
+  let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+  assert_eq!(err, ContractError::InvalidBatchPayment { expected: Uint128::new(1250), received: Uint128::new(1000) });
+
+  // the unlisted Test.3 fails the whole atomic batch
+  let info = mock_info("bider1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(1250) }]);
+  let msg = ExecuteMsg::BatchSetBid { bids: bids.clone(), atomic: true };
+  let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+  assert_eq!(err, ContractError::AskNotFound {});
+
+  // and is refunded in best effort mode
+  let msg = ExecuteMsg::BatchSetBid { bids, atomic: false };
+  let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+  assert_eq!(res.messages.len(), 1);
+  assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send { to_address: "bider1".to_string(), amount: vec![Coin{
+      denom: "uheart".to_string(),
+      amount: Uint128::new(500)
+  }] }));
+  assert_eq!(res.events.iter().filter(|e| e.ty == "batch-bid").count(), 2);
+  assert_eq!(res.events.iter().filter(|e| e.ty == "batch-bid-failed").count(), 1);
+  let ask = query_ask(deps.as_ref(), Addr::unchecked("collection1"), "Test.2".to_string()).unwrap().ask.unwrap();
+  assert_eq!(ask.max_bidder, Some(Addr::unchecked("bider1")));
+  assert_eq!(ask.max_bid, Some(Uint128::new(400)));
+
+  // an invalid collection address is skipped and refunded like any other failed bid
+  let info = mock_info("bider1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(100) }]);
+  let invalid = BatchBid { collection: "C".to_string(), ..batch_bid("Test.1", 100) };
+  let msg = ExecuteMsg::BatchSetBid { bids: vec![invalid.clone()], atomic: true };
+  execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+  let msg = ExecuteMsg::BatchSetBid { bids: vec![invalid], atomic: false };
+  let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+  assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send { to_address: "bider1".to_string(), amount: vec![Coin{
+      denom: "uheart".to_string(),
+      amount: Uint128::new(100)
+  }] }));
+  let failed = res.events.iter().find(|e| e.ty == "batch-bid-failed").unwrap();
+  assert!(failed.attributes.iter().any(|a| a.key == "collection" && a.value == "C"));
+
+  let nft_ref = |token_id: &str| NftRef { collection: "collection1".to_string(), token_id: token_id.to_string() };
+  let msg = ExecuteMsg::BatchRemoveAsk { asks: vec![nft_ref("Test.1"), nft_ref("Test.2")] };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller2", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedOwner {});
+
+  let msg = ExecuteMsg::BatchUpdateAskPrice { updates: vec![AskPriceUpdate {
+    collection: "collection1".to_string(),
+    token_id: "Test.1".to_string(),
+    price: Coin{ denom: "uheart".to_string(), amount: Uint128::new(600) },
+  }] };
+  execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  let ask = query_ask(deps.as_ref(), Addr::unchecked("collection1"), "Test.1".to_string()).unwrap().ask.unwrap();
+  assert_eq!(ask.price, Uint128::new(600));
+
+  // removing both asks returns both NFTs and refunds both bids
+  let msg = ExecuteMsg::BatchRemoveAsk { asks: vec![nft_ref("Test.1"), nft_ref("Test.2")] };
+  let res = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  assert_eq!(res.messages.len(), 4);
+  assert_eq!(res.events.iter().filter(|e| e.ty == "batch-remove-ask").count(), 2);
+  assert!(query_ask(deps.as_ref(), Addr::unchecked("collection1"), "Test.1".to_string()).unwrap().ask.is_none());
+
+  let msg = ExecuteMsg::BatchRemoveAsk { asks: vec![] };
+  let err = execute(deps.as_mut(), env, mock_info("seller1", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::EmptyBatch {});
+}
+
+#[test]
+fn test_sweep_floor(){
+  let mut deps = mock_deps();
+  let mut env = mock_env();
+  setup_contract(deps.as_mut());
+
+  list_nft_for(deps.as_mut(), "collection1", "Test.1", "seller1", SaleType::FixedPrice, 500).unwrap();
+  list_nft_for(deps.as_mut(), "collection1", "Test.2", "seller1", SaleType::FixedPrice, 200).unwrap();
+  list_nft_for(deps.as_mut(), "collection1", "Test.3", "seller2", SaleType::FixedPrice, 300).unwrap();
+  list_nft_for(deps.as_mut(), "collection1", "Test.4", "seller2", SaleType::Auction, 100).unwrap();
+  list_nft_for(deps.as_mut(), "collection1", "Test.5", "bider1", SaleType::FixedPrice, 150).unwrap();
+  list_nft_for(deps.as_mut(), "collection2", "Test.6", "seller1", SaleType::FixedPrice, 100).unwrap();
+
+  let msg = ExecuteMsg::SweepFloor {
+    collection: "collection1".to_string(),
+    max_items: 10,
+    max_total: Uint128::new(1000),
+    max_unit_price: Uint128::new(400),
+  };
+  let info = mock_info("bider1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(900) }]);
+  let err = execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap_err();
+  assert_eq!(err, ContractError::InvalidBatchPayment { expected: Uint128::new(1000), received: Uint128::new(900) });
+
+  // the auction, the sweeper's own ask and the ask above the unit price are left alone
+  env.block.time = env.block.time.plus_seconds(10);
+  let info = mock_info("bider1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(1000) }]);
+  let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+  let event = res.events.iter().find(|e| e.ty == "sweep-floor").unwrap();
+  assert!(event.attributes.iter().any(|a| a.key == "token_ids" && a.value == "Test.2,Test.3"));
+  assert!(event.attributes.iter().any(|a| a.key == "spent" && a.value == "500"));
+  assert_eq!(res.messages.last().unwrap().msg, CosmosMsg::Bank(BankMsg::Send { to_address: "bider1".to_string(), amount: vec![Coin{
+      denom: "uheart".to_string(),
+      amount: Uint128::new(500)
+  }] }));
+  for token_id in ["Test.2", "Test.3"] {
+    assert!(query_ask(deps.as_ref(), Addr::unchecked("collection1"), token_id.to_string()).unwrap().ask.is_none());
+  }
+  for token_id in ["Test.1", "Test.4", "Test.5"] {
+    assert!(query_ask(deps.as_ref(), Addr::unchecked("collection1"), token_id.to_string()).unwrap().ask.is_some());
+  }
+
+  // the budget stops the sweep before the item cap does
+  list_nft_for(deps.as_mut(), "collection1", "Test.7", "seller1", SaleType::FixedPrice, 250).unwrap();
+  list_nft_for(deps.as_mut(), "collection1", "Test.8", "seller1", SaleType::FixedPrice, 260).unwrap();
+  let msg = ExecuteMsg::SweepFloor {
+    collection: "collection1".to_string(),
+    max_items: 10,
+    max_total: Uint128::new(500),
+    max_unit_price: Uint128::new(1000),
+  };
+  let info = mock_info("bider2", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(500) }]);
+  let res = execute(deps.as_mut(), env, info, msg).unwrap();
+  let event = res.events.iter().find(|e| e.ty == "sweep-floor").unwrap();
+  assert!(event.attributes.iter().any(|a| a.key == "token_ids" && a.value == "Test.5,Test.7"));
+  assert!(event.attributes.iter().any(|a| a.key == "refund" && a.value == "100"));
+}
+
+#[test]
+fn test_bundle_ask(){
+  let mut deps = mock_deps();
+  let mut env = mock_env();
+  setup_contract(deps.as_mut());
+
+  let nft_ref = |collection: &str, token_id: &str| NftRef { collection: collection.to_string(), token_id: token_id.to_string() };
+  let share = |collection: &str, percent: u64| BundleShare { collection: collection.to_string(), share: Decimal::percent(percent) };
+  let bundle_msg = |sale_type: SaleType, items: Vec<NftRef>, allocations: Vec<BundleShare>| ExecuteMsg::SetBundleAsk {
+    sale_type,
+    items,
+    allocations,
+    price: Coin{ denom: "uheart".to_string(), amount: Uint128::new(1000) },
+    funds_recipient: None,
+    expires: 300,
+  };
+  let items = vec![nft_ref(ROYALTY_COLLECTION, "Test.1"), nft_ref(ROYALTY_COLLECTION, "Test.2"), nft_ref(CW2981_COLLECTION, "Test.3")];
+
+  let msg = bundle_msg(SaleType::FixedPrice, items.clone(), vec![share(ROYALTY_COLLECTION, 60), share(CW2981_COLLECTION, 30)]);
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::InvalidBundleAllocation {});
+  let msg = bundle_msg(SaleType::FixedPrice, vec![nft_ref("collection1", "Test.1"), nft_ref("collection1", "Other.1")], vec![]);
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedOwner {});
+  let msg = bundle_msg(SaleType::FixedPrice, vec![nft_ref(UNKNOWN_COLLECTION, "Test.1")], vec![]);
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::ContractNotFound {});
+
+  // every NFT is escrowed when the bundle is created
+  let msg = bundle_msg(SaleType::FixedPrice, items, vec![share(ROYALTY_COLLECTION, 60), share(CW2981_COLLECTION, 40)]);
+  let res = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  assert_eq!(res.messages.len(), 3);
+  assert_eq!(query_bundle_asks_by_seller(deps.as_ref(), Addr::unchecked("seller1"), None, None).unwrap().bundles.len(), 1);
+
+  let msg = ExecuteMsg::SetBundleBid { id: 1 };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(900) }]), msg.clone()).unwrap_err();
+  assert_eq!(err, ContractError::InvalidPrice {});
+
+  // 600 pays royalty collection fees and royalties, 400 those of the CW2981 collection
+  let res = execute(deps.as_mut(), env.clone(), mock_info("bider1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(1000) }]), msg).unwrap();
+  let bank_sends: Vec<(String, Uint128)> = res.messages.iter().filter_map(|m| match &m.msg {
+    CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => Some((to_address.clone(), amount[0].amount)),
+    _ => None,
+  }).collect();
+  assert_eq!(bank_sends, vec![
+    ("fee_recipient".to_string(), Uint128::new(1)),
+    ("artist2".to_string(), Uint128::new(19)),
+    ("artist1".to_string(), Uint128::new(41)),
+    ("fee_recipient".to_string(), Uint128::new(1)),
+    ("artist3".to_string(), Uint128::new(28)),
+    ("seller1".to_string(), Uint128::new(910)),
+  ]);
+  assert_eq!(res.messages.len(), 9);
+  assert!(query_bundle_ask(deps.as_ref(), 1).unwrap().bundle.is_none());
+
+  // auctions escrow the highest bid and settle after expiry
+  let msg = bundle_msg(SaleType::Auction, vec![nft_ref("collection1", "Test.4"), nft_ref("collection1", "Test.5")], vec![]);
+  execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  let msg = ExecuteMsg::SetBundleBid { id: 2 };
+  execute(deps.as_mut(), env.clone(), mock_info("bider1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(1000) }]), msg.clone()).unwrap();
+  let res = execute(deps.as_mut(), env.clone(), mock_info("bider2", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(1100) }]), msg).unwrap();
+  assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send { to_address: "bider1".to_string(), amount: vec![Coin{
+      denom: "uheart".to_string(),
+      amount: Uint128::new(1000)
+  }] }));
+  assert_eq!(escrow_summary(deps.as_ref(), &env).unwrap().bundle_escrow, Uint128::new(1100));
+
+  let msg = ExecuteMsg::AcceptBundleBid { id: 2 };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg.clone()).unwrap_err();
+  assert_eq!(err, ContractError::AuctionNotEnded {});
+  env.block.time = env.block.time.plus_seconds(350);
+  let res = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  // fee of 2 to the fee recipient, then the NFTs go to the highest bidder
+  assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send { to_address: "fee_recipient".to_string(), amount: vec![Coin{
+      denom: "uheart".to_string(),
+      amount: Uint128::new(2)
+  }] }));
+  assert_eq!(res.messages[1].msg, CosmosMsg::Bank(BankMsg::Send { to_address: "seller1".to_string(), amount: vec![Coin{
+      denom: "uheart".to_string(),
+      amount: Uint128::new(1098)
+  }] }));
+  assert_eq!(res.messages[3].msg, CosmosMsg::Wasm(WasmMsg::Execute {
+    contract_addr: "collection1".to_string(),
+    msg: to_binary(&Cw721ExecuteMsg::TransferNft { recipient: "bider2".to_string(), token_id: "Test.5".to_string() }).unwrap(),
+    funds: vec![],
+  }));
+  assert_eq!(escrow_summary(deps.as_ref(), &env).unwrap().bundle_escrow, Uint128::zero());
+
+  // removing a bundle returns its NFTs
+  let msg = bundle_msg(SaleType::Auction, vec![nft_ref("collection1", "Test.6")], vec![]);
+  execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  let msg = ExecuteMsg::RemoveBundleAsk { id: 3 };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller2", &[]), msg.clone()).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedOwner {});
+  let res = execute(deps.as_mut(), env, mock_info("seller1", &[]), msg).unwrap();
+  assert_eq!(res.messages.len(), 1);
+}
+
+#[test]
+fn test_swap(){
+  let mut deps = mock_deps();
+  let mut env = mock_env();
+  setup_contract(deps.as_mut());
+
+  let nft_ref = |collection: &str, token_id: &str| NftRef { collection: collection.to_string(), token_id: token_id.to_string() };
+  let transfer = |collection: &str, token_id: &str, recipient: &str| CosmosMsg::Wasm(WasmMsg::Execute {
+    contract_addr: collection.to_string(),
+    msg: to_binary(&Cw721ExecuteMsg::TransferNft { recipient: recipient.to_string(), token_id: token_id.to_string() }).unwrap(),
+    funds: vec![],
+  });
+
+  let msg = ExecuteMsg::CreateSwap {
+    offered: vec![nft_ref("collection1", "Other.1")],
+    requested: vec![nft_ref("collection2", "Test.1")],
+    counterparty: None,
+    expires: 300,
+  };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedOwner {});
+  let msg = ExecuteMsg::CreateSwap {
+    offered: vec![nft_ref("collection1", "Test.1")],
+    requested: vec![nft_ref(UNKNOWN_COLLECTION, "Other.1")],
+    counterparty: None,
+    expires: 300,
+  };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::ContractNotFound {});
+
+  // the expiry must fit the ask expiry range of every collection involved
+  let config = CollectionConfig { ask_expiry: Some(ExpiryRange { min: 100, max: 200 }), ..CollectionConfig::default() };
+  sudo(deps.as_mut(), env.clone(), SudoMsg::SetCollectionConfig { collection: "collection3".to_string(), config }).unwrap();
+  let msg = ExecuteMsg::CreateSwap {
+    offered: vec![nft_ref("collection1", "Test.1")],
+    requested: vec![nft_ref("collection3", "Other.1")],
+    counterparty: None,
+    expires: 300,
+  };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap_err();
+  assert!(matches!(err, ContractError::ExpiryRange(ExpiryRangeError::InvalidExpirationRange {})), "{:?}", err);
+
+  // seller1 offers two NFTs and 100 for two NFTs of seller2
+  let msg = ExecuteMsg::CreateSwap {
+    offered: vec![nft_ref("collection1", "Test.1"), nft_ref("collection1", "Test.2")],
+    requested: vec![nft_ref("collection2", "Other.1"), nft_ref("collection2", "Other.2")],
+    counterparty: Some("seller2".to_string()),
+    expires: 300,
+  };
+  let res = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(100) }]), msg).unwrap();
+  assert_eq!(res.messages[0].msg, transfer("collection1", "Test.1", MOCK_CONTRACT_ADDR));
+  assert_eq!(query_swaps_by_counterparty(deps.as_ref(), Addr::unchecked("seller2"), None, None).unwrap().swaps.len(), 1);
+  assert_eq!(escrow_summary(deps.as_ref(), &env).unwrap().swap_escrow, Uint128::new(100));
+
+  let msg = ExecuteMsg::AcceptSwap { id: 1 };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg.clone()).unwrap_err();
+  assert_eq!(err, ContractError::SwapSelfAccept {});
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedCounterparty {});
+
+  // accepting by sending one requested NFT, the other moves through an approval
+  let receive = |token_id: &str, id: u64| ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+    sender: "seller2".to_string(),
+    token_id: token_id.to_string(),
+    msg: to_binary(&ReceiveNftMsg::V1(ReceiveNftMsgV1::AcceptSwap { id })).unwrap(),
+  });
+  let err = execute(deps.as_mut(), env.clone(), mock_info("collection1", &[]), receive("Other.1", 1)).unwrap_err();
+  assert_eq!(err, ContractError::SwapTokenNotRequested {});
+  let res = execute(deps.as_mut(), env.clone(), mock_info("collection2", &[]), receive("Other.1", 1)).unwrap();
+  let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+  assert_eq!(msgs, vec![
+    transfer("collection2", "Other.1", "seller1"),
+    transfer("collection2", "Other.2", "seller1"),
+    transfer("collection1", "Test.1", "seller2"),
+    transfer("collection1", "Test.2", "seller2"),
+    CosmosMsg::Bank(BankMsg::Send { to_address: "seller2".to_string(), amount: vec![Coin{ denom: "uheart".to_string(), amount: Uint128::new(100) }] }),
+  ]);
+  assert!(query_swap(deps.as_ref(), 1).unwrap().swap.is_none());
+  assert_eq!(escrow_summary(deps.as_ref(), &env).unwrap().swap_escrow, Uint128::zero());
+
+  // an expired swap can be cleaned up by anyone and returns the escrow
+  let msg = ExecuteMsg::CreateSwap {
+    offered: vec![nft_ref("collection1", "Test.3")],
+    requested: vec![nft_ref("collection2", "Other.3")],
+    counterparty: None,
+    expires: 300,
+  };
+  execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  let msg = ExecuteMsg::CancelSwap { id: 2 };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &[]), msg.clone()).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedOwner {});
+  env.block.time = env.block.time.plus_seconds(350);
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller2", &[]), ExecuteMsg::AcceptSwap { id: 2 }).unwrap_err();
+  assert_eq!(err, ContractError::SwapExpired {});
+  let res = execute(deps.as_mut(), env, mock_info("bider1", &[]), msg).unwrap();
+  assert_eq!(res.messages[0].msg, transfer("collection1", "Test.3", "seller1"));
+}
+
+#[test]
+fn test_offers(){
+  let mut deps = mock_deps();
+  let env = mock_env();
+  setup_contract(deps.as_mut());
+  sudo(deps.as_mut(), env.clone(), SudoMsg::AddSaleHookV2 { hook: "hook_v2".to_string() }).unwrap();
+
+  list_nft_for(deps.as_mut(), "collection1", "Test.1", "seller1", SaleType::FixedPrice, 1000).unwrap();
+  list_nft_for(deps.as_mut(), "collection1", "Test.2", "seller1", SaleType::Auction, 1000).unwrap();
+  let funds = |amount: u128| vec![Coin{ denom: "uheart".to_string(), amount: Uint128::new(amount) }];
+  let set_offer = |token_id: &str| ExecuteMsg::SetOffer { collection: "collection1".to_string(), token_id: token_id.to_string(), expires: 200 };
+
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &funds(500)), set_offer("Test.2")).unwrap_err();
+  assert_eq!(err, ContractError::OfferOnAuction {});
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &funds(1000)), set_offer("Test.1")).unwrap_err();
+  assert_eq!(err, ContractError::OfferNotBelowAsk {});
+
+  // replacing an offer refunds the previous one
+  execute(deps.as_mut(), env.clone(), mock_info("bider1", &funds(500)), set_offer("Test.1")).unwrap();
+  let res = execute(deps.as_mut(), env.clone(), mock_info("bider1", &funds(600)), set_offer("Test.1")).unwrap();
+  assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send { to_address: "bider1".to_string(), amount: funds(500) }));
+  execute(deps.as_mut(), env.clone(), mock_info("bider2", &funds(700)), set_offer("Test.1")).unwrap();
+  let offers = query_offers(deps.as_ref(), Addr::unchecked("collection1"), "Test.1".to_string(), None, None).unwrap().offers;
+  assert_eq!(offers.len(), 2);
+  let offers = query_offers(deps.as_ref(), Addr::unchecked("collection1"), "Test.1".to_string(), Some("bider1".to_string()), None).unwrap().offers;
+  assert_eq!(offers[0].buyer, Addr::unchecked("bider2"));
+  assert_eq!(query_offers_by_seller(deps.as_ref(), Addr::unchecked("seller1"), None, None).unwrap().offers.len(), 2);
+
+  // the seller counters bider1 privately
+  let counter = |price: u128| ExecuteMsg::CounterOffer { collection: "collection1".to_string(), token_id: "Test.1".to_string(), buyer: "bider1".to_string(), price: Uint128::new(price) };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider2", &[]), counter(800)).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedOwner {});
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), counter(1000)).unwrap_err();
+  assert_eq!(err, ContractError::InvalidCounterPrice {});
+  sudo(deps.as_mut(), env.clone(), SudoMsg::SetPause { listing: true, bidding: false, settlement: false, removal: false }).unwrap();
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), counter(800)).unwrap_err();
+  assert_eq!(err, ContractError::Paused("listing".to_string()));
+  sudo(deps.as_mut(), env.clone(), SudoMsg::SetPause { listing: false, bidding: false, settlement: false, removal: false }).unwrap();
+  execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), counter(800)).unwrap();
+
+  let accept_counter = ExecuteMsg::AcceptCounterOffer { collection: "collection1".to_string(), token_id: "Test.1".to_string() };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider2", &funds(100)), accept_counter.clone()).unwrap_err();
+  assert_eq!(err, ContractError::NoCounterOffer {});
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &funds(100)), accept_counter.clone()).unwrap_err();
+  assert_eq!(err, ContractError::InvalidPrice {});
+  let res = execute(deps.as_mut(), env.clone(), mock_info("bider1", &funds(200)), accept_counter).unwrap();
+  assert!(res.attributes.iter().any(|a| a.key == "price" && a.value == "800"));
+  assert!(query_ask(deps.as_ref(), Addr::unchecked("collection1"), "Test.1".to_string()).unwrap().ask.is_none());
+  assert!(query_offer(deps.as_ref(), Addr::unchecked("collection1"), "Test.1".to_string(), Addr::unchecked("bider1")).unwrap().offer.is_none());
+
+  // the remaining offer can no longer be accepted but is still withdrawable
+  list_nft_for(deps.as_mut(), "collection1", "Test.1", "seller2", SaleType::FixedPrice, 1000).unwrap();
+  let msg = ExecuteMsg::AcceptOffer { collection: "collection1".to_string(), token_id: "Test.1".to_string(), buyer: "bider2".to_string() };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller2", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::OfferNotFound {});
+  let msg = ExecuteMsg::RemoveOffer { collection: "collection1".to_string(), token_id: "Test.1".to_string() };
+  let res = execute(deps.as_mut(), env.clone(), mock_info("bider2", &[]), msg).unwrap();
+  assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send { to_address: "bider2".to_string(), amount: funds(700) }));
+
+  // a seller accepting an offer sells at the offered price
+  execute(deps.as_mut(), env.clone(), mock_info("bider2", &funds(900)), set_offer("Test.1")).unwrap();
+  let msg = ExecuteMsg::AcceptOffer { collection: "collection1".to_string(), token_id: "Test.1".to_string(), buyer: "bider2".to_string() };
+  let res = execute(deps.as_mut(), env, mock_info("seller2", &[]), msg).unwrap();
+  assert!(res.attributes.iter().any(|a| a.key == "price" && a.value == "900"));
+  assert!(res.attributes.iter().any(|a| a.key == "buyer" && a.value == "bider2"));
+  assert_eq!(v2_sale_origin(&res), SaleOrigin::Offer);
+}
+
+// An ask as stored before `bid_count` and `content_type` were added
+#[derive(Serialize, Deserialize)]
+struct LegacyAsk {
+  sale_type: SaleType,
+  collection: Addr,
+  token_id: String,
+  img_url: String,
+  seller: Addr,
+  price: Uint128,
+  funds_recipient: Option<Addr>,
+  expires_at: cosmwasm_std::Timestamp,
+  max_bid: Option<Uint128>,
+  max_bidder: Option<Addr>,
+}
+
+#[test]
+fn test_signed_orders(){
+  use crate::signed_order::order_digest;
+  use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+  let mut deps = mock_deps();
+  let env = mock_env();
+  setup_contract(deps.as_mut());
+
+  sudo(deps.as_mut(), env.clone(), SudoMsg::AddSaleHookV2 { hook: "hook_v2".to_string() }).unwrap();
+
+  let signing_key = SigningKey::from_bytes(&[7u8; 32]).unwrap();
+  let pubkey = Binary::from(signing_key.verifying_key().to_bytes().as_slice());
+  let funds = vec![Coin{ denom: "uheart".to_string(), amount: Uint128::new(1000) }];
+  let order = |nonce: u64| SignedOrder {
+    seller: "seller1".to_string(),
+    collection: "collection1".to_string(),
+    token_id: "Test.1".to_string(),
+    price: Coin{ denom: "uheart".to_string(), amount: Uint128::new(1000) },
+    expires_at: env.block.time.plus_seconds(100),
+    nonce,
+  };
+  let sign = |order: &SignedOrder| {
+    let sign_doc = OrderSignDoc { chain_id: env.block.chain_id.clone(), marketplace: env.contract.address.to_string(), order: order.clone() };
+    let signature: Signature = signing_key.sign(&to_vec(&sign_doc).unwrap());
+    Binary::from(signature.as_ref())
+  };
+  let fill = |order: SignedOrder, signature: Binary| ExecuteMsg::FillSignedOrder { order, signature };
+
+  // the seller signs the digest the marketplace verifies
+  assert_eq!(order_digest(&env, &order(1)).unwrap(), Sha256::digest(&to_vec(&OrderSignDoc { chain_id: env.block.chain_id.clone(), marketplace: env.contract.address.to_string(), order: order(1) }).unwrap()).to_vec());
+
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &funds), fill(order(1), sign(&order(1)))).unwrap_err();
+  assert_eq!(err, ContractError::NoOrderKey {});
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), ExecuteMsg::SetOrderKey { pubkey: Binary::from(&[1u8; 20]) }).unwrap_err();
+  assert_eq!(err, ContractError::InvalidOrderKey {});
+  execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), ExecuteMsg::SetOrderKey { pubkey: pubkey.clone() }).unwrap();
+  assert_eq!(query_order_key(deps.as_ref(), Addr::unchecked("seller1")).unwrap().pubkey, Some(pubkey));
+
+  // tampering with the order breaks the signature
+  let mut cheaper = order(1);
+  cheaper.price.amount = Uint128::new(500);
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &funds), fill(cheaper, sign(&order(1)))).unwrap_err();
+  assert_eq!(err, ContractError::InvalidOrderSignature {});
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &[]), fill(order(1), sign(&order(1)))).unwrap_err();
+  assert!(matches!(err, ContractError::BidPaymentError(_)));
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &funds), fill(order(1), sign(&order(1)))).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedOwner {});
+  let mut expired_env = env.clone();
+  expired_env.block.time = env.block.time.plus_seconds(100);
+  let err = execute(deps.as_mut(), expired_env, mock_info("bider1", &funds), fill(order(1), sign(&order(1)))).unwrap_err();
+  assert_eq!(err, ContractError::OrderExpired {});
+
+  // a filled order pays out like a sale and cannot be filled again
+  let res = execute(deps.as_mut(), env.clone(), mock_info("bider1", &funds), fill(order(1), sign(&order(1)))).unwrap();
+  assert!(res.messages.iter().any(|m| m.msg == CosmosMsg::Wasm(WasmMsg::Execute {
+    contract_addr: "collection1".to_string(),
+    msg: to_binary(&Cw721ExecuteMsg::TransferNft { recipient: "bider1".to_string(), token_id: "Test.1".to_string() }).unwrap(),
+    funds: vec![],
+  })));
+  assert!(res.attributes.iter().any(|a| a.key == "nonce" && a.value == "1"));
+  assert_eq!(v2_sale_origin(&res), SaleOrigin::SignedOrder);
+  assert_eq!(query_order_nonce(deps.as_ref(), Addr::unchecked("seller1"), 1).unwrap().status, Some(NonceStatus::Used));
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &funds), fill(order(1), sign(&order(1)))).unwrap_err();
+  assert_eq!(err, ContractError::OrderNonceUsed(1));
+
+  // cancelled nonces cannot be filled
+  execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), ExecuteMsg::CancelSignedOrders { nonces: vec![1, 2] }).unwrap();
+  assert_eq!(query_order_nonce(deps.as_ref(), Addr::unchecked("seller1"), 1).unwrap().status, Some(NonceStatus::Used));
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &funds), fill(order(2), sign(&order(2)))).unwrap_err();
+  assert_eq!(err, ContractError::OrderNonceCancelled(2));
+}
+
+#[test]
+fn test_migrate(){
+  let mut deps = mock_deps();
+  let env = mock_env();
+  setup_contract(deps.as_mut());
+
+  set_contract_version(deps.as_mut().storage, "crates.io:human-marketplace", "9.0.0").unwrap();
+  let err = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap_err();
+  assert_eq!(err, ContractError::CannotMigrateDowngrade { from: "9.0.0".to_string(), to: env!("CARGO_PKG_VERSION").to_string() });
+
+  set_contract_version(deps.as_mut().storage, "crates.io:human-marketplace", "1.0.0").unwrap();
+  let legacy_asks: Map<(Addr, String), LegacyAsk> = Map::new("asks");
+  for token_id in ["Test.1", "Test.2", "Test.3"] {
+    legacy_asks.save(deps.as_mut().storage, (Addr::unchecked("collection1"), token_id.to_string()), &LegacyAsk {
+      sale_type: SaleType::Auction,
+      collection: Addr::unchecked("collection1"),
+      token_id: token_id.to_string(),
+      img_url: "".to_string(),
+      seller: Addr::unchecked("seller1"),
+      price: Uint128::new(300),
+      funds_recipient: None,
+      expires_at: env.block.time.plus_seconds(300),
+      max_bid: Some(Uint128::new(10)),
+      max_bidder: Some(env.contract.address.clone()),
+    }).unwrap();
+  }
+  migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+  let status = query_migration_status(deps.as_ref()).unwrap();
+  assert_eq!(status.version.version, env!("CARGO_PKG_VERSION"));
+  assert_eq!(status.records, Some(RecordMigration::Asks { start_after: None }));
+
+  let msg = ExecuteMsg::MigrateRecords { limit: Some(2) };
+  let err = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg.clone()).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedOperator {});
+  sudo(deps.as_mut(), env.clone(), SudoMsg::AddOperator { operator: "operator1".to_string() }).unwrap();
+
+  // two batches of asks, then one for the (empty) bids
+  for _ in 0..3 {
+    execute(deps.as_mut(), env.clone(), mock_info("operator1", &[]), msg.clone()).unwrap();
+  }
+  assert_eq!(query_migration_status(deps.as_ref()).unwrap().records, Some(RecordMigration::Bundles { start_after: None }));
+  // and one each to count the escrow of bundles, offers, swaps and trait bids
+  for _ in 0..4 {
+    execute(deps.as_mut(), env.clone(), mock_info("operator1", &[]), msg.clone()).unwrap();
+  }
+  assert_eq!(query_migration_status(deps.as_ref()).unwrap().records, None);
+  let err = execute(deps.as_mut(), env.clone(), mock_info("operator1", &[]), msg).unwrap_err();
+  assert_eq!(err, ContractError::NoRecordMigration {});
+
+  // rewritten asks are reachable through the rebuilt content type index
+  let migrated = query_asks_by_content_type(deps.as_ref(), "".to_string(), None, None).unwrap().asks;
+  assert_eq!(migrated.len(), 3);
+  assert_eq!(migrated[0].bid_count, Uint128::zero());
+  // and through the trait index, with the traits read from the NFTs
+  let by_trait = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+    query_asks_by_trait(deps.as_ref(), Addr::unchecked("collection1"), "background".to_string(), "blue".to_string(), None, None).unwrap().asks.len()
+  };
+  assert_eq!(by_trait(&deps), 3);
+
+  // asks stored by 1.1.0 were never indexed by trait nor kept custody, only the 1.2.0 step rewrites them
+  let mut deps = mock_deps();
+  setup_contract(deps.as_mut());
+  sudo(deps.as_mut(), env.clone(), SudoMsg::AddOperator { operator: "operator1".to_string() }).unwrap();
+  list_nft(deps.as_mut(), "collection1", "Held.1", "seller1").unwrap();
+  let key = (Addr::unchecked("collection1"), "Held.1".to_string());
+  let mut ask = asks().load(deps.as_ref().storage, key.clone()).unwrap();
+  ask.traits = vec![];
+  asks().save(deps.as_mut().storage, key.clone(), &ask).unwrap();
+  CUSTODY.remove(deps.as_mut().storage, key.clone());
+  assert_eq!(by_trait(&deps), 0);
+
+  set_contract_version(deps.as_mut().storage, "crates.io:human-marketplace", "1.1.0").unwrap();
+  let res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+  let steps: Vec<_> = res.attributes.iter().filter(|a| a.key == "migration_step").map(|a| a.value.as_str()).collect();
+  assert_eq!(steps, vec!["1.2.0"]);
+  execute(deps.as_mut(), env.clone(), mock_info("operator1", &[]), ExecuteMsg::MigrateRecords { limit: None }).unwrap();
+  assert_eq!(by_trait(&deps), 1);
+
+  // the NFT of the migrated ask can be rescued to its seller once the ask is lost
+  let custody = query_custody(deps.as_ref(), Addr::unchecked("collection1"), "Held.1".to_string()).unwrap().custody.unwrap();
+  assert_eq!(custody, Custody { depositor: Addr::unchecked("seller1"), deposited_at: env.block.time });
+  asks().remove(deps.as_mut().storage, key).unwrap();
+  let msg = ExecuteMsg::RescueNft { collection: "collection1".to_string(), token_id: "Held.1".to_string(), recipient: "seller1".to_string() };
+  let res = execute(deps.as_mut(), env, mock_info("operator1", &[]), msg).unwrap();
+  assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute {
+    contract_addr: "collection1".to_string(),
+    msg: to_binary(&Cw721ExecuteMsg::TransferNft { recipient: "seller1".to_string(), token_id: "Held.1".to_string() }).unwrap(),
+    funds: vec![],
+  }));
+}
+
+#[test]
+fn test_asks_by_trait(){
+  let mut deps = mock_deps();
+  setup_contract(deps.as_mut());
+  let env = mock_env();
+
+  for token_id in ["Gold.1", "Gold.2", "Test.1"] {
+    list_nft_for(deps.as_mut(), "collection1", token_id, "seller1", SaleType::FixedPrice, 300).unwrap();
+  }
+  list_nft_for(deps.as_mut(), "collection2", "Gold.3", "seller1", SaleType::FixedPrice, 300).unwrap();
+
+  let collection1 = Addr::unchecked("collection1");
+  let by_trait = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, value: &str, start_after: Option<&str>| {
+    query_asks_by_trait(deps.as_ref(), collection1.clone(), "background".to_string(), value.to_string(), start_after.map(str::to_string), None)
+      .unwrap()
+      .asks
+      .into_iter()
+      .map(|ask| ask.token_id)
+      .collect::<Vec<_>>()
+  };
+
+  // the traits are read from the NFT when it is listed
+  let ask = query_ask(deps.as_ref(), collection1.clone(), "Gold.1".to_string()).unwrap().ask.unwrap();
+  assert_eq!(ask.traits[0].value, "gold");
+  assert_eq!(by_trait(&deps, "gold", None), vec!["Gold.1", "Gold.2"]);
+  assert_eq!(by_trait(&deps, "gold", Some("Gold.1")), vec!["Gold.2"]);
+  assert_eq!(by_trait(&deps, "blue", None), vec!["Test.1"]);
+  assert!(by_trait(&deps, "red", None).is_empty());
+
+  // updating an ask keeps it indexed once, removing it drops it from the index
+  let msg = ExecuteMsg::UpdateAskPrice {
+    collection: "collection1".to_string(),
+    token_id: "Gold.1".to_string(),
+    price: Coin{ denom: "uheart".to_string(), amount: Uint128::new(400) },
+  };
+  execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  assert_eq!(by_trait(&deps, "gold", None), vec!["Gold.1", "Gold.2"]);
+
+  let msg = ExecuteMsg::RemoveAsk { collection: "collection1".to_string(), token_id: "Gold.1".to_string() };
+  execute(deps.as_mut(), env, mock_info("seller1", &[]), msg).unwrap();
+  assert_eq!(by_trait(&deps, "gold", None), vec!["Gold.2"]);
+}
+
+#[test]
+fn test_trait_bids(){
+  let mut deps = mock_deps();
+  let mut env = mock_env();
+  setup_contract(deps.as_mut());
+  sudo(deps.as_mut(), env.clone(), SudoMsg::AddSaleHookV2 { hook: "hook_v2".to_string() }).unwrap();
+
+  let uheart = |amount: u128| Coin{ denom: "uheart".to_string(), amount: Uint128::new(amount) };
+  let set_bid = |value: &str, expires: u64| ExecuteMsg::SetTraitBid {
+    collection: "collection1".to_string(),
+    trait_type: "background".to_string(),
+    value: value.to_string(),
+    expires,
+  };
+
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &[uheart(5)]), set_bid("gold", 300)).unwrap_err();
+  assert_eq!(err, ContractError::PriceTooSmall(Uint128::new(5)));
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &[uheart(100)]), set_bid("gold", 50)).unwrap_err();
+  assert_eq!(err, ContractError::ExpiryRange(ExpiryRangeError::InvalidExpirationRange {}));
+
+  execute(deps.as_mut(), env.clone(), mock_info("bider1", &[uheart(200)]), set_bid("gold", 300)).unwrap();
+  execute(deps.as_mut(), env.clone(), mock_info("bider2", &[uheart(100)]), set_bid("gold", 300)).unwrap();
+  execute(deps.as_mut(), env.clone(), mock_info("bider1", &[uheart(150)]), set_bid("blue", 300)).unwrap();
+
+  let collection1 = Addr::unchecked("collection1");
+  let sorted = query_trait_bids_sorted_by_price(deps.as_ref(), collection1.clone(), "background".to_string(), "gold".to_string(), None, None).unwrap();
+  assert_eq!(sorted.bids.iter().map(|b| b.id).collect::<Vec<_>>(), vec![2, 1]);
+  assert_eq!(query_trait_bids_by_bidder(deps.as_ref(), Addr::unchecked("bider1"), None, None).unwrap().bids.len(), 2);
+
+  // the NFT sent must come from the collection of the bid and have its trait
+  let receive = |token_id: &str, sender: &str, id: u64| ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+    sender: sender.to_string(),
+    token_id: token_id.to_string(),
+    msg: to_binary(&ReceiveNftMsg::V1(ReceiveNftMsgV1::AcceptTraitBid { id })).unwrap(),
+  });
+  let err = execute(deps.as_mut(), env.clone(), mock_info("collection2", &[]), receive("Gold.1", "seller1", 1)).unwrap_err();
+  assert_eq!(err, ContractError::CollectionMismatch {});
+  let err = execute(deps.as_mut(), env.clone(), mock_info("collection1", &[]), receive("Test.1", "seller1", 1)).unwrap_err();
+  assert_eq!(err, ContractError::TraitMismatch {});
+  let err = execute(deps.as_mut(), env.clone(), mock_info("collection1", &[]), receive("Gold.1", "bider1", 1)).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedOwner {});
+
+  let res = execute(deps.as_mut(), env.clone(), mock_info("collection1", &[]), receive("Gold.1", "seller1", 1)).unwrap();
+  assert_eq!(v2_sale_origin(&res), SaleOrigin::TraitBid);
+  let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+  assert!(msgs.contains(&CosmosMsg::Wasm(WasmMsg::Execute {
+    contract_addr: "collection1".to_string(),
+    msg: to_binary(&Cw721ExecuteMsg::TransferNft { recipient: "bider1".to_string(), token_id: "Gold.1".to_string() }).unwrap(),
+    funds: vec![],
+  })));
+  assert!(query_trait_bid(deps.as_ref(), 1).unwrap().bid.is_none());
+  let err = execute(deps.as_mut(), env.clone(), mock_info("collection1", &[]), receive("Gold.2", "seller1", 1)).unwrap_err();
+  assert_eq!(err, ContractError::TraitBidNotFound {});
+
+  // only the bidder removes a live bid, anyone removes an expired one
+  let err = execute(deps.as_mut(), env.clone(), mock_info("bider1", &[]), ExecuteMsg::RemoveTraitBid { id: 2 }).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedOwner {});
+  let res = execute(deps.as_mut(), env.clone(), mock_info("bider2", &[]), ExecuteMsg::RemoveTraitBid { id: 2 }).unwrap();
+  assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send { to_address: "bider2".to_string(), amount: vec![uheart(100)] }));
+
+  env.block.time = env.block.time.plus_seconds(350);
+  let err = execute(deps.as_mut(), env.clone(), mock_info("collection1", &[]), receive("Test.1", "seller1", 3)).unwrap_err();
+  assert_eq!(err, ContractError::TraitBidExpired {});
+  let res = execute(deps.as_mut(), env, mock_info("seller1", &[]), ExecuteMsg::RemoveTraitBid { id: 3 }).unwrap();
+  assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send { to_address: "bider1".to_string(), amount: vec![uheart(150)] }));
+}
+
+#[test]
+fn test_escrow_summary(){
+  let mut deps = mock_deps();
+  let env = mock_env();
+  setup_contract(deps.as_mut());
+  let uheart = |amount: u128| Coin{ denom: "uheart".to_string(), amount: Uint128::new(amount) };
+
+  list_nft(deps.as_mut(), "collection1", "Test.1", "seller1").unwrap();
+  let msg = ExecuteMsg::SetBid { collection: "collection1".to_string(), token_id: "Test.1".to_string() };
+  execute(deps.as_mut(), env.clone(), mock_info("bider1", &[uheart(400)]), msg).unwrap();
+  list_nft_for(deps.as_mut(), "collection1", "Test.2", "seller1", SaleType::FixedPrice, 300).unwrap();
+  let msg = ExecuteMsg::SetOffer { collection: "collection1".to_string(), token_id: "Test.2".to_string(), expires: 300 };
+  execute(deps.as_mut(), env.clone(), mock_info("bider2", &[uheart(200)]), msg).unwrap();
+  let msg = ExecuteMsg::SetTraitBid { collection: "collection1".to_string(), trait_type: "background".to_string(), value: "gold".to_string(), expires: 300 };
+  execute(deps.as_mut(), env.clone(), mock_info("bider2", &[uheart(100)]), msg).unwrap();
+
+  // a balance short of the escrow is reported as a deficit and nothing can be recovered
+  deps.querier.update_balance(MOCK_CONTRACT_ADDR, vec![uheart(650)]);
+  let summary = escrow_summary(deps.as_ref(), &env).unwrap();
+  assert_eq!(summary.auction_escrow, Uint128::new(400));
+  assert_eq!(summary.offer_escrow, Uint128::new(200));
+  assert_eq!(summary.trait_bid_escrow, Uint128::new(100));
+  assert_eq!(summary.liabilities, Uint128::new(700));
+  assert_eq!(summary.surplus, Uint128::zero());
+  assert_eq!(summary.deficit, Uint128::new(50));
+  let msg = SudoMsg::RecoverSurplus { recipient: "owner".to_string(), amount: None };
+  let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+  assert_eq!(err, ContractError::InsufficientSurplus(Uint128::zero()));
+
+  deps.querier.update_balance(MOCK_CONTRACT_ADDR, vec![uheart(730)]);
+  let msg = SudoMsg::RecoverSurplus { recipient: "owner".to_string(), amount: Some(Uint128::new(20)) };
+  let res = sudo(deps.as_mut(), env.clone(), msg).unwrap();
+  assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send { to_address: "owner".to_string(), amount: vec![uheart(20)] }));
+
+  // the totals follow funds as they are released, an outbid bid leaves only the new highest one
+  let msg = ExecuteMsg::SetBid { collection: "collection1".to_string(), token_id: "Test.1".to_string() };
+  execute(deps.as_mut(), env.clone(), mock_info("bider3", &[uheart(500)]), msg).unwrap();
+  let msg = ExecuteMsg::RemoveOffer { collection: "collection1".to_string(), token_id: "Test.2".to_string() };
+  execute(deps.as_mut(), env.clone(), mock_info("bider2", &[]), msg).unwrap();
+  let summary = escrow_summary(deps.as_ref(), &env).unwrap();
+  assert_eq!((summary.auction_escrow, summary.offer_escrow, summary.liabilities), (Uint128::new(500), Uint128::zero(), Uint128::new(600)));
+
+  // a migration counts the escrow of stored records again, nothing is recovered until it is done
+  sudo(deps.as_mut(), env.clone(), SudoMsg::AddOperator { operator: "operator1".to_string() }).unwrap();
+  set_contract_version(deps.as_mut().storage, "crates.io:human-marketplace", "1.1.0").unwrap();
+  migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+  let summary = escrow_summary(deps.as_ref(), &env).unwrap();
+  assert!(summary.counting);
+  assert_eq!(summary.liabilities, Uint128::zero());
+  let msg = SudoMsg::RecoverSurplus { recipient: "owner".to_string(), amount: None };
+  let err = sudo(deps.as_mut(), env.clone(), msg.clone()).unwrap_err();
+  assert_eq!(err, ContractError::EscrowNotCounted {});
+
+  // a bid placed before the count reaches the bids is counted once, by the count
+  let bid = ExecuteMsg::SetBid { collection: "collection1".to_string(), token_id: "Test.1".to_string() };
+  execute(deps.as_mut(), env.clone(), mock_info("bider1", &[uheart(600)]), bid).unwrap();
+  assert_eq!(escrow_summary(deps.as_ref(), &env).unwrap().liabilities, Uint128::zero());
+  let migrate_records = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, until: Option<RecordMigration>| {
+    while query_migration_status(deps.as_ref()).unwrap().records != until {
+      execute(deps.as_mut(), env.clone(), mock_info("operator1", &[]), ExecuteMsg::MigrateRecords { limit: Some(1) }).unwrap();
+    }
+  };
+  migrate_records(&mut deps, Some(RecordMigration::Bundles { start_after: None }));
+  assert_eq!(escrow_summary(deps.as_ref(), &env).unwrap().auction_escrow, Uint128::new(600));
+
+  // once counted, bids are tracked again while the count goes on
+  let bid = ExecuteMsg::SetBid { collection: "collection1".to_string(), token_id: "Test.1".to_string() };
+  execute(deps.as_mut(), env.clone(), mock_info("bider3", &[uheart(650)]), bid).unwrap();
+  migrate_records(&mut deps, None);
+  let summary = escrow_summary(deps.as_ref(), &env).unwrap();
+  assert!(!summary.counting);
+  assert_eq!((summary.auction_escrow, summary.trait_bid_escrow, summary.liabilities), (Uint128::new(650), Uint128::new(100), Uint128::new(750)));
+  deps.querier.update_balance(MOCK_CONTRACT_ADDR, vec![uheart(760)]);
+  let res = sudo(deps.as_mut(), env, msg).unwrap();
+  assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send { to_address: "owner".to_string(), amount: vec![uheart(10)] }));
+}
+
+#[test]
+fn test_rescue_nft(){
+  let mut deps = mock_deps();
+  let env = mock_env();
+  setup_contract(deps.as_mut());
+  sudo(deps.as_mut(), env.clone(), SudoMsg::AddOperator { operator: "operator1".to_string() }).unwrap();
+
+  let collection1 = Addr::unchecked("collection1");
+  let rescue = |token_id: &str, recipient: &str| ExecuteMsg::RescueNft {
+    collection: "collection1".to_string(),
+    token_id: token_id.to_string(),
+    recipient: recipient.to_string(),
+  };
+  let lose_ask = |deps: DepsMut, token_id: &str| asks().remove(deps.storage, ask_key(&collection1, &token_id.to_string())).unwrap();
+
+  // listing records who deposited the NFT, the rescue only works once the ask is gone
+  list_nft(deps.as_mut(), "collection1", "Held.1", "seller1").unwrap();
+  let custody = query_custody(deps.as_ref(), collection1.clone(), "Held.1".to_string()).unwrap().custody.unwrap();
+  assert_eq!(custody.depositor, Addr::unchecked("seller1"));
+  let err = execute(deps.as_mut(), env.clone(), mock_info("operator1", &[]), rescue("Held.1", "seller1")).unwrap_err();
+  assert_eq!(err, ContractError::AskStillListed {});
+
+  lose_ask(deps.as_mut(), "Held.1");
+  let err = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), rescue("Held.1", "seller1")).unwrap_err();
+  assert_eq!(err, ContractError::UnauthorizedOperator {});
+  let err = execute(deps.as_mut(), env.clone(), mock_info("operator1", &[]), rescue("Held.1", "operator1")).unwrap_err();
+  assert_eq!(err, ContractError::RescueRecipientNotDepositor {});
+  let res = execute(deps.as_mut(), env.clone(), mock_info("operator1", &[]), rescue("Held.1", "seller1")).unwrap();
+  assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute {
+    contract_addr: "collection1".to_string(),
+    msg: to_binary(&Cw721ExecuteMsg::TransferNft { recipient: "seller1".to_string(), token_id: "Held.1".to_string() }).unwrap(),
+    funds: vec![],
+  }));
+  assert!(query_custody(deps.as_ref(), collection1.clone(), "Held.1".to_string()).unwrap().custody.is_none());
+
+  // the NFT must still be held by the marketplace
+  list_nft(deps.as_mut(), "collection1", "Test.1", "seller1").unwrap();
+  lose_ask(deps.as_mut(), "Test.1");
+  let err = execute(deps.as_mut(), env.clone(), mock_info("operator1", &[]), rescue("Test.1", "seller1")).unwrap_err();
+  assert_eq!(err, ContractError::NftNotInCustody {});
+
+  // an NFT that left through its ask has no custody left to rescue
+  list_nft(deps.as_mut(), "collection1", "Held.2", "seller1").unwrap();
+  let msg = ExecuteMsg::RemoveAsk { collection: "collection1".to_string(), token_id: "Held.2".to_string() };
+  execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  let msg = SudoMsg::RescueNft { collection: "collection1".to_string(), token_id: "Held.2".to_string(), recipient: "owner".to_string() };
+  let err = sudo(deps.as_mut(), env.clone(), msg).unwrap_err();
+  assert_eq!(err, ContractError::NoCustodyRecord {});
+
+  // governance may pick any recipient
+  list_nft(deps.as_mut(), "collection1", "Held.3", "seller1").unwrap();
+  lose_ask(deps.as_mut(), "Held.3");
+  let msg = SudoMsg::RescueNft { collection: "collection1".to_string(), token_id: "Held.3".to_string(), recipient: "owner".to_string() };
+  let res = sudo(deps.as_mut(), env, msg).unwrap();
+  assert_eq!(res.attributes.iter().find(|a| a.key == "recipient").unwrap().value, "owner");
+}
+
+#[test]
+fn test_sale_hooks(){
+  let mut deps = mock_deps();
+  let mut env = mock_env();
+  setup_contract(deps.as_mut());
+  sudo(deps.as_mut(), env.clone(), SudoMsg::AddSaleHookV2 { hook: "hook_v2".to_string() }).unwrap();
+
+  // an auction listed at 300 that sells for the winning bid of 1000
+  list_nft(deps.as_mut(), ROYALTY_COLLECTION, "Test.1", "seller1").unwrap();
+  let info = mock_info("bidder1", &[Coin{ denom: "uheart".to_string(), amount: Uint128::new(1000) }]);
+  let msg = ExecuteMsg::SetBid { collection: ROYALTY_COLLECTION.to_string(), token_id: "Test.1".to_string() };
+  execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+  env.block.time = env.block.time.plus_seconds(350);
+  let msg = ExecuteMsg::AcceptBid { collection: ROYALTY_COLLECTION.to_string(), token_id: "Test.1".to_string() };
+  let res = execute(deps.as_mut(), env.clone(), mock_info("seller1", &[]), msg).unwrap();
+  let hook_msg = |hook: &str| res.messages.iter().find_map(|m| match &m.msg {
+    CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) if contract_addr == hook => Some(from_binary::<SaleExecuteMsg>(msg).unwrap()),
+    _ => None,
+  }).unwrap();
+
+  assert_eq!(hook_msg("hook"), SaleExecuteMsg::SaleHook(SaleHookMsg {
+    collection: ROYALTY_COLLECTION.to_string(),
+    token_id: "Test.1".to_string(),
+    price: Coin { denom: "uheart".to_string(), amount: Uint128::new(1000) },
+    seller: "seller1".to_string(),
+    buyer: "bidder1".to_string(),
+  }));
+  assert_eq!(hook_msg("hook_v2"), SaleExecuteMsg::SaleHookV2(SaleHookMsgV2 {
+    collection: ROYALTY_COLLECTION.to_string(),
+    token_id: "Test.1".to_string(),
+    origin: SaleOrigin::Auction,
+    price: Coin { denom: "uheart".to_string(), amount: Uint128::new(1000) },
+    seller: "seller1".to_string(),
+    buyer: "bidder1".to_string(),
+    royalties: vec![
+      RoyaltyPayout { recipient: Addr::unchecked("artist2"), amount: Uint128::new(33) },
+      RoyaltyPayout { recipient: Addr::unchecked("artist1"), amount: Uint128::new(67) },
+    ],
+    protocol_fee: Uint128::new(2),
+    finders_fee: Uint128::zero(),
+    seller_amount: Uint128::new(898),
+    timestamp: env.block.time,
+  }));
+
+  // v2 consumers can opt out again
+  sudo(deps.as_mut(), env, SudoMsg::RemoveSaleHookV2 { hook: "hook_v2".to_string() }).unwrap();
+  assert!(SALE_HOOKS_V2.query_hooks(deps.as_ref()).unwrap().hooks.is_empty());
+}
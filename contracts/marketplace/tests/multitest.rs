@@ -0,0 +1,572 @@
+//! End to end tests of the marketplace against the cw721-base collection of this repo and
+//! the mock collection factory, with real NFT custody and bank balances.
+
+use cosmwasm_std::{
+    coins, to_binary, Addr, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Response, StdResult, Timestamp, Uint128,
+};
+use cw721::OwnerOfResponse;
+use cw721_base::msg::CollectionInfo;
+use cw721_base::{
+    ContractError as Cw721ContractError, Cw721Contract, ExecuteMsg as Cw721ExecuteMsg,
+    InstantiateMsg as Cw721InstantiateMsg, Metadata, MintMsg, QueryMsg as Cw721QueryMsg, Royalty,
+    Trait,
+};
+use cw_multi_test::{App, AppBuilder, AppResponse, Contract, ContractWrapper, Executor};
+use human_marketplace::msg::{
+    AskInfo, AskResponse, BidResponse, EscrowSummaryResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+    SudoMsg,
+};
+use human_marketplace::state::SaleType;
+use human_marketplace::{ContractError, ExpiryRange};
+use mock_collection_factory::msg::{
+    ExecuteMsg as FactoryExecuteMsg, InstantiateMsg as FactoryInstantiateMsg,
+};
+
+const DENOM: &str = "uheart";
+const ADMIN: &str = "admin";
+const MINTER: &str = "minter";
+const ARTIST: &str = "artist";
+const FEE_RECIPIENT: &str = "fee_recipient";
+const SELLER: &str = "seller";
+const BIDDER1: &str = "bidder1";
+const BIDDER2: &str = "bidder2";
+const INITIAL_BALANCE: u128 = 10_000;
+const ASK_EXPIRY: u64 = 300;
+
+/// Contracts of one test, `collection` is registered with the factory and pays `ARTIST`
+/// a 10% royalty
+struct Suite {
+    app: App,
+    marketplace: Addr,
+    factory: Addr,
+    collection: Addr,
+}
+
+fn marketplace_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        human_marketplace::execute::execute,
+        human_marketplace::execute::instantiate,
+        human_marketplace::query::query,
+    )
+    .with_sudo(human_marketplace::sudo::sudo)
+    .with_reply(human_marketplace::execute::reply);
+    Box::new(contract)
+}
+
+fn factory_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        mock_collection_factory::contract::execute,
+        mock_collection_factory::contract::instantiate,
+        mock_collection_factory::contract::query,
+    );
+    Box::new(contract)
+}
+
+// cw721-base is a library dependency, so its entry points are wrapped here
+fn cw721_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721ExecuteMsg<Metadata>,
+) -> Result<Response, Cw721ContractError> {
+    Cw721Contract::<Metadata, Empty>::default().execute(deps, env, info, msg)
+}
+
+fn cw721_instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw721InstantiateMsg,
+) -> Result<Response, Cw721ContractError> {
+    Cw721Contract::<Metadata, Empty>::default().instantiate(deps, env, info, msg)
+}
+
+fn cw721_query(deps: Deps, env: Env, msg: Cw721QueryMsg) -> StdResult<Binary> {
+    Cw721Contract::<Metadata, Empty>::default().query(deps, env, msg)
+}
+
+fn cw721_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(cw721_execute, cw721_instantiate, cw721_query))
+}
+
+impl Suite {
+    fn new() -> Self {
+        let mut app = AppBuilder::new().build(|router, _, storage| {
+            for addr in [SELLER, BIDDER1, BIDDER2] {
+                router
+                    .bank
+                    .init_balance(storage, &Addr::unchecked(addr), coins(INITIAL_BALANCE, DENOM))
+                    .unwrap();
+            }
+        });
+
+        let factory_id = app.store_code(factory_contract());
+        let factory = app
+            .instantiate_contract(
+                factory_id,
+                Addr::unchecked(ADMIN),
+                &FactoryInstantiateMsg {},
+                &[],
+                "factory",
+                None,
+            )
+            .unwrap();
+
+        let marketplace_id = app.store_code(marketplace_contract());
+        let marketplace = app
+            .instantiate_contract(
+                marketplace_id,
+                Addr::unchecked(ADMIN),
+                &InstantiateMsg {
+                    trading_fee_bps: 250,
+                    fee_recipient: Some(FEE_RECIPIENT.to_string()),
+                    ask_expiry: ExpiryRange::new(100, 500),
+                    bid_expiry: ExpiryRange::new(100, 500),
+                    operators: vec![],
+                    sale_hook: None,
+                    min_price: Uint128::new(10),
+                    listing_fee: Uint128::zero(),
+                    create_collection_address: factory.to_string(),
+                },
+                &[],
+                "marketplace",
+                None,
+            )
+            .unwrap();
+
+        let collection_id = app.store_code(cw721_contract());
+        let collection = app
+            .instantiate_contract(
+                collection_id,
+                Addr::unchecked(ADMIN),
+                &Cw721InstantiateMsg {
+                    name: "Humans".to_string(),
+                    symbol: "HMN".to_string(),
+                    minter: MINTER.to_string(),
+                    admin: ADMIN.to_string(),
+                    collection_info: collection_info(),
+                    royalty_info: Some(Royalty {
+                        address: ARTIST.to_string(),
+                        royalty_rate: Decimal::percent(10),
+                        shares: None,
+                    }),
+                    mint_info: None,
+                },
+                &[],
+                "collection",
+                None,
+            )
+            .unwrap();
+
+        let mut suite = Suite {
+            app,
+            marketplace,
+            factory,
+            collection,
+        };
+        suite.register(&suite.collection.clone());
+        suite
+    }
+
+    fn register(&mut self, collection: &Addr) {
+        let msg = FactoryExecuteMsg::AddCollection {
+            address: collection.to_string(),
+        };
+        self.app
+            .execute_contract(Addr::unchecked(ADMIN), self.factory.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    /// Mints `token_id` to `SELLER`, tokens whose id contains "Gold" get a gold background
+    fn mint(&mut self, token_id: &str) {
+        let background = if token_id.contains("Gold") { "gold" } else { "blue" };
+        let msg = Cw721ExecuteMsg::Mint(MintMsg {
+            token_id: token_id.to_string(),
+            owner: SELLER.to_string(),
+            token_uri: None,
+            content_type: "image/png".to_string(),
+            extension: Metadata {
+                image_url: format!("ipfs://humans/{}.png", token_id),
+                minter: MINTER.to_string(),
+                attributes: vec![Trait {
+                    display_type: None,
+                    trait_type: "background".to_string(),
+                    value: background.to_string(),
+                }],
+                ..Metadata::default()
+            },
+        });
+        self.app
+            .execute_contract(Addr::unchecked(MINTER), self.collection.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    /// Lists `token_id` of `SELLER` by sending it to the marketplace
+    fn list(&mut self, token_id: &str, sale_type: SaleType, price: u128) -> anyhow::Result<AppResponse> {
+        let ask_info = AskInfo {
+            sale_type,
+            collection: self.collection.clone(),
+            token_id: token_id.to_string(),
+            price: Coin::new(price, DENOM),
+            funds_recipient: None,
+            expires: ASK_EXPIRY,
+        };
+        let msg = Cw721ExecuteMsg::<Metadata>::SendNft {
+            contract: self.marketplace.to_string(),
+            token_id: token_id.to_string(),
+            msg: to_binary(&ask_info).unwrap(),
+        };
+        self.app
+            .execute_contract(Addr::unchecked(SELLER), self.collection.clone(), &msg, &[])
+    }
+
+    fn bid(&mut self, bidder: &str, token_id: &str, price: u128) -> anyhow::Result<AppResponse> {
+        let msg = ExecuteMsg::SetBid {
+            collection: self.collection.to_string(),
+            token_id: token_id.to_string(),
+        };
+        self.app.execute_contract(
+            Addr::unchecked(bidder),
+            self.marketplace.clone(),
+            &msg,
+            &coins(price, DENOM),
+        )
+    }
+
+    fn execute(&mut self, sender: &str, msg: ExecuteMsg) -> anyhow::Result<AppResponse> {
+        self.app
+            .execute_contract(Addr::unchecked(sender), self.marketplace.clone(), &msg, &[])
+    }
+
+    fn sudo(&mut self, msg: SudoMsg) -> anyhow::Result<AppResponse> {
+        self.app.wasm_sudo(self.marketplace.clone(), &msg)
+    }
+
+    fn end_auctions(&mut self) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(ASK_EXPIRY + 1);
+            block.height += 1;
+        });
+    }
+
+    fn owner_of(&self, token_id: &str) -> String {
+        let res: OwnerOfResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                &self.collection,
+                &Cw721QueryMsg::OwnerOf {
+                    token_id: token_id.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        res.owner
+    }
+
+    fn balance(&self, addr: impl Into<String>) -> u128 {
+        self.app.wrap().query_balance(addr, DENOM).unwrap().amount.u128()
+    }
+
+    fn ask(&self, token_id: &str) -> AskResponse {
+        let msg = QueryMsg::Ask {
+            collection: self.collection.to_string(),
+            token_id: token_id.to_string(),
+        };
+        self.app.wrap().query_wasm_smart(&self.marketplace, &msg).unwrap()
+    }
+
+    fn escrow_summary(&self) -> EscrowSummaryResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.marketplace, &QueryMsg::EscrowSummary {})
+            .unwrap()
+    }
+
+    fn bid_of(&self, bidder: &str, token_id: &str) -> BidResponse {
+        let msg = QueryMsg::Bid {
+            collection: self.collection.to_string(),
+            token_id: token_id.to_string(),
+            bidder: bidder.to_string(),
+        };
+        self.app.wrap().query_wasm_smart(&self.marketplace, &msg).unwrap()
+    }
+}
+
+fn collection_info() -> CollectionInfo {
+    CollectionInfo {
+        title: Some("Humans".to_string()),
+        creator: Some(ADMIN.to_string()),
+        image_url: None,
+        background_url: None,
+        logo_url: None,
+        collection_id: None,
+        metadata_url: None,
+        social_links: None,
+        description: None,
+        is_launch: None,
+    }
+}
+
+fn contract_error(err: anyhow::Error) -> ContractError {
+    err.downcast().unwrap()
+}
+
+#[test]
+fn listing_takes_custody_of_the_nft() {
+    let mut suite = Suite::new();
+    suite.mint("Gold.1");
+
+    suite.list("Gold.1", SaleType::FixedPrice, 1_000).unwrap();
+    assert_eq!(suite.owner_of("Gold.1"), suite.marketplace.to_string());
+
+    // the ask is built from the metadata the collection reports
+    let ask = suite.ask("Gold.1").ask.unwrap();
+    assert_eq!(ask.seller, Addr::unchecked(SELLER));
+    assert_eq!(ask.price, Uint128::new(1_000));
+    assert_eq!(ask.img_url, "ipfs://humans/Gold.1.png");
+    assert_eq!(ask.content_type, "image/png");
+    assert_eq!(ask.traits[0].value, "gold");
+    assert_eq!(
+        ask.expires_at,
+        suite.app.block_info().time.plus_seconds(ASK_EXPIRY)
+    );
+}
+
+#[test]
+fn batch_listing_pulls_the_nfts_through_an_approval() {
+    let mut suite = Suite::new();
+    suite.mint("Test.1");
+    suite.mint("Test.2");
+    suite.mint("Test.3");
+    let approve = Cw721ExecuteMsg::<Metadata>::ApproveAll {
+        operator: suite.marketplace.to_string(),
+        expires: None,
+    };
+    let collection = suite.collection.clone();
+    suite
+        .app
+        .execute_contract(Addr::unchecked(SELLER), collection, &approve, &[])
+        .unwrap();
+
+    let ask_info = |token_id: &str, sale_type: SaleType| AskInfo {
+        sale_type,
+        collection: suite.collection.clone(),
+        token_id: token_id.to_string(),
+        price: Coin::new(1_000, DENOM),
+        funds_recipient: None,
+        expires: ASK_EXPIRY,
+    };
+
+    // an NFT of someone else fails the whole batch
+    let asks = vec![ask_info("Test.1", SaleType::FixedPrice), ask_info("Test.2", SaleType::Auction)];
+    let err = suite
+        .execute(BIDDER1, ExecuteMsg::BatchSetAsk { asks: asks.clone() })
+        .unwrap_err();
+    assert_eq!(contract_error(err), ContractError::UnauthorizedOwner {});
+    assert!(suite.ask("Test.1").ask.is_none());
+
+    suite.execute(SELLER, ExecuteMsg::BatchSetAsk { asks }).unwrap();
+    assert_eq!(suite.owner_of("Test.1"), suite.marketplace.to_string());
+    assert_eq!(suite.owner_of("Test.2"), suite.marketplace.to_string());
+    assert_eq!(suite.owner_of("Test.3"), SELLER);
+    assert_eq!(suite.ask("Test.2").ask.unwrap().sale_type, SaleType::Auction);
+
+    // listed NFTs sell like any other ask
+    suite.bid(BIDDER1, "Test.1", 1_000).unwrap();
+    assert_eq!(suite.owner_of("Test.1"), BIDDER1);
+}
+
+#[test]
+fn unregistered_collection_cannot_list() {
+    let mut suite = Suite::new();
+    let collection_id = suite.app.store_code(cw721_contract());
+    let unregistered = suite
+        .app
+        .instantiate_contract(
+            collection_id,
+            Addr::unchecked(ADMIN),
+            &Cw721InstantiateMsg {
+                name: "Fakes".to_string(),
+                symbol: "FAKE".to_string(),
+                minter: MINTER.to_string(),
+                admin: ADMIN.to_string(),
+                collection_info: collection_info(),
+                royalty_info: None,
+                mint_info: None,
+            },
+            &[],
+            "unregistered",
+            None,
+        )
+        .unwrap();
+    suite.collection = unregistered.clone();
+    suite.mint("Test.1");
+
+    let err = suite.list("Test.1", SaleType::FixedPrice, 1_000).unwrap_err();
+    assert_eq!(contract_error(err), ContractError::ContractNotFound {});
+    assert_eq!(suite.owner_of("Test.1"), SELLER);
+
+    // once the factory knows the collection its NFTs can be listed
+    suite.register(&unregistered);
+    suite.list("Test.1", SaleType::FixedPrice, 1_000).unwrap();
+    assert_eq!(suite.owner_of("Test.1"), suite.marketplace.to_string());
+}
+
+#[test]
+fn fixed_price_buy_pays_fee_royalty_and_seller() {
+    let mut suite = Suite::new();
+    suite.mint("Test.1");
+    suite.list("Test.1", SaleType::FixedPrice, 1_000).unwrap();
+
+    suite.bid(BIDDER1, "Test.1", 1_000).unwrap();
+
+    // 2.5% protocol fee goes to the fee recipient, 10% royalty to the artist
+    assert_eq!(suite.owner_of("Test.1"), BIDDER1);
+    assert!(suite.ask("Test.1").ask.is_none());
+    assert_eq!(suite.balance(BIDDER1), INITIAL_BALANCE - 1_000);
+    assert_eq!(suite.balance(ARTIST), 100);
+    assert_eq!(suite.balance(SELLER), INITIAL_BALANCE + 875);
+    assert_eq!(suite.balance(FEE_RECIPIENT), 25);
+    assert_eq!(suite.balance(suite.marketplace.clone()), 0);
+}
+
+#[test]
+fn auction_settles_to_the_highest_bid() {
+    let mut suite = Suite::new();
+    suite.mint("Test.1");
+    suite.list("Test.1", SaleType::Auction, 500).unwrap();
+
+    // an outbid bidder is refunded at once
+    suite.bid(BIDDER1, "Test.1", 600).unwrap();
+    suite.bid(BIDDER2, "Test.1", 800).unwrap();
+    assert_eq!(suite.balance(BIDDER1), INITIAL_BALANCE);
+    assert_eq!(suite.balance(BIDDER2), INITIAL_BALANCE - 800);
+    assert_eq!(suite.balance(suite.marketplace.clone()), 800);
+    assert!(!suite.bid_of(BIDDER1, "Test.1").bid.unwrap().active);
+
+    let accept_msg = ExecuteMsg::AcceptBid {
+        collection: suite.collection.to_string(),
+        token_id: "Test.1".to_string(),
+    };
+    let err = suite.execute(SELLER, accept_msg.clone()).unwrap_err();
+    assert_eq!(contract_error(err), ContractError::AuctionNotEnded {});
+
+    suite.end_auctions();
+    suite.execute(SELLER, accept_msg).unwrap();
+
+    assert_eq!(suite.owner_of("Test.1"), BIDDER2);
+    assert_eq!(suite.balance(ARTIST), 80);
+    assert_eq!(suite.balance(SELLER), INITIAL_BALANCE + 700);
+    assert_eq!(suite.balance(FEE_RECIPIENT), 20);
+    assert_eq!(suite.balance(suite.marketplace.clone()), 0);
+    assert!(suite.bid_of(BIDDER2, "Test.1").bid.is_none());
+}
+
+#[test]
+fn unsold_auction_returns_the_nft() {
+    let mut suite = Suite::new();
+    suite.mint("Test.1");
+    suite.list("Test.1", SaleType::Auction, 500).unwrap();
+
+    suite.end_auctions();
+    let msg = ExecuteMsg::AcceptBid {
+        collection: suite.collection.to_string(),
+        token_id: "Test.1".to_string(),
+    };
+    suite.execute(SELLER, msg).unwrap();
+
+    assert_eq!(suite.owner_of("Test.1"), SELLER);
+    assert_eq!(suite.balance(SELLER), INITIAL_BALANCE);
+    assert!(suite.ask("Test.1").ask.is_none());
+}
+
+#[test]
+fn removing_an_ask_returns_the_nft_and_refunds_the_bid() {
+    let mut suite = Suite::new();
+    suite.mint("Test.1");
+    suite.list("Test.1", SaleType::Auction, 500).unwrap();
+    suite.bid(BIDDER1, "Test.1", 600).unwrap();
+
+    let msg = ExecuteMsg::RemoveAsk {
+        collection: suite.collection.to_string(),
+        token_id: "Test.1".to_string(),
+    };
+    let err = suite.execute(BIDDER1, msg.clone()).unwrap_err();
+    assert_eq!(contract_error(err), ContractError::UnauthorizedOwner {});
+    suite.execute(SELLER, msg).unwrap();
+
+    assert_eq!(suite.owner_of("Test.1"), SELLER);
+    assert_eq!(suite.balance(BIDDER1), INITIAL_BALANCE);
+    assert_eq!(suite.balance(suite.marketplace.clone()), 0);
+    assert!(suite.ask("Test.1").ask.is_none());
+    assert!(suite.bid_of(BIDDER1, "Test.1").bid.is_none());
+}
+
+#[test]
+fn expired_ask_cannot_be_bought() {
+    let mut suite = Suite::new();
+    suite.mint("Test.1");
+    suite.list("Test.1", SaleType::FixedPrice, 1_000).unwrap();
+    suite.app.update_block(|block| {
+        block.time = Timestamp::from_seconds(block.time.seconds() + ASK_EXPIRY + 1);
+    });
+
+    let err = suite.bid(BIDDER1, "Test.1", 1_000).unwrap_err();
+    assert_eq!(contract_error(err), ContractError::AskExpired {});
+    assert_eq!(suite.balance(BIDDER1), INITIAL_BALANCE);
+    assert_eq!(suite.owner_of("Test.1"), suite.marketplace.to_string());
+}
+
+#[test]
+fn only_the_surplus_above_escrow_is_recoverable() {
+    let mut suite = Suite::new();
+    suite.mint("Test.1");
+    suite.mint("Test.2");
+    suite.list("Test.1", SaleType::Auction, 500).unwrap();
+    suite.bid(BIDDER1, "Test.1", 600).unwrap();
+    suite.bid(BIDDER2, "Test.1", 700).unwrap();
+
+    // the outbid bid was refunded, only the highest one is owed
+    let summary = suite.escrow_summary();
+    assert_eq!(summary.balance, Uint128::new(700));
+    assert_eq!(summary.auction_escrow, Uint128::new(700));
+    assert_eq!(summary.liabilities, Uint128::new(700));
+    assert_eq!(summary.surplus, Uint128::zero());
+
+    // funds sent outside of any order are surplus, a sale leaves nothing behind
+    suite.list("Test.2", SaleType::FixedPrice, 1_000).unwrap();
+    suite.bid(BIDDER1, "Test.2", 1_000).unwrap();
+    let marketplace = suite.marketplace.clone();
+    suite
+        .app
+        .send_tokens(Addr::unchecked(BIDDER1), marketplace, &coins(25, DENOM))
+        .unwrap();
+    let summary = suite.escrow_summary();
+    assert_eq!(summary.balance, Uint128::new(725));
+    assert_eq!(summary.surplus, Uint128::new(25));
+    assert_eq!(summary.deficit, Uint128::zero());
+
+    let recover = |amount: Option<u128>| SudoMsg::RecoverSurplus {
+        recipient: ADMIN.to_string(),
+        amount: amount.map(Uint128::new),
+    };
+    let err = suite.sudo(recover(Some(26))).unwrap_err();
+    assert_eq!(contract_error(err), ContractError::InsufficientSurplus(Uint128::new(25)));
+    suite.sudo(recover(None)).unwrap();
+    assert_eq!(suite.balance(ADMIN), 25);
+    assert_eq!(suite.balance(suite.marketplace.clone()), 700);
+    let err = suite.sudo(recover(None)).unwrap_err();
+    assert_eq!(contract_error(err), ContractError::InsufficientSurplus(Uint128::zero()));
+
+    // the escrow still settles the auction
+    suite.end_auctions();
+    let msg = ExecuteMsg::AcceptBid {
+        collection: suite.collection.to_string(),
+        token_id: "Test.1".to_string(),
+    };
+    suite.execute(SELLER, msg).unwrap();
+    assert_eq!(suite.owner_of("Test.1"), BIDDER2);
+    assert_eq!(suite.escrow_summary().liabilities, Uint128::zero());
+}
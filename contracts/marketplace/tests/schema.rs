@@ -0,0 +1,143 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{create_dir_all, read_dir, read_to_string, remove_dir_all};
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+#[allow(dead_code)]
+#[path = "../examples/schema.rs"]
+mod schema;
+
+fn read_schemas(dir: &Path) -> BTreeMap<String, String> {
+    read_files(dir, ".json")
+}
+
+fn read_files(dir: &Path, suffix: &str) -> BTreeMap<String, String> {
+    read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_string_lossy().ends_with(suffix))
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            (name.trim_end_matches(suffix).to_string(), read_to_string(&path).unwrap())
+        })
+        .collect()
+}
+
+/// Collects the type names, property keys and string enum values a schema declares
+fn collect_names(schema: &Value, types: &mut BTreeSet<String>, keys: &mut BTreeSet<String>, values: &mut BTreeSet<String>) {
+    match schema {
+        Value::Object(map) => {
+            if let Some(Value::String(title)) = map.get("title") {
+                types.insert(title.clone());
+            }
+            if let Some(Value::Object(definitions)) = map.get("definitions") {
+                types.extend(definitions.keys().cloned());
+            }
+            if let Some(Value::Object(properties)) = map.get("properties") {
+                keys.extend(properties.keys().cloned());
+            }
+            if let Some(Value::Array(variants)) = map.get("enum") {
+                values.extend(variants.iter().filter_map(|v| v.as_str().map(str::to_string)));
+            }
+            for value in map.values() {
+                collect_names(value, types, keys, values);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_names(item, types, keys, values);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Fails when the checked-in schema is stale, regenerate it with `cargo schema`
+#[test]
+fn schema_is_up_to_date() {
+    let out_dir = std::env::temp_dir().join(format!("marketplace-schema-{}", std::process::id()));
+    create_dir_all(&out_dir).unwrap();
+    schema::export_schemas(&out_dir);
+    let generated = read_schemas(&out_dir);
+    remove_dir_all(&out_dir).unwrap();
+
+    let checked_in = read_schemas(&PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("schema"));
+
+    assert_eq!(
+        checked_in.keys().collect::<Vec<_>>(),
+        generated.keys().collect::<Vec<_>>(),
+        "schema files differ, run `cargo schema` in contracts/marketplace"
+    );
+    for (name, schema) in &generated {
+        assert!(
+            &checked_in[name] == schema,
+            "{}.json is out of date, run `cargo schema` in contracts/marketplace",
+            name
+        );
+    }
+}
+
+/// Fails when the TypeScript types lag behind the schema, regenerate them with `yarn codegen`
+/// in types/. Checks that every schema has its declaration file and barrel export, and that
+/// every type, field, variant and enum value of the schema is declared.
+#[test]
+fn types_are_up_to_date() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let schemas = read_schemas(&manifest_dir.join("schema"));
+    let types_dir = manifest_dir.join("../../types/contracts/marketplace");
+    let mut declarations = read_files(&types_dir, ".d.ts");
+    let shared = declarations.remove("shared-types").unwrap();
+    let index = read_to_string(types_dir.join("index.ts")).unwrap();
+
+    assert_eq!(
+        declarations.keys().collect::<Vec<_>>(),
+        schemas.keys().collect::<Vec<_>>(),
+        "types differ from the schema files, run `yarn codegen` in types/"
+    );
+
+    for (name, schema) in &schemas {
+        let ts = &declarations[name];
+        // dedup empties files whose declarations all moved to shared-types
+        let export = format!("export * from \"./{}\";", name);
+        let exported = index.lines().any(|line| line == export);
+        let commented = index.lines().any(|line| line == format!("// {}", export));
+        assert!(
+            if ts.trim().is_empty() { commented } else { exported },
+            "index.ts does not export {}, run `yarn codegen` in types/",
+            name
+        );
+
+        let (mut types, mut keys, mut values) = (BTreeSet::new(), BTreeSet::new(), BTreeSet::new());
+        collect_names(&serde_json::from_str(schema).unwrap(), &mut types, &mut keys, &mut values);
+        let sources = [ts.as_str(), shared.as_str()];
+        let declared = |pattern: &dyn Fn(&str) -> bool| sources.iter().any(|source| source.lines().any(|line| pattern(line.trim_start())));
+
+        for ty in &types {
+            assert!(
+                declared(&|line| line.starts_with(&format!("export type {} ", ty))
+                    || line.starts_with(&format!("export interface {} ", ty))),
+                "{}.d.ts does not declare {}, run `yarn codegen` in types/",
+                name,
+                ty
+            );
+        }
+        for key in &keys {
+            assert!(
+                declared(&|line| line.starts_with(&format!("{}:", key)) || line.starts_with(&format!("{}?:", key))),
+                "{}.d.ts does not declare field {}, run `yarn codegen` in types/",
+                name,
+                key
+            );
+        }
+        for value in &values {
+            let literal = format!("\"{}\"", value);
+            assert!(
+                declared(&|line| line.contains(&literal)),
+                "{}.d.ts does not declare {}, run `yarn codegen` in types/",
+                name,
+                literal
+            );
+        }
+    }
+}
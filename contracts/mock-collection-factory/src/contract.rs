@@ -0,0 +1,66 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+};
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{ADMIN, COLLECTIONS};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    ADMIN.save(deps.storage, &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("admin", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    if info.sender != ADMIN.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match msg {
+        ExecuteMsg::AddCollection { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            COLLECTIONS.save(deps.storage, &address, &Empty {})?;
+            Ok(Response::new()
+                .add_attribute("action", "add_collection")
+                .add_attribute("collection", address))
+        }
+        ExecuteMsg::RemoveCollection { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            COLLECTIONS.remove(deps.storage, &address);
+            Ok(Response::new()
+                .add_attribute("action", "remove_collection")
+                .add_attribute("collection", address))
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::CheckCollection { address } => {
+            let registered = deps
+                .api
+                .addr_validate(&address)
+                .map(|address| COLLECTIONS.has(deps.storage, &address))
+                .unwrap_or(false);
+            to_binary(&registered)
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Register a collection as created by the factory, only callable by the admin
+    AddCollection { address: String },
+    /// Forget a registered collection, only callable by the admin
+    RemoveCollection { address: String },
+}
+
+/// Same shape as the marketplace's `CreateCollectionQueryMsg`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Whether the collection was registered
+    /// Return type: `bool`
+    CheckCollection { address: String },
+}
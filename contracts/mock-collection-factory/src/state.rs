@@ -0,0 +1,7 @@
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Item, Map};
+
+/// Instantiator of the factory, the only one who registers collections
+pub const ADMIN: Item<Addr> = Item::new("admin");
+
+pub const COLLECTIONS: Map<&Addr, Empty> = Map::new("collections");
@@ -0,0 +1,97 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use cosmwasm_std::{CustomMsg, DepsMut, Env, Event, MessageInfo, Response};
+
+use crate::error::ContractError;
+use crate::msg::UpdateCollectionInfoMsg;
+use crate::state::{Cw721Contract, COLLECTION_INFO};
+
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn update_collection_info(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        update: UpdateCollectionInfoMsg,
+    ) -> Result<Response<C>, ContractError> {
+        self.only_admin(deps.as_ref(), &info)?;
+
+        let mut collection_info = COLLECTION_INFO.load(deps.storage)?;
+        // the identity of a launched collection is what buyers relied on
+        if collection_info.is_launch == Some(true) {
+            let frozen = [
+                ("title", update.title.is_some()),
+                ("creator", update.creator.is_some()),
+                ("collection_id", update.collection_id.is_some()),
+            ];
+            if let Some((field, _)) = frozen.iter().find(|(_, changed)| *changed) {
+                return Err(ContractError::FrozenAfterLaunch(field.to_string()));
+            }
+        }
+
+        let mut updated = vec![];
+        let c = &mut collection_info;
+        set_field(&mut updated, "title", &mut c.title, update.title);
+        set_field(&mut updated, "creator", &mut c.creator, update.creator);
+        set_field(&mut updated, "image_url", &mut c.image_url, update.image_url);
+        set_field(&mut updated, "background_url", &mut c.background_url, update.background_url);
+        set_field(&mut updated, "logo_url", &mut c.logo_url, update.logo_url);
+        set_field(&mut updated, "collection_id", &mut c.collection_id, update.collection_id);
+        set_field(&mut updated, "metadata_url", &mut c.metadata_url, update.metadata_url);
+        set_field(&mut updated, "social_links", &mut c.social_links, update.social_links);
+        set_field(&mut updated, "description", &mut c.description, update.description);
+
+        if let Some(creator) = &collection_info.creator {
+            deps.api.addr_validate(creator)?;
+        }
+        collection_info.validate()?;
+        COLLECTION_INFO.save(deps.storage, &collection_info)?;
+
+        let event = Event::new("collection-info-update")
+            .add_attribute("collection", env.contract.address)
+            .add_attribute("fields", updated.join(","));
+        Ok(Response::new()
+            .add_attribute("action", "update_collection_info")
+            .add_event(event))
+    }
+
+    pub fn launch(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        self.only_admin(deps.as_ref(), &info)?;
+
+        let mut collection_info = COLLECTION_INFO.load(deps.storage)?;
+        if collection_info.is_launch == Some(true) {
+            return Err(ContractError::AlreadyLaunched {});
+        }
+        collection_info.is_launch = Some(true);
+        COLLECTION_INFO.save(deps.storage, &collection_info)?;
+
+        let event = Event::new("collection-launch")
+            .add_attribute("collection", env.contract.address)
+            .add_attribute("launch_time", env.block.time.seconds().to_string());
+        Ok(Response::new()
+            .add_attribute("action", "launch")
+            .add_event(event))
+    }
+}
+
+fn set_field<V>(
+    updated: &mut Vec<&'static str>,
+    name: &'static str,
+    field: &mut Option<V>,
+    value: Option<V>,
+) {
+    if value.is_some() {
+        *field = value;
+        updated.push(name);
+    }
+}
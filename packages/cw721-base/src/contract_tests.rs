@@ -0,0 +1,754 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{
+    coin, coins, from_binary, to_binary, BankMsg, CosmosMsg, Decimal, Deps, DepsMut, Empty, Uint128,
+    WasmMsg,
+};
+
+use cw721::{Cw721ReceiveMsg, Expiration, OwnerOfResponse, TokensResponse};
+
+use crate::msg::{
+    AdminResponse, CollectionInfo, ListingSaleType, MintInfo, MintListing, MintStatusResponse,
+    NftInfoResponse, RoyaltyInfoResponse, SocialLinkType, UpdateCollectionInfoMsg,
+    MAX_DESCRIPTION_LENGTH, MAX_SOCIAL_LINKS,
+};
+use crate::royalty::ROYALTY_INCREASE_DELAY;
+use crate::state::MAX_ATTRIBUTES;
+use crate::{
+    CollectionInfoResponse, ContractError, Cw721Contract, ExecuteMsg, Extension, InstantiateMsg,
+    Metadata, MintMsg, QueryMsg, Royalty, Trait,
+};
+
+const MINTER: &str = "minter";
+const ADMIN: &str = "admin";
+
+fn setup_contract(deps: DepsMut<'_>) -> Cw721Contract<'static, Extension, Empty> {
+    setup_contract_with_mint(deps, None)
+}
+
+fn setup_contract_with_mint(
+    deps: DepsMut<'_>,
+    mint_info: Option<MintInfo>,
+) -> Cw721Contract<'static, Extension, Empty> {
+    let contract = Cw721Contract::default();
+    let msg = InstantiateMsg {
+        name: "Humans".to_string(),
+        symbol: "HMN".to_string(),
+        minter: MINTER.to_string(),
+        admin: ADMIN.to_string(),
+        collection_info: CollectionInfo {
+            title: Some("Humans".to_string()),
+            creator: Some("creator".to_string()),
+            image_url: None,
+            background_url: None,
+            logo_url: None,
+            collection_id: None,
+            metadata_url: None,
+            social_links: None,
+            description: None,
+            is_launch: None,
+        },
+        royalty_info: Some(Royalty {
+            address: "artist".to_string(),
+            royalty_rate: Decimal::percent(5),
+            shares: None,
+        }),
+        mint_info,
+    };
+    let res = contract
+        .instantiate(deps, mock_env(), mock_info("creator", &[]), msg)
+        .unwrap();
+    assert_eq!(0, res.messages.len());
+    contract
+}
+
+fn mint_msg(token_id: &str, owner: &str) -> ExecuteMsg<Extension> {
+    ExecuteMsg::Mint(MintMsg {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: Some(format!("ipfs://{}", token_id)),
+        content_type: "image/png".to_string(),
+        extension: Metadata {
+            image_url: format!("ipfs://{}.png", token_id),
+            minter: MINTER.to_string(),
+            name: Some(format!("Human #{}", token_id)),
+            attributes: vec![Trait {
+                display_type: None,
+                trait_type: "background".to_string(),
+                value: "gold".to_string(),
+            }],
+            ..Metadata::default()
+        },
+    })
+}
+
+#[test]
+fn minting() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // only the minter can mint
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("random", &[]), mint_msg("1", "owner"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg("1", "owner"))
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg("1", "other"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Claimed {});
+
+    // the marketplace lists with the media type and image of the token
+    let info: NftInfoResponse<Extension> = from_binary(
+        &contract
+            .query(deps.as_ref(), mock_env(), QueryMsg::NftInfo { token_id: "1".to_string() })
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(info.content_type, "image/png");
+    assert_eq!(info.created_time, mock_env().block.time.seconds());
+    assert_eq!(info.extension.image_url, "ipfs://1.png");
+    assert_eq!(info.extension.attributes[0].value, "gold");
+    assert_eq!(contract.num_tokens(deps.as_ref()).unwrap().count, 1);
+
+    let tokens: TokensResponse = from_binary(
+        &contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Tokens { owner: "owner".to_string(), start_after: None, limit: None },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(tokens.tokens, vec!["1".to_string()]);
+}
+
+#[test]
+fn minting_validates_metadata() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mint_with = |update: &dyn Fn(&mut MintMsg<Extension>)| match mint_msg("1", "owner") {
+        ExecuteMsg::Mint(mut msg) => {
+            update(&mut msg);
+            ExecuteMsg::Mint(msg)
+        }
+        _ => unreachable!(),
+    };
+
+    let invalid = [
+        (
+            mint_with(&|m| m.content_type = "png".to_string()),
+            ContractError::InvalidContentType("png".to_string()),
+        ),
+        (
+            mint_with(&|m| m.extension.animation_url = Some("video.mp4".to_string())),
+            ContractError::InvalidUrl("video.mp4".to_string()),
+        ),
+        (
+            mint_with(&|m| m.extension.attributes[0].value = String::new()),
+            ContractError::InvalidTrait("background".to_string()),
+        ),
+        (
+            mint_with(&|m| {
+                let attribute = m.extension.attributes[0].clone();
+                m.extension.attributes = vec![attribute; MAX_ATTRIBUTES + 1]
+            }),
+            ContractError::TooManyAttributes(MAX_ATTRIBUTES),
+        ),
+    ];
+    for (msg, expected) in invalid {
+        let err = contract
+            .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), msg)
+            .unwrap_err();
+        assert_eq!(err, expected);
+    }
+}
+
+#[test]
+fn collection_state_and_admin() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let state: CollectionInfoResponse = from_binary(
+        &contract
+            .query(deps.as_ref(), mock_env(), QueryMsg::GetCollectionState {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(state.minter, MINTER);
+    assert_eq!(state.collection_info.title, Some("Humans".to_string()));
+    assert_eq!(state.royalty_info.unwrap().royalty_rate, Decimal::percent(5));
+
+    // only the admin can hand over the minter and admin roles
+    let msg = ExecuteMsg::UpdateMinter { minter: "minter2".to_string() };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg)
+        .unwrap();
+    assert_eq!(contract.minter(deps.as_ref()).unwrap().minter, "minter2");
+
+    let msg = ExecuteMsg::UpdateAdmin { admin: "admin2".to_string() };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg)
+        .unwrap();
+    let admin: AdminResponse =
+        from_binary(&contract.query(deps.as_ref(), mock_env(), QueryMsg::Admin {}).unwrap())
+            .unwrap();
+    assert_eq!(admin.admin, "admin2");
+}
+
+#[test]
+fn invalid_royalty() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<Extension, Empty>::default();
+    let msg = InstantiateMsg {
+        name: "Humans".to_string(),
+        symbol: "HMN".to_string(),
+        minter: MINTER.to_string(),
+        admin: ADMIN.to_string(),
+        collection_info: CollectionInfo {
+            title: None,
+            creator: None,
+            image_url: None,
+            background_url: None,
+            logo_url: None,
+            collection_id: None,
+            metadata_url: None,
+            social_links: None,
+            description: None,
+            is_launch: None,
+        },
+        royalty_info: Some(Royalty {
+            address: "artist".to_string(),
+            royalty_rate: Decimal::percent(101),
+            shares: None,
+        }),
+        mint_info: None,
+    };
+    let err = contract
+        .instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidRoyalities {});
+}
+
+#[test]
+fn transferring_and_sending() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg("1", "owner"))
+        .unwrap();
+
+    let transfer = ExecuteMsg::TransferNft {
+        recipient: "buyer".to_string(),
+        token_id: "1".to_string(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("random", &[]), transfer.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // an approved marketplace can move the token, the approval is cleared on transfer
+    let approve = ExecuteMsg::Approve {
+        spender: "marketplace".to_string(),
+        token_id: "1".to_string(),
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), approve)
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("marketplace", &[]), transfer)
+        .unwrap();
+    let owner: OwnerOfResponse = from_binary(
+        &contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::OwnerOf { token_id: "1".to_string(), include_expired: None },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(owner.owner, "buyer");
+    assert!(owner.approvals.is_empty());
+
+    // sending calls the receiving contract
+    let payload = to_binary("list").unwrap();
+    let send = ExecuteMsg::SendNft {
+        contract: "marketplace".to_string(),
+        token_id: "1".to_string(),
+        msg: payload.clone(),
+    };
+    let res = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("buyer", &[]), send)
+        .unwrap();
+    let expected = Cw721ReceiveMsg {
+        sender: "buyer".to_string(),
+        token_id: "1".to_string(),
+        msg: payload,
+    }
+    .into_cosmos_msg("marketplace")
+    .unwrap();
+    assert_eq!(res.messages[0].msg, expected);
+}
+
+#[test]
+fn operators_and_burning() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg("1", "owner"))
+        .unwrap();
+
+    let approve_all = ExecuteMsg::ApproveAll {
+        operator: "operator".to_string(),
+        expires: Some(Expiration::AtHeight(1)),
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), approve_all)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Expired {});
+    let approve_all = ExecuteMsg::ApproveAll { operator: "operator".to_string(), expires: None };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), approve_all)
+        .unwrap();
+
+    let burn = ExecuteMsg::Burn { token_id: "1".to_string() };
+    let revoke_all = ExecuteMsg::RevokeAll { operator: "operator".to_string() };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), revoke_all)
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("operator", &[]), burn.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), burn)
+        .unwrap();
+    assert_eq!(contract.num_tokens(deps.as_ref()).unwrap().count, 0);
+    assert!(contract
+        .query(deps.as_ref(), mock_env(), QueryMsg::NftInfo { token_id: "1".to_string() })
+        .is_err());
+}
+
+fn paid_mint_info() -> MintInfo {
+    MintInfo {
+        base_token_uri: "ipfs://tokens/".to_string(),
+        base_image_uri: "ipfs://images".to_string(),
+        total_supply: Uint128::new(3),
+        start_mint_time: mock_env().block.time.seconds(),
+        per_address_limit: Uint128::new(2),
+        public_price: coin(100, "uheart"),
+        private_price: coin(50, "uheart"),
+        mint_flag: false,
+        is_public_mint: false,
+        nft_base_name: "Human".to_string(),
+        content_type: "image/png".to_string(),
+    }
+}
+
+fn mint_status(contract: &Cw721Contract<Extension, Empty>, deps: Deps, address: &str) -> MintStatusResponse {
+    from_binary(
+        &contract
+            .query(deps, mock_env(), QueryMsg::MintStatus { address: address.to_string() })
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn paid_mint_phases() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract_with_mint(deps.as_mut(), Some(paid_mint_info()));
+    let mint = ExecuteMsg::MintPaid { list: None };
+
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("buyer", &coins(50, "uheart")), mint.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::MintingClosed {});
+
+    // only the admin opens the mint and manages the allowlist
+    let open = ExecuteMsg::SetMintPhase { mint_flag: true, is_public_mint: false };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), open.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), open)
+        .unwrap();
+
+    let mut early = mock_env();
+    early.block.time = early.block.time.minus_seconds(1);
+    let err = contract
+        .execute(deps.as_mut(), early, mock_info("buyer", &coins(50, "uheart")), mint.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::MintNotStarted {});
+
+    // the private phase is allowlist only, at the private price
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("buyer", &coins(50, "uheart")), mint.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotAllowlisted {});
+    let allowlist = ExecuteMsg::UpdateAllowlist { add: vec!["buyer".to_string()], remove: vec![] };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), allowlist)
+        .unwrap();
+    assert!(mint_status(&contract, deps.as_ref(), "buyer").allowlisted);
+
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("buyer", &coins(100, "uheart")), mint.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::IncorrectMintPayment(coin(50, "uheart")));
+    let res = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("buyer", &coins(50, "uheart")), mint.clone())
+        .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "creator".to_string(),
+            amount: coins(50, "uheart"),
+        })
+    );
+
+    let info: NftInfoResponse<Extension> = from_binary(
+        &contract
+            .query(deps.as_ref(), mock_env(), QueryMsg::NftInfo { token_id: "Human.1".to_string() })
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(info.token_uri, Some("ipfs://tokens/1".to_string()));
+    assert_eq!(info.content_type, "image/png");
+    assert_eq!(info.extension.image_url, "ipfs://images/1");
+    assert_eq!(info.extension.minter, "buyer");
+
+    // the public phase is open to anyone at the public price, within the address limit
+    let public = ExecuteMsg::SetMintPhase { mint_flag: true, is_public_mint: true };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), public)
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("buyer", &coins(100, "uheart")), mint.clone())
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("buyer", &coins(100, "uheart")), mint.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::MintLimitReached {});
+
+    let status = mint_status(&contract, deps.as_ref(), "buyer");
+    assert_eq!(status.minted, 2);
+    assert_eq!(status.address_minted, 2);
+    assert_eq!(status.next_token_id, "Human.3");
+
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("other", &coins(100, "uheart")), mint.clone())
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("third", &coins(100, "uheart")), mint)
+        .unwrap_err();
+    assert_eq!(err, ContractError::SoldOut {});
+    assert_eq!(contract.num_tokens(deps.as_ref()).unwrap().count, 3);
+}
+
+#[test]
+fn paid_mint_into_listing() {
+    let mut deps = mock_dependencies();
+    let mut mint_info = paid_mint_info();
+    mint_info.mint_flag = true;
+    mint_info.is_public_mint = true;
+    let contract = setup_contract_with_mint(deps.as_mut(), Some(mint_info));
+
+    let listing = MintListing {
+        marketplace: "marketplace".to_string(),
+        sale_type: ListingSaleType::FixedPrice,
+        price: coin(500, "uheart"),
+        funds_recipient: None,
+        expires: 3600,
+    };
+    let mint = ExecuteMsg::MintPaid { list: Some(listing) };
+
+    // the surplus over the mint price is forwarded as the listing fee
+    let res = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("buyer", &coins(110, "uheart")), mint)
+        .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    match &res.messages[1].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) => {
+            assert_eq!(contract_addr, "marketplace");
+            assert_eq!(funds, &coins(10, "uheart"));
+            let ReceiveMsg::ReceiveNft(receive) = from_binary(msg).unwrap();
+            assert_eq!(receive.sender, "buyer");
+            assert_eq!(receive.token_id, "Human.1");
+            assert_eq!(
+                String::from_utf8(receive.msg.to_vec()).unwrap(),
+                r#"{"set_ask":{"sale_type":"fixed_price","collection":"cosmos2contract","token_id":"Human.1","price":{"denom":"uheart","amount":"500"},"funds_recipient":null,"expires":3600}}"#
+            );
+        }
+        msg => panic!("unexpected message {:?}", msg),
+    }
+
+    // the marketplace holds the token for the listing
+    let owner: OwnerOfResponse = from_binary(
+        &contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::OwnerOf { token_id: "Human.1".to_string(), include_expired: None },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(owner.owner, "marketplace");
+}
+
+#[test]
+fn royalty_updates() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let royalty = |rate: u64| Royalty {
+        address: "artist".to_string(),
+        royalty_rate: Decimal::percent(rate),
+        shares: None,
+    };
+    let royalty_info = |deps: Deps, env| -> RoyaltyInfoResponse {
+        from_binary(&contract.query(deps, env, QueryMsg::RoyaltyInfo {}).unwrap()).unwrap()
+    };
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::UpdateRoyalty { royalty: royalty(3) },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::UpdateRoyalty { royalty: royalty(101) },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidRoyalities {});
+
+    // a decrease applies at once
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::UpdateRoyalty { royalty: royalty(3) },
+        )
+        .unwrap();
+    let event = &res.events[0];
+    assert_eq!(event.ty, "royalty-update");
+    assert!(event.attributes.iter().any(|a| a.key == "previous_rate" && a.value == "0.05"));
+    let info = royalty_info(deps.as_ref(), mock_env());
+    assert_eq!(info.royalty_info, Some(royalty(3)));
+    assert_eq!(info.pending, None);
+
+    // an increase waits for the delay
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::UpdateRoyalty { royalty: royalty(8) },
+        )
+        .unwrap();
+    let info = royalty_info(deps.as_ref(), mock_env());
+    assert_eq!(info.royalty_info, Some(royalty(3)));
+    let pending = info.pending.unwrap();
+    assert_eq!(pending.royalty, royalty(8));
+    assert_eq!(pending.effective_time, mock_env().block.time.seconds() + ROYALTY_INCREASE_DELAY);
+
+    let mut later = mock_env();
+    later.block.time = later.block.time.plus_seconds(ROYALTY_INCREASE_DELAY);
+    let info = royalty_info(deps.as_ref(), later.clone());
+    assert_eq!(info.royalty_info, Some(royalty(8)));
+    assert_eq!(info.pending, None);
+    let state: CollectionInfoResponse = from_binary(
+        &contract
+            .query(deps.as_ref(), later.clone(), QueryMsg::GetCollectionState {})
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(state.royalty_info, Some(royalty(8)));
+
+    // the applied increase is the base for the next update
+    contract
+        .execute(
+            deps.as_mut(),
+            later.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::UpdateRoyalty { royalty: royalty(6) },
+        )
+        .unwrap();
+    assert_eq!(royalty_info(deps.as_ref(), later.clone()).royalty_info, Some(royalty(6)));
+
+    // removing also drops a scheduled increase
+    contract
+        .execute(
+            deps.as_mut(),
+            later.clone(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::UpdateRoyalty { royalty: royalty(10) },
+        )
+        .unwrap();
+    let res = contract
+        .execute(deps.as_mut(), later.clone(), mock_info(ADMIN, &[]), ExecuteMsg::RemoveRoyalty {})
+        .unwrap();
+    assert_eq!(res.events[0].ty, "royalty-remove");
+    later.block.time = later.block.time.plus_seconds(ROYALTY_INCREASE_DELAY);
+    let info = royalty_info(deps.as_ref(), later);
+    assert_eq!(info.royalty_info, None);
+    assert_eq!(info.pending, None);
+}
+
+#[test]
+fn collection_info_updates() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let update = |collection_info: UpdateCollectionInfoMsg| ExecuteMsg::UpdateCollectionInfo {
+        collection_info,
+    };
+    let twitter = SocialLinkType {
+        tool: "twitter".to_string(),
+        link: "https://twitter.com/humans".to_string(),
+    };
+
+    let msg = update(UpdateCollectionInfoMsg {
+        description: Some("Humans of the chain".to_string()),
+        ..Default::default()
+    });
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg)
+        .unwrap();
+
+    // every field is validated
+    let invalid = [
+        (
+            UpdateCollectionInfoMsg {
+                description: Some("a".repeat(MAX_DESCRIPTION_LENGTH + 1)),
+                ..Default::default()
+            },
+            ContractError::DescriptionTooLong {},
+        ),
+        (
+            UpdateCollectionInfoMsg { title: Some("t".repeat(65)), ..Default::default() },
+            ContractError::TitleTooLong {},
+        ),
+        (
+            UpdateCollectionInfoMsg {
+                logo_url: Some("http://logo.png".to_string()),
+                ..Default::default()
+            },
+            ContractError::InvalidUrl("http://logo.png".to_string()),
+        ),
+        (
+            UpdateCollectionInfoMsg {
+                social_links: Some(vec![twitter.clone(); MAX_SOCIAL_LINKS + 1]),
+                ..Default::default()
+            },
+            ContractError::TooManySocialLinks(MAX_SOCIAL_LINKS),
+        ),
+        (
+            UpdateCollectionInfoMsg {
+                social_links: Some(vec![SocialLinkType {
+                    tool: "myspace".to_string(),
+                    link: "https://myspace.com/humans".to_string(),
+                }]),
+                ..Default::default()
+            },
+            ContractError::InvalidSocialLinkTool("myspace".to_string()),
+        ),
+    ];
+    for (collection_info, expected) in invalid {
+        let err = contract
+            .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), update(collection_info))
+            .unwrap_err();
+        assert_eq!(err, expected);
+    }
+
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            update(UpdateCollectionInfoMsg {
+                image_url: Some("ipfs://humans.png".to_string()),
+                social_links: Some(vec![twitter.clone()]),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+    assert_eq!(res.events[0].ty, "collection-info-update");
+    assert!(res.events[0]
+        .attributes
+        .iter()
+        .any(|a| a.key == "fields" && a.value == "image_url,social_links"));
+
+    // launching freezes the identity of the collection
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), ExecuteMsg::Launch {})
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), ExecuteMsg::Launch {})
+        .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyLaunched {});
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            update(UpdateCollectionInfoMsg {
+                title: Some("Other".to_string()),
+                ..Default::default()
+            }),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::FrozenAfterLaunch("title".to_string()));
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            update(UpdateCollectionInfoMsg {
+                logo_url: Some("ar://logo".to_string()),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+
+    let state: CollectionInfoResponse = from_binary(
+        &contract
+            .query(deps.as_ref(), mock_env(), QueryMsg::GetCollectionState {})
+            .unwrap(),
+    )
+    .unwrap();
+    let collection_info = state.collection_info;
+    assert_eq!(collection_info.title, Some("Humans".to_string()));
+    assert_eq!(collection_info.description, Some("Humans of the chain".to_string()));
+    assert_eq!(collection_info.image_url, Some("ipfs://humans.png".to_string()));
+    assert_eq!(collection_info.logo_url, Some("ar://logo".to_string()));
+    assert_eq!(collection_info.social_links, Some(vec![twitter]));
+    assert_eq!(collection_info.is_launch, Some(true));
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ReceiveMsg {
+    ReceiveNft(Cw721ReceiveMsg),
+}
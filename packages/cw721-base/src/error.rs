@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError};
+use cw_utils::PaymentError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -21,6 +22,57 @@ pub enum ContractError {
     #[error("Description too long")]
     DescriptionTooLong {},
 
+    #[error("Title too long")]
+    TitleTooLong {},
+
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("At most {0} social links are allowed")]
+    TooManySocialLinks(usize),
+
+    #[error("Unsupported social link tool: {0}")]
+    InvalidSocialLinkTool(String),
+
+    #[error("Invalid content type: {0}")]
+    InvalidContentType(String),
+
+    #[error("At most {0} attributes are allowed")]
+    TooManyAttributes(usize),
+
+    #[error("Trait needs a type and a value: {0}")]
+    InvalidTrait(String),
+
+    #[error("Collection is already launched")]
+    AlreadyLaunched {},
+
+    #[error("{0} cannot change after launch")]
+    FrozenAfterLaunch(String),
+
     #[error("Invalid Royalities")]
     InvalidRoyalities {},
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Collection has no mint info")]
+    NoMintInfo {},
+
+    #[error("Minting is closed")]
+    MintingClosed {},
+
+    #[error("Minting has not started")]
+    MintNotStarted {},
+
+    #[error("Address is not allowlisted for the private mint")]
+    NotAllowlisted {},
+
+    #[error("All tokens have been minted")]
+    SoldOut {},
+
+    #[error("Address reached its mint limit")]
+    MintLimitReached {},
+
+    #[error("Mint costs {0}")]
+    IncorrectMintPayment(Coin),
 }
@@ -1,11 +1,54 @@
+pub mod collection;
 mod error;
+pub mod execute;
 pub mod helpers;
+mod mint;
 pub mod msg;
+pub mod query;
+pub mod royalty;
 pub mod state;
 
 pub use crate::error::ContractError;
-pub use crate::msg::{ExecuteMsg, InstantiateMsg, MintMsg, MinterResponse, QueryMsg, CollectionInfoResponse};
-pub use crate::state::{Metadata};
+pub use crate::msg::{ExecuteMsg, InstantiateMsg, MintMsg, MinterResponse, QueryMsg, CollectionInfoResponse, Royalty, RoyaltyShare};
+pub use crate::state::{Cw721Contract, Metadata, Trait};
 
-// This is a simple type to let us handle empty extensions
-pub type Extension = Option<Metadata>;
\ No newline at end of file
+/// Token extension of the deployed contract, the marketplace lists NFTs with its `image_url`
+pub type Extension = Metadata;
+
+#[cfg(not(feature = "library"))]
+pub mod entry {
+    use super::*;
+
+    use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+
+    #[entry_point]
+    pub fn instantiate(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> Result<Response, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.instantiate(deps, env, info, msg)
+    }
+
+    #[entry_point]
+    pub fn execute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg<Extension>,
+    ) -> Result<Response, ContractError> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.execute(deps, env, info, msg)
+    }
+
+    #[entry_point]
+    pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+        let tract = Cw721Contract::<Extension, Empty>::default();
+        tract.query(deps, env, msg)
+    }
+}
+
+#[cfg(test)]
+mod contract_tests;
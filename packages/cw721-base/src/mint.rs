@@ -0,0 +1,218 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CustomMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdResult, Uint128, WasmMsg,
+};
+use cw721::Cw721ReceiveMsg;
+use cw_utils::must_pay;
+
+use crate::error::ContractError;
+use crate::msg::{ListingSaleType, MintInfo, MintListing};
+use crate::state::{
+    Cw721Contract, TokenInfo, ADDRESS_MINT_COUNT, ALLOWLIST, COLLECTION_INFO, MINT_COUNT,
+    MINT_INFO,
+};
+use crate::Extension;
+
+/// Listing payload the marketplace expects with a received NFT, `ReceiveNftMsg::SetAsk`
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+enum MarketplaceReceiveMsg {
+    SetAsk(MarketplaceAskInfo),
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+struct MarketplaceAskInfo {
+    sale_type: ListingSaleType,
+    collection: String,
+    token_id: String,
+    price: Coin,
+    funds_recipient: Option<String>,
+    expires: u64,
+}
+
+impl<'a, C> Cw721Contract<'a, Extension, C>
+where
+    C: CustomMsg,
+{
+    /// Mints the next token to the sender, or lists it on a marketplace on their behalf,
+    /// and pays the mint price to the collection creator
+    pub fn mint_paid(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        list: Option<MintListing>,
+    ) -> Result<Response<C>, ContractError> {
+        let mint_info = MINT_INFO
+            .load(deps.storage)?
+            .ok_or(ContractError::NoMintInfo {})?;
+        if !mint_info.mint_flag {
+            return Err(ContractError::MintingClosed {});
+        }
+        if env.block.time.seconds() < mint_info.start_mint_time {
+            return Err(ContractError::MintNotStarted {});
+        }
+
+        let buyer = info.sender.clone();
+        let price = if mint_info.is_public_mint {
+            mint_info.public_price.clone()
+        } else {
+            if !ALLOWLIST.has(deps.storage, &buyer) {
+                return Err(ContractError::NotAllowlisted {});
+            }
+            mint_info.private_price.clone()
+        };
+
+        let minted = MINT_COUNT.may_load(deps.storage)?.unwrap_or_default();
+        if Uint128::from(minted) >= mint_info.total_supply {
+            return Err(ContractError::SoldOut {});
+        }
+        let address_minted = ADDRESS_MINT_COUNT
+            .may_load(deps.storage, &buyer)?
+            .unwrap_or_default();
+        if Uint128::from(address_minted) >= mint_info.per_address_limit {
+            return Err(ContractError::MintLimitReached {});
+        }
+
+        // Funds above the price are only accepted as the listing fee of a listing
+        let paid = if info.funds.is_empty() {
+            Uint128::zero()
+        } else {
+            must_pay(&info, &price.denom)?
+        };
+        if paid < price.amount || (list.is_none() && paid > price.amount) {
+            return Err(ContractError::IncorrectMintPayment(price));
+        }
+        let listing_fee = paid - price.amount;
+
+        let n = minted + 1;
+        let token_id = token_id(&mint_info, n);
+        let owner = match &list {
+            Some(listing) => deps.api.addr_validate(&listing.marketplace)?,
+            None => buyer.clone(),
+        };
+        let token = TokenInfo {
+            owner,
+            approvals: vec![],
+            token_uri: Some(join_uri(&mint_info.base_token_uri, n)),
+            content_type: mint_info.content_type.clone(),
+            created_time: env.block.time.seconds(),
+            extension: Extension {
+                image_url: join_uri(&mint_info.base_image_uri, n),
+                minter: buyer.to_string(),
+                ..Extension::default()
+            },
+        };
+        self.tokens
+            .update(deps.storage, &token_id, |old| match old {
+                Some(_) => Err(ContractError::Claimed {}),
+                None => Ok(token),
+            })?;
+        self.increment_tokens(deps.storage)?;
+        MINT_COUNT.save(deps.storage, &n)?;
+        ADDRESS_MINT_COUNT.save(deps.storage, &buyer, &(address_minted + 1))?;
+
+        let mut res = Response::new();
+        if !price.amount.is_zero() {
+            res = res.add_message(BankMsg::Send {
+                to_address: self.creator(deps.as_ref())?.to_string(),
+                amount: vec![price.clone()],
+            });
+        }
+        if let Some(listing) = list {
+            let ask = MarketplaceReceiveMsg::SetAsk(MarketplaceAskInfo {
+                sale_type: listing.sale_type,
+                collection: env.contract.address.to_string(),
+                token_id: token_id.clone(),
+                price: listing.price,
+                funds_recipient: listing.funds_recipient,
+                expires: listing.expires,
+            });
+            let receive = Cw721ReceiveMsg {
+                sender: buyer.to_string(),
+                token_id: token_id.clone(),
+                msg: to_binary(&ask)?,
+            };
+            let funds = match listing_fee.is_zero() {
+                true => vec![],
+                false => vec![Coin::new(listing_fee.u128(), &price.denom)],
+            };
+            res = res
+                .add_message(WasmMsg::Execute {
+                    contract_addr: listing.marketplace.clone(),
+                    msg: receive.into_binary()?,
+                    funds,
+                })
+                .add_attribute("marketplace", listing.marketplace);
+        }
+
+        Ok(res
+            .add_attribute("action", "mint_paid")
+            .add_attribute("minter", buyer)
+            .add_attribute("token_id", token_id)
+            .add_attribute("price", price.to_string()))
+    }
+
+    pub fn set_mint_phase(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        mint_flag: bool,
+        is_public_mint: bool,
+    ) -> Result<Response<C>, ContractError> {
+        self.only_admin(deps.as_ref(), &info)?;
+
+        let mut mint_info = MINT_INFO
+            .load(deps.storage)?
+            .ok_or(ContractError::NoMintInfo {})?;
+        mint_info.mint_flag = mint_flag;
+        mint_info.is_public_mint = is_public_mint;
+        MINT_INFO.save(deps.storage, &Some(mint_info))?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_mint_phase")
+            .add_attribute("mint_flag", mint_flag.to_string())
+            .add_attribute("is_public_mint", is_public_mint.to_string()))
+    }
+
+    pub fn update_allowlist(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Response<C>, ContractError> {
+        self.only_admin(deps.as_ref(), &info)?;
+
+        for address in &add {
+            ALLOWLIST.save(deps.storage, &deps.api.addr_validate(address)?, &Empty {})?;
+        }
+        for address in &remove {
+            ALLOWLIST.remove(deps.storage, &deps.api.addr_validate(address)?);
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "update_allowlist")
+            .add_attribute("added", add.len().to_string())
+            .add_attribute("removed", remove.len().to_string()))
+    }
+
+    /// Mint proceeds go to the collection creator, or to the admin if there is none
+    fn creator(&self, deps: Deps) -> StdResult<Addr> {
+        match COLLECTION_INFO.load(deps.storage)?.creator {
+            Some(creator) => deps.api.addr_validate(&creator),
+            None => self.admin.load(deps.storage),
+        }
+    }
+}
+
+pub(crate) fn token_id(mint_info: &MintInfo, n: u64) -> String {
+    format!("{}.{}", mint_info.nft_base_name, n)
+}
+
+fn join_uri(base: &str, n: u64) -> String {
+    format!("{}/{}", base.trim_end_matches('/'), n)
+}
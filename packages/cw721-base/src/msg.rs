@@ -2,8 +2,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{ContractError};
-use cosmwasm_std::{Binary, Decimal, Uint128, Coin};
-use cw721::Expiration;
+use cosmwasm_std::{Api, Binary, Decimal, Uint128, Coin};
+use cw721::{Expiration, OwnerOfResponse};
 
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -14,18 +14,29 @@ pub struct CollectionInfoResponse {
     pub royalty_info : Option<Royalty>,
 }
 
+/// Configuration of the paid mint. Token `n` is minted as `{nft_base_name}.{n}` with its
+/// token URI and image under `base_token_uri` and `base_image_uri`.
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct MintInfo {
     pub base_token_uri: String,
     pub base_image_uri:String,
+    /// Maximum number of tokens the paid mint creates
     pub total_supply: Uint128,
+    /// Block time in seconds minting opens at
     pub start_mint_time: u64,
+    /// Maximum number of tokens one address can mint
     pub per_address_limit: Uint128,
     pub public_price: Coin,
+    /// Price for allowlisted addresses during the private phase
     pub private_price:Coin,
+    /// Whether the paid mint is open
     pub mint_flag:bool,
+    /// Whether the public phase has started, the private phase is allowlist only
     pub is_public_mint:bool,
-    pub nft_base_name:String
+    pub nft_base_name:String,
+    /// MIME type of the minted media
+    #[serde(default)]
+    pub content_type: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -48,6 +59,94 @@ pub struct SocialLinkType {
     pub link: String
 }
 
+pub const MAX_TITLE_LENGTH: usize = 64;
+pub const MAX_DESCRIPTION_LENGTH: usize = 512;
+pub const MAX_SOCIAL_LINKS: usize = 8;
+/// Social link `tool`s the marketplace shows an icon for
+pub const SOCIAL_LINK_TOOLS: &[&str] = &[
+    "website", "twitter", "discord", "telegram", "instagram", "medium", "github",
+];
+const URL_SCHEMES: &[&str] = &["https://", "ipfs://", "ar://"];
+
+impl CollectionInfo {
+    /// Validates the lengths of the text fields, the shape of every URL and the social links
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.title.as_ref().map_or(0, |t| t.chars().count()) > MAX_TITLE_LENGTH {
+            return Err(ContractError::TitleTooLong {});
+        }
+        if self.description.as_ref().map_or(0, |d| d.chars().count()) > MAX_DESCRIPTION_LENGTH {
+            return Err(ContractError::DescriptionTooLong {});
+        }
+
+        let urls = [
+            &self.image_url,
+            &self.background_url,
+            &self.logo_url,
+            &self.metadata_url,
+        ];
+        for url in urls.iter().filter_map(|url| url.as_deref()) {
+            validate_url(url)?;
+        }
+
+        let social_links = self.social_links.as_deref().unwrap_or_default();
+        if social_links.len() > MAX_SOCIAL_LINKS {
+            return Err(ContractError::TooManySocialLinks(MAX_SOCIAL_LINKS));
+        }
+        for social_link in social_links {
+            if !SOCIAL_LINK_TOOLS.contains(&social_link.tool.as_str()) {
+                return Err(ContractError::InvalidSocialLinkTool(social_link.tool.clone()));
+            }
+            validate_url(&social_link.link)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn validate_url(url: &str) -> Result<(), ContractError> {
+    let shaped = URL_SCHEMES
+        .iter()
+        .any(|scheme| url.len() > scheme.len() && url.starts_with(scheme))
+        && !url.chars().any(char::is_whitespace);
+    if !shaped {
+        return Err(ContractError::InvalidUrl(url.to_string()));
+    }
+    Ok(())
+}
+
+/// Content types are empty or a `type/subtype` media type such as `image/png`
+pub fn validate_content_type(content_type: &str) -> Result<(), ContractError> {
+    if content_type.is_empty() {
+        return Ok(());
+    }
+    let shaped = match content_type.split_once('/') {
+        Some((kind, subtype)) => {
+            !kind.is_empty()
+                && !subtype.is_empty()
+                && !subtype.contains('/')
+                && !content_type.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+    if !shaped {
+        return Err(ContractError::InvalidContentType(content_type.to_string()));
+    }
+    Ok(())
+}
+
+/// Collection info fields to change, `None` leaves a field as it is
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct UpdateCollectionInfoMsg {
+    pub title: Option<String>,
+    pub creator: Option<String>,
+    pub image_url: Option<String>,
+    pub background_url: Option<String>,
+    pub logo_url: Option<String>,
+    pub collection_id: Option<String>,
+    pub metadata_url: Option<String>,
+    pub social_links: Option<Vec<SocialLinkType>>,
+    pub description: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     /// Name of the NFT contract
@@ -62,6 +161,11 @@ pub struct InstantiateMsg {
 
     pub admin: String,
     pub collection_info: CollectionInfo,
+    /// Royalty paid on marketplace sales of the collection's NFTs
+    #[serde(default)]
+    pub royalty_info: Option<Royalty>,
+    #[serde(default)]
+    pub mint_info: Option<MintInfo>,
 }
 
 /// This is like Cw721ExecuteMsg but we add a Mint command for an owner
@@ -108,6 +212,51 @@ pub enum ExecuteMsg<T> {
 
     /// Update admin
     UpdateAdmin { admin: String },
+
+    /// Mint the next token of the collection, paying the price of the current phase.
+    /// With `list`, the token is listed on a marketplace as it is minted, with any funds
+    /// sent above the mint price forwarded as the listing fee.
+    MintPaid { list: Option<MintListing> },
+
+    /// Open or close the paid mint and switch between the private and public phase,
+    /// only callable by the admin
+    SetMintPhase { mint_flag: bool, is_public_mint: bool },
+
+    /// Add and remove addresses allowed to mint in the private phase,
+    /// only callable by the admin
+    UpdateAllowlist { add: Vec<String>, remove: Vec<String> },
+
+    /// Set the royalty paid on marketplace sales, only callable by the admin.
+    /// A higher rate applies after a delay, anything else at once.
+    UpdateRoyalty { royalty: Royalty },
+
+    /// Stop paying royalties, only callable by the admin
+    RemoveRoyalty {},
+
+    /// Change the collection info, only callable by the admin.
+    /// Once launched the title, creator and collection id are frozen.
+    UpdateCollectionInfo { collection_info: UpdateCollectionInfoMsg },
+
+    /// Mark the collection as launched, only callable by the admin
+    Launch {},
+}
+
+/// Marketplace listing created for a token as it is minted
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintListing {
+    pub marketplace: String,
+    pub sale_type: ListingSaleType,
+    pub price: Coin,
+    pub funds_recipient: Option<String>,
+    /// Seconds from now the listing expires in
+    pub expires: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ListingSaleType {
+    FixedPrice,
+    Auction,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -120,6 +269,9 @@ pub struct MintMsg<T> {
     /// Should point to a JSON file that conforms to the ERC721
     /// Metadata JSON Schema
     pub token_uri: Option<String>,
+    /// MIME type of the NFT media, e.g. `image/png`
+    #[serde(default)]
+    pub content_type: String,
     /// Any custom extension used by this contract
     pub extension: T,
 }
@@ -198,6 +350,35 @@ pub enum QueryMsg {
     Admin {},
 
     GetCollectionState  {},
+
+    /// Paid mint progress, overall and for `address`
+    /// Return type: `MintStatusResponse`
+    MintStatus { address: String },
+
+    /// Royalty in effect and any scheduled increase
+    /// Return type: `RoyaltyInfoResponse`
+    RoyaltyInfo {},
+}
+
+/// Like `cw721::NftInfoResponse`, with the media type and mint time the marketplace lists by
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct NftInfoResponse<T> {
+    /// Universal resource identifier for this NFT
+    /// Should point to a JSON file that conforms to the ERC721
+    /// Metadata JSON Schema
+    pub token_uri: Option<String>,
+    pub content_type: String,
+    pub created_time: u64,
+    /// You can add any custom metadata here when you extend cw721-base
+    pub extension: T,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct AllNftInfoResponse<T> {
+    /// Who can transfer the token
+    pub access: OwnerOfResponse,
+    /// Data on the token itself,
+    pub info: NftInfoResponse<T>,
 }
 
 /// Shows who can mint these tokens
@@ -206,6 +387,22 @@ pub struct MinterResponse {
     pub minter: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintStatusResponse {
+    /// Tokens created by the paid mint so far
+    pub minted: u64,
+    /// Id the next paid mint creates
+    pub next_token_id: String,
+    pub address_minted: u64,
+    pub allowlisted: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RoyaltyInfoResponse {
+    pub royalty_info: Option<Royalty>,
+    pub pending: Option<PendingRoyalty>,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct AdminResponse {
     pub admin: String,
@@ -215,15 +412,52 @@ pub struct AdminResponse {
 pub struct Royalty {
     pub address: String,
     pub royalty_rate: Decimal,
+    /// Optional split of the royalty between several recipients.
+    /// `address` receives whatever the shares leave over.
+    #[serde(default)]
+    pub shares: Option<Vec<RoyaltyShare>>,
 }
 
+/// Royalty increase waiting for its `effective_time`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRoyalty {
+    pub royalty: Royalty,
+    /// Block time in seconds the royalty applies from
+    pub effective_time: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltyShare {
+    pub address: String,
+    /// Fraction of the royalty paid to `address`
+    pub share: Decimal,
+}
 
 impl Royalty {
+    /// Validates the rate, the shares and every recipient address
+    pub fn validate(&self, api: &dyn Api) -> Result<(), ContractError> {
+        self.royalty_rate_validate()?;
+        api.addr_validate(&self.address)?;
+        for share in self.shares.iter().flatten() {
+            api.addr_validate(&share.address)?;
+        }
+        Ok(())
+    }
+
     pub fn royalty_rate_validate(&self) -> Result<Decimal, ContractError> {
         if self.royalty_rate > Decimal::one() {
             return Err(ContractError::InvalidRoyalities {});
         }
 
+        let total_share = self
+            .shares
+            .iter()
+            .flatten()
+            .fold(Decimal::zero(), |total, s| total + s.share);
+        if total_share > Decimal::one() {
+            return Err(ContractError::InvalidRoyalities {});
+        }
+
         Ok(self.royalty_rate)
     }
 }
@@ -0,0 +1,356 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use cosmwasm_std::{to_binary, Addr, Binary, BlockInfo, CustomMsg, Deps, Env, Order, StdResult};
+
+use cw721::{
+    ApprovalResponse, ApprovalsResponse, ContractInfoResponse, NumTokensResponse,
+    OperatorsResponse, OwnerOfResponse, TokensResponse,
+};
+use cw_storage_plus::Bound;
+use cw_utils::maybe_addr;
+
+use crate::mint::token_id;
+use crate::msg::{
+    AdminResponse, AllNftInfoResponse, CollectionInfoResponse, MintStatusResponse,
+    MinterResponse, NftInfoResponse, QueryMsg, RoyaltyInfoResponse,
+};
+use crate::royalty::effective_royalty;
+use crate::state::{
+    Approval, Cw721Contract, TokenInfo, ADDRESS_MINT_COUNT, ALLOWLIST, COLLECTION_INFO,
+    MINT_COUNT, MINT_INFO, PENDING_ROYALTY,
+};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    pub fn contract_info(&self, deps: Deps) -> StdResult<ContractInfoResponse> {
+        self.contract_info.load(deps.storage)
+    }
+
+    pub fn num_tokens(&self, deps: Deps) -> StdResult<NumTokensResponse> {
+        let count = self.token_count(deps.storage)?;
+        Ok(NumTokensResponse { count })
+    }
+
+    pub fn nft_info(&self, deps: Deps, token_id: String) -> StdResult<NftInfoResponse<T>> {
+        let info = self.tokens.load(deps.storage, &token_id)?;
+        Ok(NftInfoResponse {
+            token_uri: info.token_uri,
+            content_type: info.content_type,
+            created_time: info.created_time,
+            extension: info.extension,
+        })
+    }
+
+    pub fn owner_of(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<OwnerOfResponse> {
+        let info = self.tokens.load(deps.storage, &token_id)?;
+        Ok(OwnerOfResponse {
+            owner: info.owner.to_string(),
+            approvals: humanize_approvals(&env.block, &info, include_expired),
+        })
+    }
+
+    /// operators returns all operators owner given access to
+    pub fn operators(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: String,
+        include_expired: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OperatorsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let res: StdResult<Vec<_>> = self
+            .operators
+            .prefix(&owner_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter(|r| {
+                include_expired || r.is_err() || !r.as_ref().unwrap().1.is_expired(&env.block)
+            })
+            .take(limit)
+            .map(parse_approval)
+            .collect();
+        Ok(OperatorsResponse { operators: res? })
+    }
+
+    pub fn approval(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        spender: String,
+        include_expired: bool,
+    ) -> StdResult<ApprovalResponse> {
+        let token = self.tokens.load(deps.storage, &token_id)?;
+
+        // token owner has absolute approval
+        if token.owner == spender {
+            let approval = cw721::Approval {
+                spender: token.owner.to_string(),
+                expires: cw721::Expiration::Never {},
+            };
+            return Ok(ApprovalResponse { approval });
+        }
+
+        let filtered: Vec<_> = token
+            .approvals
+            .into_iter()
+            .filter(|t| t.spender == spender)
+            .filter(|t| include_expired || !t.is_expired(&env.block))
+            .map(|a| cw721::Approval {
+                spender: a.spender.into_string(),
+                expires: a.expires,
+            })
+            .collect();
+
+        if filtered.is_empty() {
+            return Err(cosmwasm_std::StdError::not_found("Approval not found"));
+        }
+        // we expect only one item
+        let approval = filtered[0].clone();
+
+        Ok(ApprovalResponse { approval })
+    }
+
+    /// approvals returns all approvals owner given access to
+    pub fn approvals(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<ApprovalsResponse> {
+        let token = self.tokens.load(deps.storage, &token_id)?;
+        let approvals: Vec<_> = token
+            .approvals
+            .into_iter()
+            .filter(|t| include_expired || !t.is_expired(&env.block))
+            .map(|a| cw721::Approval {
+                spender: a.spender.into_string(),
+                expires: a.expires,
+            })
+            .collect();
+
+        Ok(ApprovalsResponse { approvals })
+    }
+
+    pub fn tokens(
+        &self,
+        deps: Deps,
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let tokens: Vec<String> = self
+            .tokens
+            .idx
+            .owner
+            .prefix(owner_addr)
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(TokensResponse { tokens })
+    }
+
+    pub fn all_tokens(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        let tokens: StdResult<Vec<String>> = self
+            .tokens
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(k, _)| k))
+            .collect();
+
+        Ok(TokensResponse { tokens: tokens? })
+    }
+
+    pub fn all_nft_info(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<AllNftInfoResponse<T>> {
+        let info = self.tokens.load(deps.storage, &token_id)?;
+        Ok(AllNftInfoResponse {
+            access: OwnerOfResponse {
+                owner: info.owner.to_string(),
+                approvals: humanize_approvals(&env.block, &info, include_expired),
+            },
+            info: NftInfoResponse {
+                token_uri: info.token_uri,
+                content_type: info.content_type,
+                created_time: info.created_time,
+                extension: info.extension,
+            },
+        })
+    }
+
+    pub fn minter(&self, deps: Deps) -> StdResult<MinterResponse> {
+        let minter = self.minter.load(deps.storage)?;
+        Ok(MinterResponse {
+            minter: minter.to_string(),
+        })
+    }
+
+    pub fn admin(&self, deps: Deps) -> StdResult<AdminResponse> {
+        let admin = self.admin.load(deps.storage)?;
+        Ok(AdminResponse {
+            admin: admin.to_string(),
+        })
+    }
+
+    pub fn collection_state(&self, deps: Deps, env: Env) -> StdResult<CollectionInfoResponse> {
+        Ok(CollectionInfoResponse {
+            collection_info: COLLECTION_INFO.load(deps.storage)?,
+            mint_info: MINT_INFO.load(deps.storage)?,
+            minter: self.minter.load(deps.storage)?.to_string(),
+            royalty_info: effective_royalty(deps.storage, &env.block)?,
+        })
+    }
+
+    pub fn royalty_info(&self, deps: Deps, env: Env) -> StdResult<RoyaltyInfoResponse> {
+        let pending = PENDING_ROYALTY
+            .may_load(deps.storage)?
+            .filter(|p| p.effective_time > env.block.time.seconds());
+        Ok(RoyaltyInfoResponse {
+            royalty_info: effective_royalty(deps.storage, &env.block)?,
+            pending,
+        })
+    }
+
+    pub fn mint_status(&self, deps: Deps, address: String) -> StdResult<MintStatusResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let minted = MINT_COUNT.may_load(deps.storage)?.unwrap_or_default();
+        let next_token_id = match MINT_INFO.load(deps.storage)? {
+            Some(mint_info) => token_id(&mint_info, minted + 1),
+            None => String::new(),
+        };
+
+        Ok(MintStatusResponse {
+            minted,
+            next_token_id,
+            address_minted: ADDRESS_MINT_COUNT
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default(),
+            allowlisted: ALLOWLIST.has(deps.storage, &address),
+        })
+    }
+
+    pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::Minter {} => to_binary(&self.minter(deps)?),
+            QueryMsg::Admin {} => to_binary(&self.admin(deps)?),
+            QueryMsg::GetCollectionState {} => to_binary(&self.collection_state(deps, env)?),
+            QueryMsg::RoyaltyInfo {} => to_binary(&self.royalty_info(deps, env)?),
+            QueryMsg::MintStatus { address } => to_binary(&self.mint_status(deps, address)?),
+            QueryMsg::ContractInfo {} => to_binary(&self.contract_info(deps)?),
+            QueryMsg::NftInfo { token_id } => to_binary(&self.nft_info(deps, token_id)?),
+            QueryMsg::OwnerOf {
+                token_id,
+                include_expired,
+            } => to_binary(&self.owner_of(deps, env, token_id, include_expired.unwrap_or(false))?),
+            QueryMsg::AllNftInfo {
+                token_id,
+                include_expired,
+            } => to_binary(&self.all_nft_info(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::AllOperators {
+                owner,
+                include_expired,
+                start_after,
+                limit,
+            } => to_binary(&self.operators(
+                deps,
+                env,
+                owner,
+                include_expired.unwrap_or(false),
+                start_after,
+                limit,
+            )?),
+            QueryMsg::NumTokens {} => to_binary(&self.num_tokens(deps)?),
+            QueryMsg::Tokens {
+                owner,
+                start_after,
+                limit,
+            } => to_binary(&self.tokens(deps, owner, start_after, limit)?),
+            QueryMsg::AllTokens { start_after, limit } => {
+                to_binary(&self.all_tokens(deps, start_after, limit)?)
+            }
+            QueryMsg::Approval {
+                token_id,
+                spender,
+                include_expired,
+            } => to_binary(&self.approval(
+                deps,
+                env,
+                token_id,
+                spender,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::Approvals {
+                token_id,
+                include_expired,
+            } => to_binary(&self.approvals(deps, env, token_id, include_expired.unwrap_or(false))?),
+        }
+    }
+}
+
+fn parse_approval(item: StdResult<(Addr, cw721::Expiration)>) -> StdResult<cw721::Approval> {
+    item.map(|(spender, expires)| cw721::Approval {
+        spender: spender.to_string(),
+        expires,
+    })
+}
+
+fn humanize_approvals<T>(
+    block: &BlockInfo,
+    info: &TokenInfo<T>,
+    include_expired: bool,
+) -> Vec<cw721::Approval> {
+    info.approvals
+        .iter()
+        .filter(|apr| include_expired || !apr.is_expired(block))
+        .map(humanize_approval)
+        .collect()
+}
+
+fn humanize_approval(approval: &Approval) -> cw721::Approval {
+    cw721::Approval {
+        spender: approval.spender.to_string(),
+        expires: approval.expires,
+    }
+}
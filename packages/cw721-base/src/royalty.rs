@@ -0,0 +1,105 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use cosmwasm_std::{
+    BlockInfo, CustomMsg, Decimal, DepsMut, Env, Event, MessageInfo, Response, StdResult, Storage,
+};
+
+use crate::error::ContractError;
+use crate::msg::{PendingRoyalty, Royalty};
+use crate::state::{Cw721Contract, PENDING_ROYALTY, ROYALTY_INFO};
+
+/// Seconds a royalty increase waits before it applies, so sellers can react to it
+pub const ROYALTY_INCREASE_DELAY: u64 = 24 * 60 * 60;
+
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+{
+    /// Changes the royalty. Lowering the rate or changing recipients applies at once,
+    /// raising it is scheduled `ROYALTY_INCREASE_DELAY` seconds ahead.
+    pub fn update_royalty(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        royalty: Royalty,
+    ) -> Result<Response<C>, ContractError> {
+        self.only_admin(deps.as_ref(), &info)?;
+        royalty.validate(deps.api)?;
+
+        let current = apply_pending_royalty(deps.storage, &env.block)?;
+        let current_rate = current.map_or(Decimal::zero(), |r| r.royalty_rate);
+
+        let effective_time = if royalty.royalty_rate > current_rate {
+            let effective_time = env.block.time.seconds() + ROYALTY_INCREASE_DELAY;
+            PENDING_ROYALTY.save(
+                deps.storage,
+                &PendingRoyalty {
+                    royalty: royalty.clone(),
+                    effective_time,
+                },
+            )?;
+            effective_time
+        } else {
+            // a decrease also replaces any increase still waiting
+            PENDING_ROYALTY.remove(deps.storage);
+            ROYALTY_INFO.save(deps.storage, &Some(royalty.clone()))?;
+            env.block.time.seconds()
+        };
+
+        let event = Event::new("royalty-update")
+            .add_attribute("collection", env.contract.address)
+            .add_attribute("address", royalty.address)
+            .add_attribute("royalty_rate", royalty.royalty_rate.to_string())
+            .add_attribute("previous_rate", current_rate.to_string())
+            .add_attribute("effective_time", effective_time.to_string());
+        Ok(Response::new()
+            .add_attribute("action", "update_royalty")
+            .add_event(event))
+    }
+
+    /// Removes the royalty and any scheduled increase at once
+    pub fn remove_royalty(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        self.only_admin(deps.as_ref(), &info)?;
+
+        PENDING_ROYALTY.remove(deps.storage);
+        ROYALTY_INFO.save(deps.storage, &None)?;
+
+        let event = Event::new("royalty-remove")
+            .add_attribute("collection", env.contract.address)
+            .add_attribute("effective_time", env.block.time.seconds().to_string());
+        Ok(Response::new()
+            .add_attribute("action", "remove_royalty")
+            .add_event(event))
+    }
+}
+
+/// Royalty in effect at `block`, a scheduled increase counts once its time has come
+pub fn effective_royalty(storage: &dyn Storage, block: &BlockInfo) -> StdResult<Option<Royalty>> {
+    match PENDING_ROYALTY.may_load(storage)? {
+        Some(pending) if pending.effective_time <= block.time.seconds() => Ok(Some(pending.royalty)),
+        _ => ROYALTY_INFO.load(storage),
+    }
+}
+
+/// Moves a scheduled increase whose time has come into `ROYALTY_INFO`
+fn apply_pending_royalty(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+) -> StdResult<Option<Royalty>> {
+    let royalty = effective_royalty(storage, block)?;
+    if let Some(pending) = PENDING_ROYALTY.may_load(storage)? {
+        if pending.effective_time <= block.time.seconds() {
+            PENDING_ROYALTY.remove(storage);
+            ROYALTY_INFO.save(storage, &royalty)?;
+        }
+    }
+    Ok(royalty)
+}
@@ -2,97 +2,97 @@ use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, BlockInfo, Decimal,  Uint128};
-
-use cw721::{Expiration};
-use cw_storage_plus::{Index, IndexList, Item, MultiIndex};
-
-// pub struct Cw721Contract<'a, T, C>
-// where
-//     T: Serialize + DeserializeOwned + Clone,
-// {
-//     pub contract_info: Item<'a, ContractInfoResponse>,
-//     pub minter: Item<'a, Addr>,
-//     pub admin: Item<'a, Addr>,
-
-//     pub token_count: Item<'a, u64>,
-//     /// Stored as (granter, operator) giving operator full control over granter's account
-//     pub operators: Map<'a, (&'a Addr, &'a Addr), Expiration>,
-//     pub tokens: IndexedMap<'a, &'a str, TokenInfo<T>, TokenIndexes<'a, T>>,
-
-//     pub(crate) _custom_response: PhantomData<C>,
-// }
-
-// // This is a signal, the implementations are in other files
-// impl<'a, T, C> Cw721<T, C> for Cw721Contract<'a, T, C>
-// where
-//     T: Serialize + DeserializeOwned + Clone,
-//     C: CustomMsg,
-// {
-// }
-
-// impl<T, C> Default for Cw721Contract<'static, T, C>
-// where
-//     T: Serialize + DeserializeOwned + Clone,
-// {
-//     fn default() -> Self {
-//         Self::new(
-//             "nft_info",
-//             "minter",
-//             "admin",
-//             "num_tokens",
-//             "operators",
-//             "tokens",
-//             "tokens__owner",
-//         )
-//     }
-// }
-
-// impl<'a, T, C> Cw721Contract<'a, T, C>
-// where
-//     T: Serialize + DeserializeOwned + Clone,
-// {
-//     fn new(
-//         contract_key: &'a str,
-//         minter_key: &'a str,
-//         admin_key: &'a str,
-
-//         token_count_key: &'a str,
-//         operator_key: &'a str,
-//         tokens_key: &'a str,
-//         tokens_owner_key: &'a str,
-//     ) -> Self {
-//         let indexes = TokenIndexes {
-//             owner: MultiIndex::new(token_owner_idx, tokens_key, tokens_owner_key),
-//         };
-//         Self {
-//             contract_info: Item::new(contract_key),
-//             minter: Item::new(minter_key),
-//             admin: Item::new(admin_key),
-
-//             token_count: Item::new(token_count_key),
-//             operators: Map::new(operator_key),
-//             tokens: IndexedMap::new(tokens_key, indexes),
-//             _custom_response: PhantomData,
-//         }
-//     }
-
-//     pub fn token_count(&self, storage: &dyn Storage) -> StdResult<u64> {
-//         Ok(self.token_count.may_load(storage)?.unwrap_or_default())
-//     }
-
-//     pub fn increment_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
-//         let val = self.token_count(storage)? + 1;
-//         self.token_count.save(storage, &val)?;
-//         Ok(val)
-//     }
-
-//     pub fn decrement_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
-//         let val = self.token_count(storage)? - 1;
-//         self.token_count.save(storage, &val)?;
-//         Ok(val)
-//     }
-// }
+use std::marker::PhantomData;
+
+use cosmwasm_std::{Addr, BlockInfo, Empty, StdResult, Storage};
+
+use cw721::{ContractInfoResponse, Expiration};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+
+use crate::error::ContractError;
+use crate::msg::{
+    validate_url, CollectionInfo, MintInfo, PendingRoyalty, Royalty, MAX_DESCRIPTION_LENGTH,
+    MAX_TITLE_LENGTH,
+};
+
+pub struct Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    pub contract_info: Item<'a, ContractInfoResponse>,
+    pub minter: Item<'a, Addr>,
+    pub admin: Item<'a, Addr>,
+
+    pub token_count: Item<'a, u64>,
+    /// Stored as (granter, operator) giving operator full control over granter's account
+    pub operators: Map<'a, (&'a Addr, &'a Addr), Expiration>,
+    pub tokens: IndexedMap<'a, &'a str, TokenInfo<T>, TokenIndexes<'a, T>>,
+
+    pub(crate) _custom_response: PhantomData<C>,
+}
+
+impl<T, C> Default for Cw721Contract<'static, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    fn default() -> Self {
+        Self::new(
+            "nft_info",
+            "minter",
+            "admin",
+            "num_tokens",
+            "operators",
+            "tokens",
+            "tokens__owner",
+        )
+    }
+}
+
+impl<'a, T, C> Cw721Contract<'a, T, C>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    fn new(
+        contract_key: &'a str,
+        minter_key: &'a str,
+        admin_key: &'a str,
+
+        token_count_key: &'a str,
+        operator_key: &'a str,
+        tokens_key: &'a str,
+        tokens_owner_key: &'a str,
+    ) -> Self {
+        let indexes = TokenIndexes {
+            owner: MultiIndex::new(token_owner_idx, tokens_key, tokens_owner_key),
+        };
+        Self {
+            contract_info: Item::new(contract_key),
+            minter: Item::new(minter_key),
+            admin: Item::new(admin_key),
+
+            token_count: Item::new(token_count_key),
+            operators: Map::new(operator_key),
+            tokens: IndexedMap::new(tokens_key, indexes),
+            _custom_response: PhantomData,
+        }
+    }
+
+    pub fn token_count(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self.token_count.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn increment_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
+        let val = self.token_count(storage)? + 1;
+        self.token_count.save(storage, &val)?;
+        Ok(val)
+    }
+
+    pub fn decrement_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
+        let val = self.token_count(storage)? - 1;
+        self.token_count.save(storage, &val)?;
+        Ok(val)
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct TokenInfo<T> {
@@ -106,6 +106,12 @@ pub struct TokenInfo<T> {
     /// Metadata JSON Schema
     pub token_uri: Option<String>,
 
+    /// MIME type of the NFT media
+    pub content_type: String,
+
+    /// Block time in seconds the NFT was minted at
+    pub created_time: u64,
+
     /// You can add any custom metadata here when you extend cw721-base
     pub extension: T,
 }
@@ -145,25 +151,65 @@ pub fn token_owner_idx<T>(d: &TokenInfo<T>) -> Addr {
     d.owner.clone()
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
 pub struct Metadata {
     pub image_url: String,
-    pub minter: String
+    pub minter: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub animation_url: Option<String>,
+    /// Attributes the marketplace indexes listings and trait bids by
+    #[serde(default)]
+    pub attributes: Vec<Trait>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Royalty {
-    pub address: Addr,
-    pub royalty_rate: Decimal,
+pub struct Trait {
+    #[serde(default)]
+    pub display_type: Option<String>,
+    pub trait_type: String,
+    pub value: String,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct CollectionInfo<T> {
-    pub creator: String,
-    pub description: String,
-    pub image: String,
-    pub external_link: Option<String>,
-    pub royalty_info: Option<T>,
+pub const MAX_ATTRIBUTES: usize = 32;
+
+impl Metadata {
+    /// Validates the text lengths, the animation URL and the attributes
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.name.as_ref().map_or(0, |n| n.chars().count()) > MAX_TITLE_LENGTH {
+            return Err(ContractError::TitleTooLong {});
+        }
+        if self.description.as_ref().map_or(0, |d| d.chars().count()) > MAX_DESCRIPTION_LENGTH {
+            return Err(ContractError::DescriptionTooLong {});
+        }
+        if let Some(animation_url) = &self.animation_url {
+            validate_url(animation_url)?;
+        }
+
+        if self.attributes.len() > MAX_ATTRIBUTES {
+            return Err(ContractError::TooManyAttributes(MAX_ATTRIBUTES));
+        }
+        for attribute in &self.attributes {
+            if attribute.trait_type.is_empty() || attribute.value.is_empty() {
+                return Err(ContractError::InvalidTrait(attribute.trait_type.clone()));
+            }
+        }
+        Ok(())
+    }
 }
 
-pub const COLLECTION_INFO: Item<CollectionInfo<Royalty>> = Item::new("collection_info");
+/// Collection details returned by `GetCollectionState`
+pub const COLLECTION_INFO: Item<CollectionInfo> = Item::new("collection_info");
+pub const ROYALTY_INFO: Item<Option<Royalty>> = Item::new("royalty_info");
+pub const MINT_INFO: Item<Option<MintInfo>> = Item::new("mint_info");
+/// Royalty increase that replaces `ROYALTY_INFO` once its time has come
+pub const PENDING_ROYALTY: Item<PendingRoyalty> = Item::new("pending_royalty");
+
+/// Tokens created by the paid mint, unlike `token_count` it does not go down on burns
+pub const MINT_COUNT: Item<u64> = Item::new("mint_count");
+pub const ADDRESS_MINT_COUNT: Map<&Addr, u64> = Map::new("address_mint_count");
+/// Addresses allowed to mint in the private phase
+pub const ALLOWLIST: Map<&Addr, Empty> = Map::new("allowlist");
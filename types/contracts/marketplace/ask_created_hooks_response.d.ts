@@ -1,6 +0,0 @@
-import { Ask } from "./shared-types";
-
-export interface AskCreatedHooksResponse {
-ask: Ask
-[k: string]: unknown
-}
@@ -0,0 +1,9 @@
+import { AskHookMsg } from "./shared-types";
+
+export type AskHookExecuteMsg = ({
+ask_created_hook: AskHookMsg
+} | {
+ask_updated_hook: AskHookMsg
+} | {
+ask_deleted_hook: AskHookMsg
+})
@@ -1,4 +0,0 @@
-export interface AskHooksResponse {
-hooks: string[]
-[k: string]: unknown
-}
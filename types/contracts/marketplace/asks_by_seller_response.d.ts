@@ -1,6 +0,0 @@
-import { Ask } from "./shared-types";
-
-export interface AsksBySellerResponse {
-asks: Ask[]
-[k: string]: unknown
-}
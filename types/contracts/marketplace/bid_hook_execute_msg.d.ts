@@ -0,0 +1,9 @@
+import { BidHookMsg } from "./shared-types";
+
+export type BidHookExecuteMsg = ({
+bid_created_hook: BidHookMsg
+} | {
+bid_updated_hook: BidHookMsg
+} | {
+bid_deleted_hook: BidHookMsg
+})
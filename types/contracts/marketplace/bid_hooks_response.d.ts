@@ -1,4 +0,0 @@
-export interface BidHooksResponse {
-hooks: string[]
-[k: string]: unknown
-}
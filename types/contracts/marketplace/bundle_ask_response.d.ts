@@ -0,0 +1,6 @@
+import { BundleAsk } from "./shared-types";
+
+export interface BundleAskResponse {
+bundle?: (BundleAsk | null)
+[k: string]: unknown
+}
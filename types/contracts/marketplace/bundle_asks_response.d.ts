@@ -0,0 +1,6 @@
+import { BundleAsk } from "./shared-types";
+
+export interface BundleAsksResponse {
+bundles: BundleAsk[]
+[k: string]: unknown
+}
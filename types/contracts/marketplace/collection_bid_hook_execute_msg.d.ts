@@ -0,0 +1,9 @@
+import { CollectionBidHookMsg } from "./shared-types";
+
+export type CollectionBidHookExecuteMsg = ({
+collection_bid_created_hook: CollectionBidHookMsg
+} | {
+collection_bid_updated_hook: CollectionBidHookMsg
+} | {
+collection_bid_deleted_hook: CollectionBidHookMsg
+})
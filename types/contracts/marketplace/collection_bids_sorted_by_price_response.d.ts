@@ -1,6 +0,0 @@
-import { Bid } from "./shared-types";
-
-export interface CollectionBidsSortedByPriceResponse {
-bids: Bid[]
-[k: string]: unknown
-}
@@ -0,0 +1,6 @@
+import { CollectionConfig } from "./shared-types";
+
+export interface CollectionConfigResponse {
+config?: (CollectionConfig | null)
+[k: string]: unknown
+}
@@ -0,0 +1,6 @@
+import { CollectionStatus } from "./shared-types";
+
+export interface CollectionStatusResponse {
+status: CollectionStatus
+[k: string]: unknown
+}
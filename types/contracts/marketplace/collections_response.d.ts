@@ -1,6 +1,6 @@
-import { Addr } from "./shared-types";
+import { CollectionStatus } from "./shared-types";
 
 export interface CollectionsResponse {
-collections: Addr[]
+collections: CollectionStatus[]
 [k: string]: unknown
 }
@@ -1,6 +0,0 @@
-import { Ask } from "./shared-types";
-
-export interface CurrentAskResponse {
-ask?: (Ask | null)
-[k: string]: unknown
-}
@@ -0,0 +1,6 @@
+import { Custody } from "./shared-types";
+
+export interface CustodyResponse {
+custody?: (Custody | null)
+[k: string]: unknown
+}
@@ -0,0 +1,36 @@
+import { Uint128 } from "./shared-types";
+
+/**
+ * Native funds the contract holds for others, against its balance
+ */
+export interface EscrowSummaryResponse {
+/**
+ * Highest bids of live and ended, unsettled auctions
+ */
+auction_escrow: Uint128
+balance: Uint128
+/**
+ * Highest bids of bundle auctions
+ */
+bundle_escrow: Uint128
+/**
+ * True until `MigrateRecords` has counted the escrow of records stored by earlier versions, the liabilities are incomplete until then
+ */
+counting: boolean
+/**
+ * Liabilities not covered by the balance, should always be zero
+ */
+deficit: Uint128
+/**
+ * Sum of all escrows
+ */
+liabilities: Uint128
+offer_escrow: Uint128
+/**
+ * Balance above the liabilities, the fees collected so far
+ */
+surplus: Uint128
+swap_escrow: Uint128
+trait_bid_escrow: Uint128
+[k: string]: unknown
+}
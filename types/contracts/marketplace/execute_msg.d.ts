@@ -1,97 +1,227 @@
-import { Coin, SaleType, Timestamp } from "./shared-types";
+import { AskInfo, Binary, Coin, CollectionConfig, Decimal, SaleType, SignedOrder, Uint128 } from "./shared-types";
 
 export type ExecuteMsg = ({
-set_ask: {
-collection: string
-expires: Timestamp
-finders_fee_bps?: (number | null)
-funds_recipient?: (string | null)
-price: Coin
-reserve_for?: (string | null)
-sale_type: SaleType
-token_id: number
-[k: string]: unknown
-}
+receive_nft: Cw721ReceiveMsg
 } | {
 remove_ask: {
 collection: string
-token_id: number
+token_id: string
 [k: string]: unknown
 }
 } | {
 update_ask_price: {
 collection: string
 price: Coin
-token_id: number
+token_id: string
 [k: string]: unknown
 }
 } | {
 set_bid: {
 collection: string
-expires: Timestamp
-finder?: (string | null)
-finders_fee_bps?: (number | null)
-sale_type: SaleType
-token_id: number
+token_id: string
 [k: string]: unknown
 }
 } | {
-remove_bid: {
+accept_bid: {
 collection: string
-token_id: number
+token_id: string
 [k: string]: unknown
 }
 } | {
-accept_bid: {
-bidder: string
+batch_set_bid: {
+atomic: boolean
+bids: BatchBid[]
+[k: string]: unknown
+}
+} | {
+sweep_floor: {
+collection: string
+max_items: number
+max_total: Uint128
+max_unit_price: Uint128
+[k: string]: unknown
+}
+} | {
+set_bundle_ask: {
+allocations: BundleShare[]
+expires: number
+funds_recipient?: (string | null)
+items: NftRef[]
+price: Coin
+sale_type: SaleType
+[k: string]: unknown
+}
+} | {
+set_bundle_bid: {
+id: number
+[k: string]: unknown
+}
+} | {
+remove_bundle_ask: {
+id: number
+[k: string]: unknown
+}
+} | {
+accept_bundle_bid: {
+id: number
+[k: string]: unknown
+}
+} | {
+create_swap: {
+counterparty?: (string | null)
+expires: number
+offered: NftRef[]
+requested: NftRef[]
+[k: string]: unknown
+}
+} | {
+accept_swap: {
+id: number
+[k: string]: unknown
+}
+} | {
+cancel_swap: {
+id: number
+[k: string]: unknown
+}
+} | {
+set_offer: {
 collection: string
-finder?: (string | null)
-token_id: number
+expires: number
+token_id: string
 [k: string]: unknown
 }
 } | {
-set_collection_bid: {
+remove_offer: {
 collection: string
-expires: Timestamp
-finders_fee_bps?: (number | null)
+token_id: string
 [k: string]: unknown
 }
 } | {
-remove_collection_bid: {
+accept_offer: {
+buyer: string
 collection: string
+token_id: string
 [k: string]: unknown
 }
 } | {
-accept_collection_bid: {
-bidder: string
+counter_offer: {
+buyer: string
 collection: string
-finder?: (string | null)
-token_id: number
+price: Uint128
+token_id: string
 [k: string]: unknown
 }
 } | {
-sync_ask: {
+accept_counter_offer: {
 collection: string
-token_id: number
+token_id: string
 [k: string]: unknown
 }
 } | {
-remove_stale_ask: {
+set_trait_bid: {
 collection: string
-token_id: number
+expires: number
+trait_type: string
+value: string
 [k: string]: unknown
 }
 } | {
-remove_stale_bid: {
-bidder: string
+remove_trait_bid: {
+id: number
+[k: string]: unknown
+}
+} | {
+set_order_key: {
+pubkey: Binary
+[k: string]: unknown
+}
+} | {
+cancel_signed_orders: {
+nonces: number[]
+[k: string]: unknown
+}
+} | {
+fill_signed_order: {
+order: SignedOrder
+/**
+ * 64 byte compact secp256k1 signature of the SHA-256 of `OrderSignDoc`
+ */
+signature: Binary
+[k: string]: unknown
+}
+} | {
+batch_set_ask: {
+asks: AskInfo[]
+[k: string]: unknown
+}
+} | {
+batch_remove_ask: {
+asks: NftRef[]
+[k: string]: unknown
+}
+} | {
+batch_update_ask_price: {
+updates: AskPriceUpdate[]
+[k: string]: unknown
+}
+} | {
+set_collection_config: {
 collection: string
-token_id: number
+config: CollectionConfig
 [k: string]: unknown
 }
 } | {
-remove_stale_collection_bid: {
-bidder: string
+migrate_records: {
+limit?: (number | null)
+[k: string]: unknown
+}
+} | {
+rescue_nft: {
 collection: string
+recipient: string
+token_id: string
 [k: string]: unknown
 }
 })
+
+/**
+ * Cw721ReceiveMsg should be de/serialized under `Receive()` variant in a ExecuteMsg
+ */
+export interface Cw721ReceiveMsg {
+msg: Binary
+sender: string
+token_id: string
+[k: string]: unknown
+}
+/**
+ * A single bid of a `BatchSetBid`
+ */
+export interface BatchBid {
+collection: string
+price: Uint128
+token_id: string
+[k: string]: unknown
+}
+/**
+ * Share of a bundle price declared for one of its collections
+ */
+export interface BundleShare {
+collection: string
+share: Decimal
+[k: string]: unknown
+}
+export interface NftRef {
+collection: string
+token_id: string
+[k: string]: unknown
+}
+/**
+ * A single price update of a `BatchUpdateAskPrice`
+ */
+export interface AskPriceUpdate {
+collection: string
+price: Coin
+token_id: string
+[k: string]: unknown
+}
@@ -0,0 +1,7 @@
+/**
+ * Response of the hook queries, mirroring the one `cw_controllers::Hooks` returns
+ */
+export interface HooksResponse {
+hooks: string[]
+[k: string]: unknown
+}
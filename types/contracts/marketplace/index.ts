@@ -1,52 +1,94 @@
+// dedup emptied this file
+// export * from "./ask";
 export * from "./ask_count_response";
-export * from "./ask_created_hooks_response";
-export * from "./ask_filled_hooks_response";
-export * from "./ask_hooks_response";
+export * from "./ask_hook_execute_msg";
+// dedup emptied this file
+// export * from "./ask_hook_msg";
+// dedup emptied this file
+// export * from "./ask_info";
 // dedup emptied this file
 // export * from "./ask_offset";
-export * from "./ask_response";
 // dedup emptied this file
-// export * from "./ask";
-export * from "./asks_by_seller_response";
+// export * from "./ask_offset_bid_count";
+// dedup emptied this file
+// export * from "./ask_offset_sell_price";
+export * from "./ask_response";
 export * from "./asks_response";
-export * from "./asks_sorted_by_price_response";
-export * from "./bid_hooks_response";
+// dedup emptied this file
+// export * from "./bid";
+export * from "./bid_hook_execute_msg";
+// dedup emptied this file
+// export * from "./bid_hook_msg";
 // dedup emptied this file
 // export * from "./bid_offset";
 export * from "./bid_response";
-// dedup emptied this file
-// export * from "./bid";
-export * from "./bids_by_bidder_response";
-export * from "./bids_by_bidder_sorted_by_expiration_response";
 export * from "./bids_response";
-export * from "./bids_sorted_by_price_response";
 // dedup emptied this file
-// export * from "./collection_bid_offset";
-export * from "./collection_bid_response";
+// export * from "./bundle_ask";
+export * from "./bundle_ask_response";
+export * from "./bundle_asks_response";
 // dedup emptied this file
 // export * from "./collection_bid";
-export * from "./collection_bids_by_bidder_response";
-export * from "./collection_bids_by_bidder_sorted_by_expiration_response";
-export * from "./collection_bids_sorted_by_price_response";
+export * from "./collection_bid_hook_execute_msg";
+// dedup emptied this file
+// export * from "./collection_bid_hook_msg";
+export * from "./collection_config_response";
 // dedup emptied this file
 // export * from "./collection_offset";
+export * from "./collection_status_response";
 export * from "./collections_response";
-export * from "./current_ask_response";
+// dedup emptied this file
+// export * from "./custody";
+export * from "./custody_response";
+export * from "./escrow_summary_response";
 export * from "./execute_msg";
+export * from "./hooks_response";
 export * from "./instantiate_msg";
-export * from "./listed_collections_response";
 export * from "./marketplace_contract";
+export * from "./migrate_msg";
+export * from "./migration_status_response";
+// dedup emptied this file
+// export * from "./offer";
+export * from "./offer_hook_execute_msg";
 // dedup emptied this file
-// export * from "./param_response";
+// export * from "./offer_hook_msg";
+// dedup emptied this file
+// export * from "./offer_offset";
+export * from "./offer_response";
+export * from "./offers_response";
+export * from "./order_digest_response";
+export * from "./order_key_response";
+export * from "./order_nonce_response";
+export * from "./order_sign_doc";
 export * from "./params_response";
+export * from "./pause_status_response";
+export * from "./payout_simulation_response";
 export * from "./query_msg";
-export * from "./reverse_asks_sorted_by_price_response";
-export * from "./reverse_bids_sorted_by_price_response";
-export * from "./reverse_collection_bids_sorted_by_price_response";
-export * from "./sale_finalized_hook_msg";
-export * from "./sale_finalized_hooks_response";
-export * from "./sale_hooks_response";
+export * from "./receive_nft_msg";
+export * from "./sale_hook_execute_msg";
+// dedup emptied this file
+// export * from "./sale_hook_msg";
+// dedup emptied this file
+// export * from "./sale_hook_msg_v2";
 export * from "./shared-types";
+// dedup emptied this file
+// export * from "./signed_order";
 export * from "./sudo_msg";
 // dedup emptied this file
 // export * from "./sudo_params";
+// dedup emptied this file
+// export * from "./swap";
+export * from "./swap_hook_execute_msg";
+// dedup emptied this file
+// export * from "./swap_hook_msg";
+export * from "./swap_response";
+export * from "./swaps_response";
+// dedup emptied this file
+// export * from "./trait_bid";
+export * from "./trait_bid_hook_execute_msg";
+// dedup emptied this file
+// export * from "./trait_bid_hook_msg";
+// dedup emptied this file
+// export * from "./trait_bid_offset";
+export * from "./trait_bid_response";
+export * from "./trait_bids_response";
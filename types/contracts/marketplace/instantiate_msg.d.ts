@@ -1,4 +1,4 @@
-import { Duration, ExpiryRange, Uint128 } from "./shared-types";
+import { ExpiryRange, Uint128 } from "./shared-types";
 
 export interface InstantiateMsg {
 /**
@@ -9,20 +9,17 @@ ask_expiry: ExpiryRange
  * Valid time range for Bids (min, max) in seconds
  */
 bid_expiry: ExpiryRange
+create_collection_address: string
 /**
- * Stale bid removal reward
+ * Receives the protocol fee, no fee is charged while unset
  */
-bid_removal_reward_bps: number
+fee_recipient?: (string | null)
 /**
  * Listing fee to reduce spam
  */
 listing_fee: Uint128
 /**
- * Max basis points for the finders fee
- */
-max_finders_fee_bps: number
-/**
- * Min value for bids and asks
+ * Max basis points for the finders fee Min value for bids and asks
  */
 min_price: Uint128
 /**
@@ -34,11 +31,7 @@ operators: string[]
  */
 sale_hook?: (string | null)
 /**
- * Duration after expiry when a bid becomes stale (in seconds)
- */
-stale_bid_duration: Duration
-/**
- * Fair Burn fee for winning bids 0.25% = 25, 0.5% = 50, 1% = 100, 2.5% = 250
+ * Protocol fee for winning bids 0.25% = 25, 0.5% = 50, 1% = 100, 2.5% = 250
  */
 trading_fee_bps: number
 [k: string]: unknown
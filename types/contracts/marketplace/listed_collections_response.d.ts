@@ -1,6 +0,0 @@
-import { Addr } from "./shared-types";
-
-export interface ListedCollectionsResponse {
-collections: Addr[]
-[k: string]: unknown
-}
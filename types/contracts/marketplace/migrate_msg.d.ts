@@ -0,0 +1,3 @@
+export interface MigrateMsg {
+[k: string]: unknown
+}
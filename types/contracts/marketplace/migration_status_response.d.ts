@@ -0,0 +1,53 @@
+import { Addr } from "./shared-types";
+
+/**
+ * Progress of rewriting stored asks and bids into their current schema after a migration, then of counting the funds escrowed by every kind of record into `ESCROW_TOTALS`
+ */
+export type RecordMigration = ({
+asks: {
+start_after?: ([Addr, string] | null)
+[k: string]: unknown
+}
+} | {
+bids: {
+start_after?: ([Addr, string, Addr] | null)
+[k: string]: unknown
+}
+} | {
+bundles: {
+start_after?: (number | null)
+[k: string]: unknown
+}
+} | {
+offers: {
+start_after?: ([Addr, string, Addr] | null)
+[k: string]: unknown
+}
+} | {
+swaps: {
+start_after?: (number | null)
+[k: string]: unknown
+}
+} | {
+trait_bids: {
+start_after?: (number | null)
+[k: string]: unknown
+}
+})
+
+export interface MigrationStatusResponse {
+records?: (RecordMigration | null)
+version: ContractVersion
+[k: string]: unknown
+}
+export interface ContractVersion {
+/**
+ * contract is the crate name of the implementing contract, eg. `crate:cw20-base` we will use other prefixes for other languages, and their standard global namespacing
+ */
+contract: string
+/**
+ * version is any string that this implementation knows. It may be simple counter "1", "2". or semantic version on release tags "v0.7.0", or some custom feature flag list. the only code that needs to understand the version parsing is code that knows how to migrate from the given contract (and is tied to it's implementation somehow)
+ */
+version: string
+[k: string]: unknown
+}
@@ -0,0 +1,9 @@
+import { OfferHookMsg } from "./shared-types";
+
+export type OfferHookExecuteMsg = ({
+offer_created_hook: OfferHookMsg
+} | {
+offer_updated_hook: OfferHookMsg
+} | {
+offer_deleted_hook: OfferHookMsg
+})
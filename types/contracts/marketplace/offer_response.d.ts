@@ -0,0 +1,6 @@
+import { Offer } from "./shared-types";
+
+export interface OfferResponse {
+offer?: (Offer | null)
+[k: string]: unknown
+}
@@ -0,0 +1,6 @@
+import { Offer } from "./shared-types";
+
+export interface OffersResponse {
+offers: Offer[]
+[k: string]: unknown
+}
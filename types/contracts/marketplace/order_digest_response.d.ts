@@ -0,0 +1,6 @@
+import { Binary } from "./shared-types";
+
+export interface OrderDigestResponse {
+digest: Binary
+[k: string]: unknown
+}
@@ -0,0 +1,6 @@
+import { Binary } from "./shared-types";
+
+export interface OrderKeyResponse {
+pubkey?: (Binary | null)
+[k: string]: unknown
+}
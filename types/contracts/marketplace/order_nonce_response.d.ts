@@ -0,0 +1,9 @@
+/**
+ * Why a signed order nonce can no longer be filled
+ */
+export type NonceStatus = ("used" | "cancelled")
+
+export interface OrderNonceResponse {
+status?: (NonceStatus | null)
+[k: string]: unknown
+}
@@ -0,0 +1,11 @@
+import { SignedOrder } from "./shared-types";
+
+/**
+ * What a seller signs for an order, binding it to one marketplace on one chain. The signed message is the SHA-256 of its JSON serialization.
+ */
+export interface OrderSignDoc {
+chain_id: string
+marketplace: string
+order: SignedOrder
+[k: string]: unknown
+}
@@ -0,0 +1,26 @@
+export interface PauseStatusResponse {
+pause: PauseState
+[k: string]: unknown
+}
+/**
+ * Emergency switches, each halting one group of `execute_*` handlers. Pausing listing, bidding or settlement never blocks sellers from removing their asks, which refunds the highest bidder, nor buyers from withdrawing their offers. Outbid bidders are refunded as soon as they are outbid.
+ */
+export interface PauseState {
+/**
+ * Placing bids on auctions
+ */
+bidding: boolean
+/**
+ * Creating asks and updating their price, including counter offers and order keys
+ */
+listing: boolean
+/**
+ * Removing asks, withdrawing offers and cancelling signed orders, only meant for when the removal path itself misbehaves
+ */
+removal: boolean
+/**
+ * Fixed price purchases and accepting winning bids
+ */
+settlement: boolean
+[k: string]: unknown
+}
@@ -0,0 +1,22 @@
+import { Addr, RoyaltyPayout, Uint128 } from "./shared-types";
+
+/**
+ * Where the royalty of a sale was looked up
+ */
+export type RoyaltySource = ("cw2981" | "collection_state" | "none")
+
+/**
+ * Settlement breakdown, `protocol_fee + royalties + seller_amount == price`
+ */
+export interface PayoutSimulationResponse {
+/**
+ * Receives the protocol fee, which is zero while no recipient is set
+ */
+fee_recipient?: (Addr | null)
+price: Uint128
+protocol_fee: Uint128
+royalties: RoyaltyPayout[]
+royalty_source: RoyaltySource
+seller_amount: Uint128
+[k: string]: unknown
+}
@@ -1,4 +1,4 @@
-import { AskOffset, BidOffset, CollectionBidOffset, CollectionOffset } from "./shared-types";
+import { AskOffset, AskOffsetBidCount, AskOffsetSellPrice, BidOffset, CollectionOffset, OfferOffset, SignedOrder, TraitBidOffset, Uint128 } from "./shared-types";
 
 export type QueryMsg = ({
 collections: {
@@ -7,31 +7,44 @@ start_after?: (string | null)
 [k: string]: unknown
 }
 } | {
+registered_collections: {
+limit?: (number | null)
+start_after?: (string | null)
+[k: string]: unknown
+}
+} | {
+collection_status: {
+collection: string
+[k: string]: unknown
+}
+} | {
+collection_config: {
+collection: string
+[k: string]: unknown
+}
+} | {
 ask: {
 collection: string
-token_id: number
+token_id: string
 [k: string]: unknown
 }
 } | {
 asks: {
 collection: string
-include_inactive?: (boolean | null)
 limit?: (number | null)
-start_after?: (number | null)
+start_after?: (string | null)
 [k: string]: unknown
 }
 } | {
 reverse_asks: {
 collection: string
-include_inactive?: (boolean | null)
 limit?: (number | null)
-start_before?: (number | null)
+start_before?: (string | null)
 [k: string]: unknown
 }
 } | {
 asks_sorted_by_price: {
 collection: string
-include_inactive?: (boolean | null)
 limit?: (number | null)
 start_after?: (AskOffset | null)
 [k: string]: unknown
@@ -39,19 +52,56 @@ start_after?: (AskOffset | null)
 } | {
 reverse_asks_sorted_by_price: {
 collection: string
-include_inactive?: (boolean | null)
 limit?: (number | null)
 start_before?: (AskOffset | null)
 [k: string]: unknown
 }
 } | {
+asks_sorted_by_bid_count: {
+limit?: (number | null)
+start_after?: (AskOffsetBidCount | null)
+[k: string]: unknown
+}
+} | {
+asks_sorted_by_sell_price: {
+limit?: (number | null)
+start_after?: (AskOffsetSellPrice | null)
+[k: string]: unknown
+}
+} | {
+asks_sorted_by_expiration: {
+limit?: (number | null)
+[k: string]: unknown
+}
+} | {
+reverse_sorted_by_sell_price: {
+limit?: (number | null)
+start_after?: (AskOffsetSellPrice | null)
+[k: string]: unknown
+}
+} | {
+asks_by_trait: {
+collection: string
+limit?: (number | null)
+start_after?: (string | null)
+trait_type: string
+value: string
+[k: string]: unknown
+}
+} | {
+asks_sorted_by_content_type: {
+content_type: string
+limit?: (number | null)
+start_after?: (CollectionOffset | null)
+[k: string]: unknown
+}
+} | {
 ask_count: {
 collection: string
 [k: string]: unknown
 }
 } | {
 asks_by_seller: {
-include_inactive?: (boolean | null)
 limit?: (number | null)
 seller: string
 start_after?: (CollectionOffset | null)
@@ -61,7 +111,7 @@ start_after?: (CollectionOffset | null)
 bid: {
 bidder: string
 collection: string
-token_id: number
+token_id: string
 [k: string]: unknown
 }
 } | {
@@ -72,18 +122,40 @@ start_after?: (CollectionOffset | null)
 [k: string]: unknown
 }
 } | {
-bids_by_bidder_sorted_by_expiration: {
-bidder: string
+bids: {
+collection: string
 limit?: (number | null)
-start_after?: (CollectionOffset | null)
+start_after?: (string | null)
+token_id: string
 [k: string]: unknown
 }
 } | {
-bids: {
+offer: {
+buyer: string
+collection: string
+token_id: string
+[k: string]: unknown
+}
+} | {
+offers: {
 collection: string
 limit?: (number | null)
 start_after?: (string | null)
-token_id: number
+token_id: string
+[k: string]: unknown
+}
+} | {
+offers_by_buyer: {
+buyer: string
+limit?: (number | null)
+start_after?: (CollectionOffset | null)
+[k: string]: unknown
+}
+} | {
+offers_by_seller: {
+limit?: (number | null)
+seller: string
+start_after?: (OfferOffset | null)
 [k: string]: unknown
 }
 } | {
@@ -101,53 +173,137 @@ start_before?: (BidOffset | null)
 [k: string]: unknown
 }
 } | {
-collection_bid: {
-bidder: string
+ask_hooks: {
+[k: string]: unknown
+}
+} | {
+bid_hooks: {
+[k: string]: unknown
+}
+} | {
+sale_hooks: {
+[k: string]: unknown
+}
+} | {
+sale_hooks_v2: {
+[k: string]: unknown
+}
+} | {
+swap_hooks: {
+[k: string]: unknown
+}
+} | {
+offer_hooks: {
+[k: string]: unknown
+}
+} | {
+trait_bid_hooks: {
+[k: string]: unknown
+}
+} | {
+trait_bid: {
+id: number
+[k: string]: unknown
+}
+} | {
+trait_bids_sorted_by_price: {
 collection: string
+limit?: (number | null)
+start_after?: (TraitBidOffset | null)
+trait_type: string
+value: string
 [k: string]: unknown
 }
 } | {
-collection_bids_by_bidder: {
-bidder: string
+reverse_trait_bids_sorted_by_price: {
+collection: string
 limit?: (number | null)
-start_after?: (CollectionOffset | null)
+start_before?: (TraitBidOffset | null)
+trait_type: string
+value: string
 [k: string]: unknown
 }
 } | {
-collection_bids_by_bidder_sorted_by_expiration: {
+trait_bids_by_bidder: {
 bidder: string
 limit?: (number | null)
-start_after?: (CollectionBidOffset | null)
+start_after?: (number | null)
 [k: string]: unknown
 }
 } | {
-collection_bids_sorted_by_price: {
-collection: string
+swap: {
+id: number
+[k: string]: unknown
+}
+} | {
+swaps_by_proposer: {
+limit?: (number | null)
+proposer: string
+start_after?: (number | null)
+[k: string]: unknown
+}
+} | {
+swaps_by_counterparty: {
+counterparty: string
 limit?: (number | null)
-start_after?: (CollectionBidOffset | null)
+start_after?: (number | null)
 [k: string]: unknown
 }
 } | {
-reverse_collection_bids_sorted_by_price: {
-collection: string
+bundle_ask: {
+id: number
+[k: string]: unknown
+}
+} | {
+bundle_asks_by_seller: {
 limit?: (number | null)
-start_before?: (CollectionBidOffset | null)
+seller: string
+start_after?: (number | null)
 [k: string]: unknown
 }
 } | {
-ask_hooks: {
+order_key: {
+seller: string
 [k: string]: unknown
 }
 } | {
-bid_hooks: {
+order_nonce: {
+nonce: number
+seller: string
 [k: string]: unknown
 }
 } | {
-sale_hooks: {
+order_digest: {
+order: SignedOrder
 [k: string]: unknown
 }
 } | {
 params: {
 [k: string]: unknown
 }
+} | {
+migration_status: {
+[k: string]: unknown
+}
+} | {
+pause_status: {
+[k: string]: unknown
+}
+} | {
+payout_simulation: {
+collection: string
+price: Uint128
+token_id: string
+[k: string]: unknown
+}
+} | {
+custody: {
+collection: string
+token_id: string
+[k: string]: unknown
+}
+} | {
+escrow_summary: {
+[k: string]: unknown
+}
 })
@@ -0,0 +1,24 @@
+import { AskInfo } from "./shared-types";
+
+/**
+ * Payload of the `Cw721ReceiveMsg` sent along with an NFT, tagged with its version. A bare `AskInfo`, the payload of earlier versions, is still read as a v1 `SetAsk`.
+ */
+export type ReceiveNftMsg = {
+v1: ReceiveNftMsgV1
+}
+/**
+ * Offers and auction bids are made on asks whose NFT the marketplace already holds, so they are accepted with `ExecuteMsg::AcceptOffer` and `ExecuteMsg::AcceptBid` instead. Bids on a whole collection are trait bids, accepted with `AcceptTraitBid`.
+ */
+export type ReceiveNftMsgV1 = ({
+set_ask: AskInfo
+} | {
+accept_swap: {
+id: number
+[k: string]: unknown
+}
+} | {
+accept_trait_bid: {
+id: number
+[k: string]: unknown
+}
+})
@@ -1,6 +0,0 @@
-import { Bid } from "./shared-types";
-
-export interface ReverseBidsSortedByPriceResponse {
-bids: Bid[]
-[k: string]: unknown
-}
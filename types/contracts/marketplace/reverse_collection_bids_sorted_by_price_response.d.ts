@@ -1,6 +0,0 @@
-import { Bid } from "./shared-types";
-
-export interface ReverseCollectionBidsSortedByPriceResponse {
-bids: Bid[]
-[k: string]: unknown
-}
@@ -0,0 +1,7 @@
+import { SaleHookMsg, SaleHookMsgV2 } from "./shared-types";
+
+export type SaleHookExecuteMsg = ({
+sale_hook: SaleHookMsg
+} | {
+sale_hook_v2: SaleHookMsgV2
+})
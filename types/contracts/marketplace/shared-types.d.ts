@@ -1,3 +1,17 @@
+/**
+ * A thin wrapper around u128 that is using strings for JSON encoding/decoding, such that the full u128 range can be used for clients that convert JSON numbers to floats, like JavaScript and jq.
+ *
+ * # Examples
+ *
+ * Use `from` to create instances of this and `u128` to get the value out:
+ *
+ * ``` # use cosmwasm_std::Uint128; let a = Uint128::from(123u128); assert_eq!(a.u128(), 123);
+ *
+ * let b = Uint128::from(42u64); assert_eq!(b.u128(), 42);
+ *
+ * let c = Uint128::from(70u32); assert_eq!(c.u128(), 70); ```
+ */
+export type Uint128 = string;
 /**
  * A human readable address.
  *
@@ -32,36 +46,67 @@ export type Timestamp = Uint64;
  * let b = Uint64::from(70u32); assert_eq!(b.u64(), 70); ```
  */
 export type Uint64 = string;
-/**
- * A thin wrapper around u128 that is using strings for JSON encoding/decoding, such that the full u128 range can be used for clients that convert JSON numbers to floats, like JavaScript and jq.
- *
- * # Examples
- *
- * Use `from` to create instances of this and `u128` to get the value out:
- *
- * ``` # use cosmwasm_std::Uint128; let a = Uint128::from(123u128); assert_eq!(a.u128(), 123);
- *
- * let b = Uint128::from(42u64); assert_eq!(b.u128(), 42);
- *
- * let c = Uint128::from(70u32); assert_eq!(c.u128(), 70); ```
- */
-export type Uint128 = string;
 export type SaleType = ("fixed_price" | "auction");
 /**
  * Represents an ask on the marketplace
  */
 export interface Ask {
     [k: string]: unknown;
+    bid_count: Uint128;
     collection: Addr;
+    content_type: string;
     expires_at: Timestamp;
-    finders_fee_bps?: (number | null);
     funds_recipient?: (Addr | null);
-    is_active: boolean;
+    img_url: string;
+    max_bid?: (Uint128 | null);
+    max_bidder?: (Addr | null);
     price: Uint128;
-    reserve_for?: (Addr | null);
     sale_type: SaleType;
     seller: Addr;
-    token_id: number;
+    token_id: string;
+    /**
+     * Traits of the NFT when it was listed, indexed for `AsksByTrait`
+     */
+    traits?: Trait[];
+}
+/**
+ * An NFT attribute, like `Trait` of cw721-base
+ */
+export interface Trait {
+    [k: string]: unknown;
+    display_type?: (string | null);
+    trait_type: string;
+    value: string;
+}
+export interface AskHookMsg {
+    [k: string]: unknown;
+    ask: Ask;
+}
+/**
+ * Listing of an NFT sent to the marketplace
+ */
+export interface AskInfo {
+    [k: string]: unknown;
+    /**
+     * Must be the collection sending the NFT
+     */
+    collection: Addr;
+    /**
+     * Seconds from now the ask expires in
+     */
+    expires: number;
+    funds_recipient?: (Addr | null);
+    price: Coin;
+    sale_type: SaleType;
+    /**
+     * Must be the token sent
+     */
+    token_id: string;
+}
+export interface Coin {
+    [k: string]: unknown;
+    amount: Uint128;
+    denom: string;
 }
 /**
  * Offset for ask pagination
@@ -69,7 +114,35 @@ export interface Ask {
 export interface AskOffset {
     [k: string]: unknown;
     price: Uint128;
-    token_id: number;
+    token_id: string;
+}
+export interface AskOffsetBidCount {
+    [k: string]: unknown;
+    bid_count: Uint128;
+    collection: string;
+    token_id: string;
+}
+export interface AskOffsetSellPrice {
+    [k: string]: unknown;
+    collection: string;
+    price: Uint128;
+    token_id: string;
+}
+/**
+ * Represents a bid (offer) on the marketplace
+ */
+export interface Bid {
+    [k: string]: unknown;
+    active: boolean;
+    bidder: Addr;
+    collection: Addr;
+    price: Uint128;
+    time: Timestamp;
+    token_id: string;
+}
+export interface BidHookMsg {
+    [k: string]: unknown;
+    bid: Bid;
 }
 /**
  * Offset for bid pagination
@@ -78,28 +151,83 @@ export interface BidOffset {
     [k: string]: unknown;
     bidder: Addr;
     price: Uint128;
-    token_id: number;
+    token_id: string;
 }
 /**
- * Represents a bid (offer) on the marketplace
+ * A fixed-point decimal value with 18 fractional digits, i.e. Decimal(1_000_000_000_000_000_000) == 1.0
+ *
+ * The greatest possible value that can be represented is 340282366920938463463.374607431768211455 (which is (2^128 - 1) / 10^18)
  */
-export interface Bid {
+export type Decimal = string;
+/**
+ * Represents an ask selling several NFTs together for one price
+ */
+export interface BundleAsk {
+    [k: string]: unknown;
+    allocations: BundleAllocation[];
+    bid_count: Uint128;
+    expires_at: Timestamp;
+    funds_recipient?: (Addr | null);
+    id: number;
+    items: NftItem[];
+    max_bid?: (Uint128 | null);
+    max_bidder?: (Addr | null);
+    price: Uint128;
+    sale_type: SaleType;
+    seller: Addr;
+}
+/**
+ * Share of a bundle's price attributed to one of its collections. Protocol fees and royalties of each collection are computed on its share.
+ */
+export interface BundleAllocation {
+    [k: string]: unknown;
+    collection: Addr;
+    share: Decimal;
+}
+/**
+ * An NFT traded as part of a bundle ask or swap
+ */
+export interface NftItem {
+    [k: string]: unknown;
+    collection: Addr;
+    token_id: string;
+}
+/**
+ * Represents a bid (offer) across an entire collection in the marketplace
+ */
+export interface CollectionBid {
     [k: string]: unknown;
     bidder: Addr;
     collection: Addr;
     expires_at: Timestamp;
-    finders_fee_bps?: (number | null);
     price: Uint128;
-    token_id: number;
+}
+export interface CollectionBidHookMsg {
+    [k: string]: unknown;
+    collection_bid: CollectionBid;
 }
 /**
- * Offset for collection bid pagination
+ * Per-collection overrides of the global `SudoParams`, set by governance or the collection admin
  */
-export interface CollectionBidOffset {
+export interface CollectionConfig {
     [k: string]: unknown;
-    bidder: string;
-    collection: string;
-    price: Uint128;
+    ask_expiry?: (ExpiryRange | null);
+    bid_expiry?: (ExpiryRange | null);
+    listing_fee?: (Uint128 | null);
+    min_price?: (Uint128 | null);
+    /**
+     * Discount on the protocol fee in basis points, only settable by governance
+     */
+    protocol_fee_discount_bps?: (number | null);
+    /**
+     * Sale types asks in this collection may use, all when unset
+     */
+    sale_types?: (SaleType[] | null);
+}
+export interface ExpiryRange {
+    [k: string]: unknown;
+    max: number;
+    min: number;
 }
 /**
  * Offset for collection pagination
@@ -107,23 +235,204 @@ export interface CollectionBidOffset {
 export interface CollectionOffset {
     [k: string]: unknown;
     collection: string;
-    token_id: number;
+    token_id: string;
 }
-export interface Coin {
+/**
+ * How a collection was (or was not) verified for listing
+ */
+export type CollectionVerification = ("allowed" | "factory_verified" | "unverified" | "denied" | "disabled");
+export interface CollectionStatus {
+    [k: string]: unknown;
+    collection: Addr;
+    reason?: (string | null);
+    verification: CollectionVerification;
+}
+/**
+ * Who sent an NFT held for an ask, kept while the marketplace holds the NFT
+ */
+export interface Custody {
+    [k: string]: unknown;
+    /**
+     * When the NFT was listed, or when the 1.2.0 record migration reached an older ask
+     */
+    deposited_at: Timestamp;
+    depositor: Addr;
+}
+/**
+ * Binary is a wrapper around Vec<u8> to add base64 de/serialization with serde. It also adds some helper methods to help encode inline.
+ *
+ * This is only needed as serde-json-{core,wasm} has a horrible encoding for Vec<u8>
+ */
+export type Binary = string;
+/**
+ * A fixed price sale signed off-chain by the seller
+ */
+export interface SignedOrder {
+    [k: string]: unknown;
+    collection: string;
+    expires_at: Timestamp;
+    nonce: number;
+    price: Coin;
+    seller: string;
+    token_id: string;
+}
+/**
+ * Represents an escrowed offer below the price of a fixed price ask
+ */
+export interface Offer {
+    [k: string]: unknown;
+    buyer: Addr;
+    collection: Addr;
+    /**
+     * Private price the seller offered back, only this buyer can fill it
+     */
+    counter_price?: (Uint128 | null);
+    expires_at: Timestamp;
+    price: Uint128;
+    /**
+     * Seller of the ask the offer was made on
+     */
+    seller: Addr;
+    token_id: string;
+}
+export interface OfferHookMsg {
+    [k: string]: unknown;
+    offer: Offer;
+}
+/**
+ * Offset for offer pagination by seller
+ */
+export interface OfferOffset {
+    [k: string]: unknown;
+    buyer: string;
+    collection: string;
+    token_id: string;
+}
+export interface SudoParams {
+    [k: string]: unknown;
+    /**
+     * Valid time range for Asks (min, max) in seconds
+     */
+    ask_expiry: ExpiryRange;
+    /**
+     * Valid time range for Bids (min, max) in seconds
+     */
+    bid_expiry: ExpiryRange;
+    create_collection_address: string;
+    /**
+     * Receives the protocol fee, no fee is charged while unset
+     */
+    fee_recipient?: (Addr | null);
+    /**
+     * Listing fee to reduce spam
+     */
+    listing_fee: Uint128;
+    /**
+     * Royalty rates reported by collections are capped at this rate
+     */
+    max_royalty_rate?: (Decimal | null);
+    /**
+     * Max value for the finders fee Min value for a bid
+     */
+    min_price: Uint128;
+    /**
+     * Operators are entites that are responsible for maintaining the active state of Asks They listen to NFT transfer events, and update the active state of Asks
+     */
+    operators: Addr[];
+    /**
+     * When set, allowlisted collections must also pass the factory `CheckCollection` query
+     */
+    require_factory_check?: boolean;
+    /**
+     * Protocol fee for winning bids, paid to `fee_recipient`
+     */
+    trading_fee_percent?: Decimal;
+}
+export interface RoyaltyPayout {
     [k: string]: unknown;
     amount: Uint128;
-    denom: string;
+    recipient: Addr;
 }
 /**
- * Duration is a delta of time. You can add it to a BlockInfo or Expiration to move that further in the future. Note that an height-based Duration and a time-based Expiration cannot be combined
+ * Offset for trait bid pagination by price
  */
-export type Duration = ({
-    height: number
-    } | {
-    time: number
-    });
-export interface ExpiryRange {
+export interface TraitBidOffset {
     [k: string]: unknown;
-    max: number;
-    min: number;
+    id: number;
+    price: Uint128;
+}
+/**
+ * How a settled sale came about, reported to the v2 sale hooks
+ */
+export type SaleOrigin = ("fixed_price" | "auction" | "offer" | "trait_bid" | "signed_order");
+export interface SaleHookMsg {
+    [k: string]: unknown;
+    buyer: string;
+    collection: string;
+    price: Coin;
+    seller: string;
+    token_id: string;
+}
+/**
+ * Sale hook payload describing how the paid amount was settled
+ */
+export interface SaleHookMsgV2 {
+    [k: string]: unknown;
+    buyer: string;
+    collection: string;
+    /**
+     * Zero until finder fees are supported, part of the payload so v2 stays stable once they are
+     */
+    finders_fee: Uint128;
+    origin: SaleOrigin;
+    /**
+     * What the buyer paid, the winning bid for auctions
+     */
+    price: Coin;
+    protocol_fee: Uint128;
+    royalties: RoyaltyPayout[];
+    seller: string;
+    /**
+     * What the seller, or the ask's funds recipient, received
+     */
+    seller_amount: Uint128;
+    timestamp: Timestamp;
+    token_id: string;
+}
+/**
+ * Represents an offer to swap escrowed NFTs, and optionally funds, for a set of NFTs
+ */
+export interface Swap {
+    [k: string]: unknown;
+    /**
+     * Only this address may accept the swap if set
+     */
+    counterparty?: (Addr | null);
+    expires_at: Timestamp;
+    id: number;
+    offered: NftItem[];
+    offered_funds: Uint128;
+    proposer: Addr;
+    requested: NftItem[];
+}
+export interface SwapHookMsg {
+    [k: string]: unknown;
+    swap: Swap;
+}
+/**
+ * Represents an escrowed bid on any NFT of a collection with the trait `trait_type`: `value`
+ */
+export interface TraitBid {
+    [k: string]: unknown;
+    bidder: Addr;
+    collection: Addr;
+    expires_at: Timestamp;
+    id: number;
+    price: Uint128;
+    trait_type: string;
+    value: string;
+}
+export interface TraitBidHookMsg {
+    [k: string]: unknown;
+    trait_bid: TraitBid;
 }
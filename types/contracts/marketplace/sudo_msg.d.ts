@@ -1,19 +1,67 @@
-import { ExpiryRange, Uint128 } from "./shared-types";
+import { CollectionConfig, Decimal, ExpiryRange, Uint128 } from "./shared-types";
 
 export type SudoMsg = ({
 update_params: {
 ask_expiry?: (ExpiryRange | null)
 bid_expiry?: (ExpiryRange | null)
-bid_removal_reward_bps?: (number | null)
+fee_recipient?: (string | null)
 listing_fee?: (Uint128 | null)
-max_finders_fee_bps?: (number | null)
+max_royalty_rate?: (Decimal | null)
 min_price?: (Uint128 | null)
 operators?: (string[] | null)
-stale_bid_duration?: (number | null)
+/**
+ * Removes the fee recipient, which stops the protocol fee. Takes precedence over `fee_recipient`
+ */
+remove_fee_recipient?: boolean
+/**
+ * Removes the royalty cap, takes precedence over `max_royalty_rate`
+ */
+remove_max_royalty_rate?: boolean
+require_factory_check?: (boolean | null)
 trading_fee_bps?: (number | null)
 [k: string]: unknown
 }
 } | {
+allow_collection: {
+collection: string
+[k: string]: unknown
+}
+} | {
+deny_collection: {
+collection: string
+[k: string]: unknown
+}
+} | {
+remove_collection: {
+collection: string
+[k: string]: unknown
+}
+} | {
+enable_collection: {
+collection: string
+[k: string]: unknown
+}
+} | {
+disable_collection: {
+collection: string
+reason: string
+[k: string]: unknown
+}
+} | {
+set_collection_config: {
+collection: string
+config: CollectionConfig
+[k: string]: unknown
+}
+} | {
+set_pause: {
+bidding: boolean
+listing: boolean
+removal: boolean
+settlement: boolean
+[k: string]: unknown
+}
+} | {
 add_operator: {
 operator: string
 [k: string]: unknown
@@ -53,4 +101,57 @@ remove_sale_hook: {
 hook: string
 [k: string]: unknown
 }
+} | {
+add_sale_hook_v2: {
+hook: string
+[k: string]: unknown
+}
+} | {
+remove_sale_hook_v2: {
+hook: string
+[k: string]: unknown
+}
+} | {
+add_swap_hook: {
+hook: string
+[k: string]: unknown
+}
+} | {
+remove_swap_hook: {
+hook: string
+[k: string]: unknown
+}
+} | {
+add_offer_hook: {
+hook: string
+[k: string]: unknown
+}
+} | {
+remove_offer_hook: {
+hook: string
+[k: string]: unknown
+}
+} | {
+add_trait_bid_hook: {
+hook: string
+[k: string]: unknown
+}
+} | {
+remove_trait_bid_hook: {
+hook: string
+[k: string]: unknown
+}
+} | {
+recover_surplus: {
+amount?: (Uint128 | null)
+recipient: string
+[k: string]: unknown
+}
+} | {
+rescue_nft: {
+collection: string
+recipient: string
+token_id: string
+[k: string]: unknown
+}
 })
@@ -0,0 +1,9 @@
+import { SwapHookMsg } from "./shared-types";
+
+export type SwapHookExecuteMsg = ({
+swap_created_hook: SwapHookMsg
+} | {
+swap_updated_hook: SwapHookMsg
+} | {
+swap_deleted_hook: SwapHookMsg
+})
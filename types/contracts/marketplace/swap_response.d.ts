@@ -0,0 +1,6 @@
+import { Swap } from "./shared-types";
+
+export interface SwapResponse {
+swap?: (Swap | null)
+[k: string]: unknown
+}
@@ -0,0 +1,6 @@
+import { Swap } from "./shared-types";
+
+export interface SwapsResponse {
+swaps: Swap[]
+[k: string]: unknown
+}
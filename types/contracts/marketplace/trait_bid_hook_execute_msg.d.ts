@@ -0,0 +1,9 @@
+import { TraitBidHookMsg } from "./shared-types";
+
+export type TraitBidHookExecuteMsg = ({
+trait_bid_created_hook: TraitBidHookMsg
+} | {
+trait_bid_updated_hook: TraitBidHookMsg
+} | {
+trait_bid_deleted_hook: TraitBidHookMsg
+})
@@ -0,0 +1,6 @@
+import { TraitBid } from "./shared-types";
+
+export interface TraitBidResponse {
+bid?: (TraitBid | null)
+[k: string]: unknown
+}
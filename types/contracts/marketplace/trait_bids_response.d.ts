@@ -0,0 +1,6 @@
+import { TraitBid } from "./shared-types";
+
+export interface TraitBidsResponse {
+bids: TraitBid[]
+[k: string]: unknown
+}